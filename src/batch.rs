@@ -0,0 +1,156 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Several statistics of one slice, sharing intermediate
+//! work: the input is sorted at most once and summed at most
+//! once however many statistics are requested.
+
+use alloc::vec::Vec;
+use core::cell::OnceCell;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::{lookup, quantile_sorted, sorted, welford, QuantileMethod};
+
+/// A slice prepared for computing several statistics by
+/// name. The sorted copy and the moments of the input are
+/// computed the first time a statistic needs them.
+///
+/// Besides the names in the [`registry`](crate::registry),
+/// the `q1`, `q3` and percentile names are understood: `p`
+/// and a whole percent from 0 to 100, so `p95` is the 95th
+/// percentile, with any decimals after an underscore, so
+/// `p99_9` is the 99.9th. Other names starting `p` and a
+/// digit, such as `p101` or `p999`, are unknown.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::batch::*;
+/// let data: Vec<f64> = (0..=100).map(f64::from).collect();
+/// let batch = Batch::new(&data);
+/// assert_eq!(Some(50.0), batch.get("mean"));
+/// assert_eq!(Some(95.0), batch.get("p95"));
+/// assert_eq!(Some(99.9), batch.get("p99_9"));
+/// assert_eq!((Some(5.0), Some(100.0)), (batch.get("p5"), batch.get("p100")));
+/// assert_eq!(None, batch.get("p999"));
+/// assert_eq!(None, batch.get("bogus"));
+/// ```
+pub struct Batch<'a> {
+    nums: &'a [f64],
+    sorted: OnceCell<Vec<f64>>,
+    moments: OnceCell<(f64, f64)>,
+}
+
+impl<'a> Batch<'a> {
+    /// Prepare input values.
+    pub fn new(nums: &'a [f64]) -> Batch<'a> {
+        Batch {
+            nums,
+            sorted: OnceCell::new(),
+            moments: OnceCell::new(),
+        }
+    }
+
+    fn sorted(&self) -> &[f64] {
+        self.sorted.get_or_init(|| sorted(self.nums))
+    }
+
+    /// Mean and sum of squared deviations, by Welford's
+    /// algorithm.
+    fn moments(&self) -> (f64, f64) {
+        *self.moments.get_or_init(|| {
+            let (_, mean, m2) = welford(self.nums.iter().copied());
+            (mean, m2)
+        })
+    }
+
+    fn variance_ddof(&self, ddof: usize) -> Option<f64> {
+        let n = self.nums.len();
+        if n <= ddof {
+            return None;
+        }
+        Some(self.moments().1 / (n - ddof) as f64)
+    }
+
+    fn quantile(&self, q: f64) -> Option<f64> {
+        quantile_sorted(self.sorted(), q, QuantileMethod::Linear)
+    }
+
+    /// The statistic with the given name, or `None` if it is
+    /// undefined for this input or the name is unknown.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        match name {
+            "mean" => Some(self.moments().0),
+            "variance" => self.variance_ddof(0),
+            "svariance" => self.variance_ddof(1),
+            "stddev" => self.variance_ddof(0).map(f64::sqrt),
+            "sstddev" => self.variance_ddof(1).map(f64::sqrt),
+            "min" => self.quantile(0.0),
+            "q1" => self.quantile(0.25),
+            "median" => self.quantile(0.5),
+            "q3" => self.quantile(0.75),
+            "max" => self.quantile(1.0),
+            "iqr" => Some(self.quantile(0.75)? - self.quantile(0.25)?),
+            "range" => Some(self.quantile(1.0)? - self.quantile(0.0)?),
+            _ => match percentile(name) {
+                Some(q) => self.quantile(q),
+                None => lookup(name)?(self.nums),
+            },
+        }
+    }
+}
+
+/// Quantile named by a percentile name such as `p95` or
+/// `p99_9`, or `None` if the name is not one.
+fn percentile(name: &str) -> Option<f64> {
+    let digits = name.strip_prefix('p')?;
+    let (whole, frac) = match digits.split_once('_') {
+        Some((whole, frac)) => (whole, Some(frac)),
+        None => (digits, None),
+    };
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_number(whole) || !frac.is_none_or(is_number) {
+        return None;
+    }
+    let percent: f64 = match frac {
+        Some(frac) => [whole, ".", frac].concat().parse().ok()?,
+        None => whole.parse().ok()?,
+    };
+    if percent > 100.0 {
+        return None;
+    }
+    Some(percent / 100.0)
+}
+
+/// Compute several named statistics of a slice at once,
+/// sharing intermediate work through a [`Batch`]. The result
+/// is a value of a struct with one `Option<f64>` field per
+/// requested statistic, named after it.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::describe;
+/// let data = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+/// let d = describe!(&data, mean, stddev, p75, max);
+/// assert_eq!(Some(3.875), d.mean);
+/// assert_eq!(Some(5.25), d.p75);
+/// assert_eq!(Some(9.0), d.max);
+/// ```
+#[macro_export]
+macro_rules! describe {
+    ($data:expr, $($stat:ident),+ $(,)?) => {{
+        let batch = $crate::batch::Batch::new($data);
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        struct Described {
+            $($stat: Option<f64>),+
+        }
+        Described {
+            $($stat: batch.get(stringify!($stat))),+
+        }
+    }};
+}
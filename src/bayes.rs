@@ -0,0 +1,134 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Bayesian inference with conjugate priors, whose
+//! posteriors are of the same family as the priors and so
+//! have closed forms.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::intervals::half_alpha;
+use crate::special::student_t_quantile;
+use crate::{has_nan, mean};
+
+/// Normal-inverse-gamma distribution of the mean `μ` and
+/// variance `σ²` of normally-distributed values: `σ²` has
+/// the inverse-gamma distribution of shape `alpha` and scale
+/// `beta`, and given `σ²`, `μ` is normal with mean `mu` and
+/// variance `σ² / kappa`. It is the conjugate prior for
+/// normal values of unknown mean and variance.
+///
+/// `mu` is the prior guess at the mean, worth `kappa` values
+/// of evidence; `beta / alpha` is roughly the prior guess at
+/// the variance, worth `2 alpha` values. Small `kappa`,
+/// `alpha` and `beta` make a vague prior that the data
+/// quickly outweigh.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::bayes::*;
+/// let prior = NormalInverseGamma::new(0.0, 1.0, 1.0, 1.0).unwrap();
+/// let posterior = prior.posterior(&[1.0, 2.0, 3.0]).unwrap();
+/// assert_eq!(1.5, posterior.mu());
+/// assert_eq!((4.0, 2.5, 3.5), (posterior.kappa(), posterior.alpha(), posterior.beta()));
+/// let (low, high) = posterior.mean_credible_interval(0.95).unwrap();
+/// let margin = 2.570581835636314 * 0.35f64.sqrt();
+/// assert!((low - (1.5 - margin)).abs() < 1e-12);
+/// assert!((high - (1.5 + margin)).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalInverseGamma {
+    mu: f64,
+    kappa: f64,
+    alpha: f64,
+    beta: f64,
+}
+
+impl NormalInverseGamma {
+    /// Normal-inverse-gamma distribution with the given
+    /// parameters, or `None` unless `mu` is finite and the
+    /// others finite and positive.
+    pub fn new(mu: f64, kappa: f64, alpha: f64, beta: f64) -> Option<NormalInverseGamma> {
+        let positive = |x: f64| x.is_finite() && x > 0.0;
+        if !mu.is_finite() || !positive(kappa) || !positive(alpha) || !positive(beta) {
+            return None;
+        }
+        Some(NormalInverseGamma {
+            mu,
+            kappa,
+            alpha,
+            beta,
+        })
+    }
+
+    /// The prior guess at the mean.
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// The number of values the guess at the mean is worth.
+    pub fn kappa(&self) -> f64 {
+        self.kappa
+    }
+
+    /// The shape of the distribution of the variance.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The scale of the distribution of the variance.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Posterior distribution given input values, taking
+    /// this distribution as the prior. An empty list leaves
+    /// the distribution as it is; values containing NaN or
+    /// infinities give `None`.
+    pub fn posterior(&self, nums: &[f64]) -> Option<NormalInverseGamma> {
+        if nums.is_empty() {
+            return Some(*self);
+        }
+        if has_nan(nums) {
+            return None;
+        }
+        let n = nums.len() as f64;
+        let m = mean(nums)?;
+        let squares: f64 = nums.iter().map(|x| (x - m) * (x - m)).sum();
+        let kappa = self.kappa + n;
+        let shift = m - self.mu;
+        NormalInverseGamma::new(
+            (self.kappa * self.mu + n * m) / kappa,
+            kappa,
+            self.alpha + 0.5 * n,
+            self.beta + 0.5 * squares + 0.5 * self.kappa * n * shift * shift / kappa,
+        )
+    }
+
+    /// Expected variance, `beta / (alpha - 1)`, or `None`
+    /// for `alpha` at most 1.0, where it is infinite.
+    pub fn expected_variance(&self) -> Option<f64> {
+        if self.alpha <= 1.0 {
+            return None;
+        }
+        Some(self.beta / (self.alpha - 1.0))
+    }
+
+    /// Equal-tailed credible interval for the mean `μ` with
+    /// probability `level` (such as 0.95). The marginal
+    /// distribution of `μ` is Student's t on `2 alpha`
+    /// degrees of freedom, centered on `mu` with scale
+    /// `√(beta / (alpha kappa))`. `None` for a `level`
+    /// outside `0.0..1.0`.
+    pub fn mean_credible_interval(&self, level: f64) -> Option<(f64, f64)> {
+        let tail = half_alpha(level)?;
+        let scale = (self.beta / (self.alpha * self.kappa)).sqrt();
+        let margin = student_t_quantile(1.0 - tail, 2.0 * self.alpha) * scale;
+        Some((self.mu - margin, self.mu + margin))
+    }
+}
@@ -0,0 +1,252 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! `Result`-returning counterparts of the statistics
+//! functions, reporting why a statistic is undefined.
+//!
+//! Each `try_` function returns `Ok` exactly when its
+//! `Option`-returning counterpart would return `Some` for
+//! NaN-free input, with the same value. Input containing a
+//! NaN is rejected with [`StatsError::ContainsNaN`].
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::StatsError;
+
+/// Reject input containing a NaN.
+fn check_nan(nums: &[f64]) -> Result<(), StatsError> {
+    if nums.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::ContainsNaN);
+    }
+    Ok(())
+}
+
+/// Require at least `needed` values.
+fn check_len(nums: &[f64], needed: usize) -> Result<(), StatsError> {
+    check_nan(nums)?;
+    match nums.len() {
+        0 if needed > 0 => Err(StatsError::EmptyInput),
+        found if found < needed => Err(StatsError::InsufficientData { needed, found }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a quantile is in `[0, 1]`.
+fn check_q(q: f64) -> Result<(), StatsError> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(StatsError::InvalidParameter("q"));
+    }
+    Ok(())
+}
+
+/// See [`mean`](crate::mean).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ContainsNaN), try_mean(&[1.0, f64::NAN]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(2.0), try_mean(&[1.0, 3.0]));
+/// ```
+pub fn try_mean(nums: &[f64]) -> Result<f64, StatsError> {
+    check_nan(nums)?;
+    Ok(crate::mean(nums).unwrap_or(0.0))
+}
+
+/// See [`variance_ddof`](crate::variance_ddof).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let err = StatsError::InsufficientData { needed: 3, found: 2 };
+/// assert_eq!(Err(err), try_variance_ddof(&[1.0, 2.0], 2));
+/// ```
+pub fn try_variance_ddof(nums: &[f64], ddof: usize) -> Result<f64, StatsError> {
+    check_len(nums, ddof + 1)?;
+    crate::variance_ddof(nums, ddof).ok_or(StatsError::EmptyInput)
+}
+
+/// See [`variance`](crate::variance).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::EmptyInput), try_variance(&[]));
+/// ```
+pub fn try_variance(nums: &[f64]) -> Result<f64, StatsError> {
+    try_variance_ddof(nums, 0)
+}
+
+/// See [`sample_variance`](crate::sample_variance).
+pub fn try_sample_variance(nums: &[f64]) -> Result<f64, StatsError> {
+    try_variance_ddof(nums, 1)
+}
+
+/// See [`stddev`](crate::stddev).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Ok(1.0), try_stddev(&[1.0, 3.0]));
+/// ```
+pub fn try_stddev(nums: &[f64]) -> Result<f64, StatsError> {
+    try_variance(nums).map(f64::sqrt)
+}
+
+/// See [`sample_stddev`](crate::sample_stddev).
+pub fn try_sample_stddev(nums: &[f64]) -> Result<f64, StatsError> {
+    try_sample_variance(nums).map(f64::sqrt)
+}
+
+/// Moment-based shape statistic requiring `needed` values and
+/// nonzero variance.
+fn try_shape(
+    nums: &[f64],
+    needed: usize,
+    stat: fn(&[f64]) -> Option<f64>,
+) -> Result<f64, StatsError> {
+    check_len(nums, needed)?;
+    stat(nums).ok_or(StatsError::ZeroVariance)
+}
+
+/// See [`skewness`](crate::skewness).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ZeroVariance), try_skewness(&[2.0, 2.0]));
+/// ```
+pub fn try_skewness(nums: &[f64]) -> Result<f64, StatsError> {
+    try_shape(nums, 1, crate::skewness)
+}
+
+/// See [`sample_skewness`](crate::sample_skewness).
+pub fn try_sample_skewness(nums: &[f64]) -> Result<f64, StatsError> {
+    try_shape(nums, 3, crate::sample_skewness)
+}
+
+/// See [`kurtosis`](crate::kurtosis).
+pub fn try_kurtosis(nums: &[f64]) -> Result<f64, StatsError> {
+    try_shape(nums, 1, crate::kurtosis)
+}
+
+/// See [`excess_kurtosis`](crate::excess_kurtosis).
+pub fn try_excess_kurtosis(nums: &[f64]) -> Result<f64, StatsError> {
+    try_shape(nums, 1, crate::excess_kurtosis)
+}
+
+/// See [`sample_excess_kurtosis`](crate::sample_excess_kurtosis).
+pub fn try_sample_excess_kurtosis(nums: &[f64]) -> Result<f64, StatsError> {
+    try_shape(nums, 4, crate::sample_excess_kurtosis)
+}
+
+/// See [`quantile_with`](crate::quantile_with).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let err = StatsError::InvalidParameter("q");
+/// assert_eq!(Err(err), try_quantile_with(&[1.0], -0.5, QuantileMethod::Linear));
+/// ```
+pub fn try_quantile_with(
+    nums: &[f64],
+    q: f64,
+    method: crate::QuantileMethod,
+) -> Result<f64, StatsError> {
+    check_len(nums, 1)?;
+    check_q(q)?;
+    crate::quantile_with(nums, q, method).ok_or(StatsError::EmptyInput)
+}
+
+/// See [`quantile`](crate::quantile).
+pub fn try_quantile(nums: &[f64], q: f64) -> Result<f64, StatsError> {
+    try_quantile_with(nums, q, crate::QuantileMethod::Linear)
+}
+
+/// See [`median`](crate::median).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Err(StatsError::ContainsNaN), try_median(&[f64::NAN, 1.0]));
+/// ```
+pub fn try_median(nums: &[f64]) -> Result<f64, StatsError> {
+    try_quantile(nums, 0.5)
+}
+
+/// See [`five_number_summary`](crate::five_number_summary).
+pub fn try_five_number_summary(nums: &[f64]) -> Result<crate::FiveNumSummary, StatsError> {
+    check_len(nums, 1)?;
+    crate::five_number_summary(nums).ok_or(StatsError::EmptyInput)
+}
+
+/// See [`iqr`](crate::iqr).
+pub fn try_iqr(nums: &[f64]) -> Result<f64, StatsError> {
+    try_five_number_summary(nums).map(|s| s.iqr())
+}
+
+/// Trimming or winsorizing statistic: check the input and
+/// the proportion.
+fn try_tails(
+    nums: &[f64],
+    proportion: f64,
+    stat: fn(&[f64], f64) -> Option<f64>,
+) -> Result<f64, StatsError> {
+    check_len(nums, 1)?;
+    if !(0.0..0.5).contains(&proportion) {
+        return Err(StatsError::InvalidParameter("proportion"));
+    }
+    stat(nums, proportion).ok_or(StatsError::EmptyInput)
+}
+
+/// See [`trimmed_mean`](crate::trimmed_mean).
+pub fn try_trimmed_mean(nums: &[f64], proportion: f64) -> Result<f64, StatsError> {
+    try_tails(nums, proportion, crate::trimmed_mean)
+}
+
+/// See [`winsorized_mean`](crate::winsorized_mean).
+pub fn try_winsorized_mean(nums: &[f64], proportion: f64) -> Result<f64, StatsError> {
+    try_tails(nums, proportion, crate::winsorized_mean)
+}
+
+/// See [`modes`](crate::modes).
+pub fn try_modes(nums: &[f64], tolerance: f64) -> Result<Vec<f64>, StatsError> {
+    check_len(nums, 1)?;
+    if tolerance.is_nan() || tolerance < 0.0 {
+        return Err(StatsError::InvalidParameter("tolerance"));
+    }
+    crate::modes(nums, tolerance).ok_or(StatsError::EmptyInput)
+}
+
+/// See [`mode`](crate::mode).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let err = StatsError::InvalidParameter("tolerance");
+/// assert_eq!(Err(err), try_mode(&[1.0], -1.0));
+/// ```
+pub fn try_mode(nums: &[f64], tolerance: f64) -> Result<f64, StatsError> {
+    Ok(try_modes(nums, tolerance)?[0])
+}
+
+/// See [`l2`](crate::l2).
+pub fn try_l2(nums: &[f64]) -> Result<f64, StatsError> {
+    check_nan(nums)?;
+    Ok(crate::l2(nums).unwrap_or(0.0))
+}
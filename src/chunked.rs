@@ -0,0 +1,285 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics of values that arrive as a sequence of
+//! slices, such as the two halves of a ring buffer or a
+//! series of packet payloads, without first copying them
+//! into one contiguous list.
+//!
+//! Each function takes anything that iterates over slices
+//! and gives the same result, up to rounding, as the
+//! function of the same name in the crate root applied to
+//! the slices laid end to end. Except for the quantiles,
+//! each makes one pass over the chunks; none copies the
+//! values.
+
+use alloc::vec::Vec;
+
+use num_traits::Float;
+
+use crate::random::Rng;
+use crate::{float, interpolate, pairwise, quantile_position, Neumaier, QuantileMethod};
+
+/// Count, mean and sum of squared deviations from the mean
+/// of the chunks, combining per-chunk moments by Chan et
+/// al.'s parallel algorithm.
+fn moments<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> (usize, T, T) {
+    //https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Parallel_algorithm
+    let (mut count, mut mean, mut m2) = (0, T::zero(), Neumaier::new());
+    for chunk in chunks {
+        if chunk.is_empty() {
+            continue;
+        }
+        let n = chunk.len();
+        let chunk_mean = crate::sum(chunk) / float(n);
+        let chunk_m2 = crate::variance_ddof(chunk, 0).unwrap() * float(n);
+        let total = count + n;
+        let delta = chunk_mean - mean;
+        mean = mean + delta * float(n) / float(total);
+        m2.add(chunk_m2);
+        m2.add(delta * delta * float(count) * float(n) / float(total));
+        count = total;
+    }
+    (count, mean, m2.total())
+}
+
+/// Sum of the chunks' values. See [`sum`](crate::sum).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// let (front, back) = ([0.1; 6], [0.1; 4]);
+/// assert_eq!(1.0, chunked::sum([&front[..], &back[..]]));
+/// ```
+pub fn sum<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> T {
+    let mut total = Neumaier::new();
+    for chunk in chunks {
+        total.merge(pairwise(chunk, |x| x));
+    }
+    total.total()
+}
+
+/// Arithmetic mean of the chunks' values; 0.0 for no values.
+/// See [`mean`](crate::mean).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// let packets = vec![vec![1.0, 2.0], vec![], vec![3.0, 4.0, 5.0]];
+/// assert_eq!(Some(3.0), chunked::mean(packets.iter().map(Vec::as_slice)));
+/// ```
+pub fn mean<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    let (mut count, mut total) = (0, Neumaier::new());
+    for chunk in chunks {
+        count += chunk.len();
+        total.merge(pairwise(chunk, |x| x));
+    }
+    if count == 0 {
+        return Some(T::zero());
+    }
+    Some(total.total() / float(count))
+}
+
+/// Variance of the chunks' values with `ddof` delta degrees
+/// of freedom. See [`variance_ddof`](crate::variance_ddof).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// assert_eq!(None, chunked::variance_ddof([&[1.0][..], &[]], 1));
+/// ```
+/// ```
+/// # use stats::chunked;
+/// let (front, back) = ([1e9 + 4.0, 1e9 + 7.0], [1e9 + 13.0, 1e9 + 16.0]);
+/// assert_eq!(Some(30.0), chunked::variance_ddof([&front[..], &back[..]], 1));
+/// ```
+pub fn variance_ddof<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(
+    chunks: I,
+    ddof: usize,
+) -> Option<T> {
+    let (count, _, m2) = moments(chunks);
+    if count <= ddof {
+        return None;
+    }
+    Some(m2 / float(count - ddof))
+}
+
+/// Population variance of the chunks' values. See
+/// [`variance`](crate::variance).
+pub fn variance<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    variance_ddof(chunks, 0)
+}
+
+/// Sample variance of the chunks' values. See
+/// [`sample_variance`](crate::sample_variance).
+pub fn sample_variance<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    variance_ddof(chunks, 1)
+}
+
+/// Population standard deviation of the chunks' values. See
+/// [`stddev`](crate::stddev).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// assert_eq!(Some(2.0), chunked::stddev([&[2.0, 4.0, 4.0][..], &[4.0, 5.0, 5.0, 7.0, 9.0]]));
+/// ```
+pub fn stddev<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    variance(chunks).map(T::sqrt)
+}
+
+/// Sample standard deviation of the chunks' values. See
+/// [`sample_stddev`](crate::sample_stddev).
+pub fn sample_stddev<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    sample_variance(chunks).map(T::sqrt)
+}
+
+/// L2 norm of the chunks' values. See [`l2`](crate::l2).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// assert_eq!(Some(5.0), chunked::l2([&[3.0][..], &[-4.0]]));
+/// ```
+pub fn l2<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    let mut total = Neumaier::new();
+    for chunk in chunks {
+        total.merge(pairwise(chunk, |x| x * x));
+    }
+    Some(total.total().sqrt())
+}
+
+/// Smallest of the chunks' values. See
+/// [`min`](crate::extrema::min).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// assert_eq!(Some(-2.0), chunked::min([&[1.0][..], &[], &[-2.0, 3.0]]));
+/// ```
+pub fn min<'a, I: IntoIterator<Item = &'a [f64]>>(chunks: I) -> Option<f64> {
+    extreme(chunks, crate::extrema::min, f64::min)
+}
+
+/// Largest of the chunks' values. See
+/// [`max`](crate::extrema::max).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// assert_eq!(None, chunked::max([&[1.0][..], &[f64::NAN]]));
+/// ```
+pub fn max<'a, I: IntoIterator<Item = &'a [f64]>>(chunks: I) -> Option<f64> {
+    extreme(chunks, crate::extrema::max, f64::max)
+}
+
+/// Combine the extreme value of each non-empty chunk,
+/// failing if any chunk has none.
+fn extreme<'a, I: IntoIterator<Item = &'a [f64]>>(
+    chunks: I,
+    of: fn(&[f64]) -> Option<f64>,
+    keep: fn(f64, f64) -> f64,
+) -> Option<f64> {
+    let mut best = None;
+    for chunk in chunks.into_iter().filter(|c| !c.is_empty()) {
+        let x = of(chunk)?;
+        best = Some(best.map_or(x, |b| keep(b, x)));
+    }
+    best
+}
+
+/// Quantile `q` of the chunks' values using the given
+/// interpolation `method`. See
+/// [`quantile_with`](crate::quantile_with). Rather than
+/// copying the values into one list, this selects the order
+/// statistics it needs by repeated passes over the chunks,
+/// each narrowing the range of candidates around a value
+/// chosen from them at random, so it takes expected
+/// `O(n log n)` time but no memory for the values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// # use stats::QuantileMethod;
+/// let halves = [&[4.0, 1.0][..], &[3.0, 2.0]];
+/// assert_eq!(Some(2.5), chunked::quantile_with(halves, 0.5, QuantileMethod::Linear));
+/// let parts = [&[9.0, 1.0, 5.0][..], &[], &[5.0, 7.0, 5.0, 2.0]];
+/// assert_eq!(Some(7.0), chunked::quantile_with(parts, 0.8, QuantileMethod::Higher));
+/// ```
+pub fn quantile_with<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(
+    chunks: I,
+    q: f64,
+    method: QuantileMethod,
+) -> Option<T> {
+    let chunks: Vec<&[T]> = chunks.into_iter().collect();
+    let values = || chunks.iter().flat_map(|c| c.iter().copied());
+    let n = chunks.iter().map(|c| c.len()).sum();
+    if n == 0 || values().any(|x| x.is_nan()) || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    let (lo, t) = quantile_position(n, q, method);
+    let x = select(&chunks, lo);
+    Some(interpolate(method, x, || select(&chunks, lo + 1), t))
+}
+
+/// The value of rank `k` among the chunks' values, which
+/// contain no NaN, by quickselect without moving them: the
+/// candidates are the values strictly between two bounds,
+/// and each round counts those either side of a random
+/// candidate and moves one of the bounds to it.
+fn select<T: Float>(chunks: &[&[T]], k: usize) -> T {
+    let values = || chunks.iter().flat_map(|c| c.iter().copied());
+    let mut rng = Rng::new(k as u64);
+    let (mut low, mut high) = (None, None);
+    //number of values at or below low
+    let mut below = 0;
+    loop {
+        let is_candidate = |x: T| low.is_none_or(|l| x > l) && high.is_none_or(|h| x < h);
+        //reservoir sample of one candidate
+        let (mut seen, mut pivot) = (0, T::zero());
+        for x in values().filter(|&x| is_candidate(x)) {
+            seen += 1;
+            if rng.below(seen) == 0 {
+                pivot = x;
+            }
+        }
+        let (mut less, mut equal) = (0, 0);
+        for x in values().filter(|&x| is_candidate(x)) {
+            if x < pivot {
+                less += 1;
+            } else if x == pivot {
+                equal += 1;
+            }
+        }
+        if k < below + less {
+            high = Some(pivot);
+        } else if k < below + less + equal {
+            return pivot;
+        } else {
+            below += less + equal;
+            low = Some(pivot);
+        }
+    }
+}
+
+/// Median of the chunks' values. See [`median`](crate::median).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::chunked;
+/// assert_eq!(Some(3.0), chunked::median([&[5.0, 1.0][..], &[3.0]]));
+/// ```
+pub fn median<'a, T: Float + 'a, I: IntoIterator<Item = &'a [T]>>(chunks: I) -> Option<T> {
+    quantile_with(chunks, 0.5, QuantileMethod::Linear)
+}
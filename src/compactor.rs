@@ -0,0 +1,91 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Approximate quantiles of a stream of values in bounded
+//! memory.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::nan_last;
+
+/// Hierarchy of compactors: level `i` holds values that each
+/// stand for `2^i` input values. When a level fills up it is
+/// sorted and every other value is promoted to the next
+/// level, alternating which half survives so that the rank
+/// errors tend to cancel. The memory used grows only with the
+/// logarithm of the number of values.
+pub(crate) struct Compactor {
+    capacity: usize,
+    levels: Vec<Vec<f64>>,
+    odd: bool,
+}
+
+impl Compactor {
+    /// Empty compactor whose levels hold up to `capacity`
+    /// values each.
+    pub(crate) fn new(capacity: usize) -> Compactor {
+        Compactor {
+            capacity,
+            levels: vec![Vec::with_capacity(capacity)],
+            odd: false,
+        }
+    }
+
+    pub(crate) fn push(&mut self, x: f64) {
+        self.levels[0].push(x);
+        let mut level = 0;
+        while self.levels[level].len() >= self.capacity {
+            self.compact(level);
+            level += 1;
+        }
+    }
+
+    /// Promote every other value of a full `level`.
+    fn compact(&mut self, level: usize) {
+        if level + 1 == self.levels.len() {
+            self.levels.push(Vec::with_capacity(self.capacity));
+        }
+        let mut full = core::mem::take(&mut self.levels[level]);
+        full.sort_unstable_by(nan_last);
+        let start = usize::from(self.odd);
+        self.odd = !self.odd;
+        self.levels[level + 1].extend(full.iter().skip(start).step_by(2));
+        full.clear();
+        self.levels[level] = full;
+    }
+
+    /// All the values pushed, sorted, if none have been
+    /// compacted away.
+    pub(crate) fn exact(&self) -> Option<Vec<f64>> {
+        if self.levels.len() > 1 {
+            return None;
+        }
+        let mut values = self.levels[0].clone();
+        values.sort_unstable_by(nan_last);
+        Some(values)
+    }
+
+    /// Approximate quantile `q` of the values pushed, found
+    /// from the weighted ranks of the retained values.
+    pub(crate) fn quantile(&self, q: f64) -> Option<f64> {
+        let mut weighted: Vec<(f64, f64)> = Vec::new();
+        for (level, values) in self.levels.iter().enumerate() {
+            let weight = (1u64 << level) as f64;
+            weighted.extend(values.iter().map(|&x| (x, weight)));
+        }
+        weighted.sort_unstable_by(|a, b| nan_last(&a.0, &b.0));
+        let total: f64 = weighted.iter().map(|p| p.1).sum();
+        let target = q * total;
+        let mut cumulative = 0.0;
+        for &(x, w) in &weighted {
+            cumulative += w;
+            if cumulative > target {
+                return Some(x);
+            }
+        }
+        weighted.last().map(|p| p.0)
+    }
+}
@@ -0,0 +1,172 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! A reusable configuration of the cross-cutting options of
+//! the statistics functions.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::{
+    quantile_sorted, sorted, try_quantile_with, try_variance_ddof, NanPolicy, QuantileMethod,
+    StatsError,
+};
+
+/// Options shared by many statistics: the delta degrees of
+/// freedom for variance, the NaN policy, and the quantile
+/// interpolation method. A `StatsConfig` is built once and
+/// then applied to any number of slices. Its statistics
+/// return `Err` where the corresponding `try_` function
+/// would, and under [`NanPolicy::Propagate`] input containing
+/// NaN gives a NaN result.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let config = StatsConfig::new()
+///     .ddof(1)
+///     .nan_policy(NanPolicy::Ignore)
+///     .quantile_method(QuantileMethod::Lower);
+/// let data = [1.0, f64::NAN, 3.0, 5.0, 7.0];
+/// assert_eq!(Ok(4.0), config.mean(&data));
+/// let d = config.describe(&data).unwrap();
+/// assert_eq!((4, 4.0, 3.0), (d.count, d.mean, d.median));
+/// ```
+/// ```
+/// # use stats::*;
+/// let config = StatsConfig::new().nan_policy(NanPolicy::Error);
+/// assert_eq!(Err(StatsError::ContainsNaN), config.stddev(&[f64::NAN]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatsConfig {
+    ddof: usize,
+    nan_policy: NanPolicy,
+    quantile_method: QuantileMethod,
+}
+
+/// Descriptive statistics of a slice under a
+/// [`StatsConfig`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Description {
+    /// Number of values, after any NaNs were dropped.
+    pub count: usize,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Variance with the configured `ddof`.
+    pub variance: f64,
+    /// Standard deviation with the configured `ddof`.
+    pub stddev: f64,
+    /// Smallest value.
+    pub min: f64,
+    /// First quartile.
+    pub q1: f64,
+    /// Median.
+    pub median: f64,
+    /// Third quartile.
+    pub q3: f64,
+    /// Largest value.
+    pub max: f64,
+}
+
+impl StatsConfig {
+    /// Default configuration: population statistics (`ddof`
+    /// 0), [`NanPolicy::Propagate`] and
+    /// [`QuantileMethod::Linear`].
+    pub fn new() -> StatsConfig {
+        StatsConfig::default()
+    }
+
+    /// Set the delta degrees of freedom used for variance
+    /// and standard deviation.
+    pub fn ddof(mut self, ddof: usize) -> StatsConfig {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Set the NaN policy.
+    pub fn nan_policy(mut self, nan_policy: NanPolicy) -> StatsConfig {
+        self.nan_policy = nan_policy;
+        self
+    }
+
+    /// Set the quantile interpolation method.
+    pub fn quantile_method(mut self, quantile_method: QuantileMethod) -> StatsConfig {
+        self.quantile_method = quantile_method;
+        self
+    }
+
+    /// Apply the NaN policy, then `stat`.
+    fn apply<T, F>(&self, nums: &[f64], nan: T, stat: F) -> Result<T, StatsError>
+    where
+        F: FnOnce(&[f64]) -> Result<T, StatsError>,
+    {
+        match self.nan_policy.clean(nums)? {
+            Some(nums) => stat(&nums),
+            None => Ok(nan),
+        }
+    }
+
+    /// Arithmetic mean.
+    pub fn mean(&self, nums: &[f64]) -> Result<f64, StatsError> {
+        self.apply(nums, f64::NAN, crate::try_mean)
+    }
+
+    /// Variance with the configured `ddof`.
+    pub fn variance(&self, nums: &[f64]) -> Result<f64, StatsError> {
+        self.apply(nums, f64::NAN, |nums| try_variance_ddof(nums, self.ddof))
+    }
+
+    /// Standard deviation with the configured `ddof`.
+    pub fn stddev(&self, nums: &[f64]) -> Result<f64, StatsError> {
+        self.variance(nums).map(f64::sqrt)
+    }
+
+    /// Quantile `q` with the configured interpolation
+    /// method.
+    pub fn quantile(&self, nums: &[f64], q: f64) -> Result<f64, StatsError> {
+        self.apply(nums, f64::NAN, |nums| {
+            try_quantile_with(nums, q, self.quantile_method)
+        })
+    }
+
+    /// Median with the configured interpolation method.
+    pub fn median(&self, nums: &[f64]) -> Result<f64, StatsError> {
+        self.quantile(nums, 0.5)
+    }
+
+    /// All of the statistics in [`Description`]. Fails if the
+    /// variance would.
+    pub fn describe(&self, nums: &[f64]) -> Result<Description, StatsError> {
+        let nan = Description {
+            count: nums.len(),
+            mean: f64::NAN,
+            variance: f64::NAN,
+            stddev: f64::NAN,
+            min: f64::NAN,
+            q1: f64::NAN,
+            median: f64::NAN,
+            q3: f64::NAN,
+            max: f64::NAN,
+        };
+        self.apply(nums, nan, |nums| {
+            let variance = try_variance_ddof(nums, self.ddof)?;
+            let sorted = sorted(nums);
+            let quartile =
+                |q| quantile_sorted(&sorted, q, self.quantile_method).ok_or(StatsError::EmptyInput);
+            Ok(Description {
+                count: nums.len(),
+                mean: crate::try_mean(nums)?,
+                variance,
+                stddev: variance.sqrt(),
+                min: quartile(0.0)?,
+                q1: quartile(0.25)?,
+                median: quartile(0.5)?,
+                q3: quartile(0.75)?,
+                max: quartile(1.0)?,
+            })
+        })
+    }
+}
@@ -0,0 +1,456 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics of the relationship between two slices of
+//! paired values, `xs[i]` being paired with `ys[i]`, and
+//! between several variables given as columns of equal
+//! length.
+//!
+//! Every function here returns `None` when its slices or
+//! columns differ in length, as well as when the statistic
+//! is otherwise ill-defined.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::Float;
+
+use crate::matrix::Matrix;
+use crate::{dot, float, has_nan, pairwise, pairwise_zip, sorted, sum};
+
+/// Sums of squared and cross deviations from the means,
+/// `(Σ(x-x̄)², Σ(y-ȳ)², Σ(x-x̄)(y-ȳ))`, of two non-empty
+/// slices of equal length.
+pub(crate) fn comoments<T: Float>(xs: &[T], ys: &[T]) -> (T, T, T) {
+    //corrected two-pass algorithm, as for variance_ddof
+    let n = float::<T, _>(xs.len());
+    let (xmean, ymean) = (sum(xs) / n, sum(ys) / n);
+    let xdev = pairwise(xs, |x| x - xmean).total();
+    let ydev = pairwise(ys, |y| y - ymean).total();
+    let sxx = pairwise(xs, |x| (x - xmean).powi(2)).total() - xdev * xdev / n;
+    let syy = pairwise(ys, |y| (y - ymean).powi(2)).total() - ydev * ydev / n;
+    let sxy = pairwise_zip(xs, ys, |x, y| (x - xmean) * (y - ymean)).total() - xdev * ydev / n;
+    (sxx, syy, sxy)
+}
+
+/// Covariance of paired values with `ddof` delta degrees of
+/// freedom: the sum of products of deviations from the
+/// means divided by `n - ddof`. The covariance is undefined
+/// for `ddof` or fewer pairs.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, covariance_ddof(&[1.0, 2.0], &[1.0], 0));
+/// assert_eq!(None, covariance_ddof(&[1.0], &[1.0], 1));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// let (xs, ys) = ([1.0, 2.0, 3.0, 4.0], [2.0, 4.0, 6.0, 8.0]);
+/// assert_eq!(Some(2.5), covariance_ddof(&xs, &ys, 0));
+/// assert_eq!(Some(10.0), covariance_ddof(&xs, &ys, 3));
+/// ```
+pub fn covariance_ddof<T: Float>(xs: &[T], ys: &[T], ddof: usize) -> Option<T> {
+    let count = xs.len();
+    if count != ys.len() || count <= ddof {
+        return None;
+    }
+    let (_, _, sxy) = comoments(xs, ys);
+    Some(sxy / float(count - ddof))
+}
+
+/// Population covariance of paired values. The covariance
+/// of no pairs is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(Some(-1.0), covariance(&[1.0, 3.0], &[3.0, 1.0]));
+/// ```
+pub fn covariance<T: Float>(xs: &[T], ys: &[T]) -> Option<T> {
+    covariance_ddof(xs, ys, 0)
+}
+
+/// Sample covariance of paired values, using Bessel's
+/// correction. The sample covariance of fewer than two pairs
+/// is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// // Offsetting the values does not lose precision.
+/// let xs = [1e9 + 1.0, 1e9 + 2.0, 1e9 + 3.0];
+/// let ys = [1e9 + 6.0, 1e9 + 4.0, 1e9 + 2.0];
+/// assert_eq!(Some(-2.0), sample_covariance(&xs, &ys));
+/// ```
+pub fn sample_covariance<T: Float>(xs: &[T], ys: &[T]) -> Option<T> {
+    covariance_ddof(xs, ys, 1)
+}
+
+/// Pearson product-moment correlation coefficient of paired
+/// values, in `-1.0..=1.0`. The correlation is undefined for
+/// fewer than two pairs, or when either slice's values are
+/// all equal. It does not depend on the choice of population
+/// or sample normalization, which cancels.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, pearson(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0]));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(Some(1.0), pearson(&[1.0, 2.0, 3.0], &[10.0, 20.0, 30.0]));
+/// let r: f64 = pearson(&[1.0, 2.0, 3.0, 4.0], &[1.0, 3.0, 2.0, 4.0]).unwrap();
+/// assert!((r - 0.8).abs() < 1e-12);
+/// ```
+pub fn pearson<T: Float>(xs: &[T], ys: &[T]) -> Option<T> {
+    if xs.len() != ys.len() || xs.len() < 2 {
+        return None;
+    }
+    let (sxx, syy, sxy) = comoments(xs, ys);
+    correlation(sxx, syy, sxy)
+}
+
+/// Correlation from the [`comoments`] of two slices, or
+/// `None` if either has no spread.
+fn correlation<T: Float>(sxx: T, syy: T, sxy: T) -> Option<T> {
+    if sxx.is_nan() || syy.is_nan() || sxy.is_nan() {
+        return Some(T::nan());
+    }
+    if sxx <= T::zero() || syy <= T::zero() {
+        return None;
+    }
+    //rounding can carry |r| slightly past 1
+    let r = sxy / (sxx * syy).sqrt();
+    Some(r.max(-T::one()).min(T::one()))
+}
+
+/// Symmetric matrix whose element `(i, j)` is `f` of the
+/// [`comoments`] of `columns[i]` and `columns[j]`, or `None`
+/// unless there is at least one column and the columns have
+/// `min_len` or more values each, all of the same length.
+fn comoment_matrix<C: AsRef<[f64]>>(
+    columns: &[C],
+    min_len: usize,
+    f: impl Fn(f64, f64, f64) -> Option<f64>,
+) -> Option<Matrix> {
+    let len = columns.first()?.as_ref().len();
+    if len < min_len || columns.iter().any(|c| c.as_ref().len() != len) {
+        return None;
+    }
+    let k = columns.len();
+    let mut entries = vec![0.0; k * k];
+    for i in 0..k {
+        for j in i..k {
+            let (sxx, syy, sxy) = comoments(columns[i].as_ref(), columns[j].as_ref());
+            let entry = f(sxx, syy, sxy)?;
+            entries[i * k + j] = entry;
+            entries[j * k + i] = entry;
+        }
+    }
+    Matrix::new(k, k, entries)
+}
+
+/// Covariance matrix of several variables with `ddof` delta
+/// degrees of freedom: element `(i, j)` is the
+/// [`covariance_ddof`] of `columns[i]` and `columns[j]`, so
+/// the diagonal holds the variances. The matrix is
+/// undefined for no columns, for columns of different
+/// lengths, and for columns of `ddof` or fewer values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, cov_matrix_ddof(&[&[1.0, 2.0][..], &[1.0]], 0));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// let columns = [[1.0, 2.0, 3.0], [6.0, 4.0, 2.0]];
+/// let cov = cov_matrix_ddof(&columns, 1).unwrap();
+/// assert_eq!(&[1.0, -2.0, -2.0, 4.0], cov.as_slice());
+/// ```
+pub fn cov_matrix_ddof<C: AsRef<[f64]>>(columns: &[C], ddof: usize) -> Option<Matrix> {
+    let n = columns.first()?.as_ref().len();
+    comoment_matrix(columns, ddof + 1, |_, _, sxy| Some(sxy / (n - ddof) as f64))
+}
+
+/// Population covariance matrix of several variables. See
+/// [`cov_matrix_ddof`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// # use stats::matrix::Matrix;
+/// // Three observations of two variables, one per row.
+/// let table = Matrix::new(3, 2, vec![1.0, 6.0, 2.0, 4.0, 3.0, 2.0]).unwrap();
+/// let cov = cov_matrix(&table.columns()).unwrap();
+/// assert_eq!(-4.0 / 3.0, cov[(0, 1)]);
+/// ```
+pub fn cov_matrix<C: AsRef<[f64]>>(columns: &[C]) -> Option<Matrix> {
+    cov_matrix_ddof(columns, 0)
+}
+
+/// Sample covariance matrix of several variables. See
+/// [`cov_matrix_ddof`].
+pub fn sample_cov_matrix<C: AsRef<[f64]>>(columns: &[C]) -> Option<Matrix> {
+    cov_matrix_ddof(columns, 1)
+}
+
+/// Correlation matrix of several variables: element `(i,
+/// j)` is the [`pearson`] correlation of `columns[i]` and
+/// `columns[j]`, and the diagonal is 1.0. The matrix is
+/// undefined for no columns, for columns of different
+/// lengths or of fewer than two values, and when any
+/// column's values are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, corr_matrix(&[[1.0, 2.0], [3.0, 3.0]]));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// let corr = corr_matrix(&[[1.0, 2.0, 3.0], [6.0, 4.0, 2.0], [1.0, 3.0, 2.0]]).unwrap();
+/// assert_eq!(&[1.0, -1.0, 0.5], corr.row(0));
+/// assert_eq!(corr[(1, 2)], corr[(2, 1)]);
+/// ```
+pub fn corr_matrix<C: AsRef<[f64]>>(columns: &[C]) -> Option<Matrix> {
+    comoment_matrix(columns, 2, correlation)
+}
+
+/// Fractional ranks of input values: the smallest value has
+/// rank 1, and tied values share the mean of the ranks they
+/// span. The ranks of a list containing NaN are undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, ranks(&[1.0, f64::NAN]));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(Some(vec![3.0, 1.5, 4.0, 1.5]), ranks(&[7.0, 2.0, 9.0, 2.0]));
+/// ```
+pub fn ranks(nums: &[f64]) -> Option<Vec<f64>> {
+    if has_nan(nums) {
+        return None;
+    }
+    let mut order: Vec<usize> = (0..nums.len()).collect();
+    order.sort_by(|&i, &j| nums[i].total_cmp(&nums[j]));
+    let mut ranks = vec![0.0; nums.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let value = nums[order[start]];
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|&&i| nums[i] == value)
+                .count();
+        //ranks start..end are 1-based start+1..=end
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    Some(ranks)
+}
+
+/// Lengths of the runs of equal values in the sorted `xs`.
+pub(crate) fn tie_runs(xs: &[f64]) -> Vec<usize> {
+    let xs = sorted(xs);
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < xs.len() {
+        let run = xs[start..].iter().take_while(|&&x| x == xs[start]).count();
+        runs.push(run);
+        start += run;
+    }
+    runs
+}
+
+/// Spearman's rank correlation coefficient of paired
+/// values: the Pearson correlation of their
+/// [`ranks`], with ties given their mean rank. It
+/// measures how well the relationship is described by a
+/// monotone function. Spearman's correlation is undefined
+/// for fewer than two pairs, for values containing NaN, and
+/// when either slice's values are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, spearman(&[1.0, 2.0], &[1.0, f64::NAN]));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys: Vec<f64> = xs.iter().map(|x: &f64| x.exp()).collect();
+/// assert_eq!(Some(1.0), spearman(&xs, &ys));
+/// assert_eq!(Some(-0.5), spearman(&[1.0, 2.0, 3.0], &[2.0, 3.0, 1.0]));
+/// ```
+pub fn spearman(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    pearson(&ranks(xs)?, &ranks(ys)?)
+}
+
+/// Number of pairs `(i, j)` with `i < j` in each run of
+/// equal values of a sorted list: the sum of `t(t-1)/2`
+/// over runs of length `t`.
+fn tied_pairs<T: PartialEq>(sorted: &[T]) -> u64 {
+    let mut total = 0;
+    let mut start = 0;
+    while start < sorted.len() {
+        let run = sorted[start..]
+            .iter()
+            .take_while(|&x| *x == sorted[start])
+            .count();
+        total += (run * (run - 1) / 2) as u64;
+        start += run;
+    }
+    total
+}
+
+/// Sort `nums` by merge sort using `scratch` of the same
+/// length, returning the number of pairs that were out of
+/// order, not counting equal values.
+fn count_inversions(nums: &mut [f64], scratch: &mut [f64]) -> u64 {
+    let n = nums.len();
+    if n < 2 {
+        return 0;
+    }
+    let mid = n / 2;
+    let mut swaps = count_inversions(&mut nums[..mid], &mut scratch[..mid])
+        + count_inversions(&mut nums[mid..], &mut scratch[mid..]);
+    let (mut i, mut j) = (0, mid);
+    for slot in scratch.iter_mut() {
+        if j == n || (i < mid && nums[i] <= nums[j]) {
+            *slot = nums[i];
+            i += 1;
+        } else {
+            *slot = nums[j];
+            swaps += (mid - i) as u64;
+            j += 1;
+        }
+    }
+    nums.copy_from_slice(scratch);
+    swaps
+}
+
+/// Kendall's rank correlation coefficient τ<sub>b</sub> of
+/// paired values: the excess of concordant over discordant
+/// pairs, normalized so that ties in either slice are
+/// accounted for. Computed in `O(n log n)` time by Knight's
+/// algorithm. Kendall's τ is undefined for fewer than two
+/// pairs, for values containing NaN, and when either
+/// slice's values are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(None, kendall_tau(&[1.0, 2.0], &[3.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::correlation::*;
+/// assert_eq!(Some(-1.0), kendall_tau(&[1.0, 2.0, 3.0], &[0.3, 0.2, 0.1]));
+/// // 5 concordant pairs and 1 discordant.
+/// let tau = kendall_tau(&[1.0, 2.0, 3.0, 4.0], &[1.0, 3.0, 2.0, 4.0]).unwrap();
+/// assert!((tau - 2.0 / 3.0).abs() < 1e-12);
+/// // 5 concordant pairs and one tied in y: τ_b = 5 / √(6 · 5).
+/// let tau = kendall_tau(&[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0, 2.0, 3.0]).unwrap();
+/// assert!((tau - 5.0 / 30f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn kendall_tau(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    //Knight (1966): https://doi.org/10.1080/01621459.1966.10480879
+    let n = xs.len();
+    if n != ys.len() || n < 2 || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let mut pairs: Vec<(f64, f64)> = xs.iter().copied().zip(ys.iter().copied()).collect();
+    pairs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let xs: Vec<f64> = pairs.iter().map(|p| p.0).collect();
+    let mut ys: Vec<f64> = pairs.iter().map(|p| p.1).collect();
+
+    let all = (n * (n - 1) / 2) as u64;
+    let xties = tied_pairs(&xs);
+    let jointties = tied_pairs(&pairs);
+    let swaps = count_inversions(&mut ys, &mut vec![0.0; n]);
+    let yties = tied_pairs(&ys);
+    if xties == all || yties == all {
+        return None;
+    }
+    //concordant minus discordant pairs
+    let net = all as f64 - (xties + yties - jointties) as f64 - 2.0 * swaps as f64;
+    let tau = net / (((all - xties) as f64) * ((all - yties) as f64)).sqrt();
+    Some(tau.clamp(-1.0, 1.0))
+}
+
+/// Partial correlation of paired values controlling for
+/// other variables: the [`pearson`] correlation of what is
+/// left of `xs` and of `ys` after removing, by least
+/// squares, the linear effect of the `controls` on each.
+/// With no controls it is the Pearson correlation. The
+/// partial correlation is undefined when the slices and
+/// controls differ in length or contain NaN, unless there are
+/// at least three more pairs than controls, and when either
+/// `xs` or `ys` is wholly explained by the controls.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::correlation::*;
+/// let xs = [1.0, 3.0, 2.0, 5.0, 4.0, 6.0];
+/// let ys = [2.0, 1.0, 4.0, 3.0, 6.0, 5.0];
+/// let zs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+/// let partial = partial_correlation(&xs, &ys, &[zs]).unwrap();
+/// let (xy, xz, yz): (f64, f64, f64) =
+///     (pearson(&xs, &ys).unwrap(), pearson(&xs, &zs).unwrap(), pearson(&ys, &zs).unwrap());
+/// let expected = (xy - xz * yz) / ((1.0 - xz * xz) * (1.0 - yz * yz)).sqrt();
+/// assert!((partial - expected).abs() < 1e-12);
+/// assert!(partial < 0.0 && xy > 0.0);
+/// ```
+pub fn partial_correlation<C: AsRef<[f64]>>(xs: &[f64], ys: &[f64], controls: &[C]) -> Option<f64> {
+    let n = xs.len();
+    if n != ys.len() || n < controls.len() + 3 || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    if controls
+        .iter()
+        .any(|c| c.as_ref().len() != n || has_nan(c.as_ref()))
+    {
+        return None;
+    }
+    let design = Matrix::from_fn(n, controls.len() + 1, |i, j| {
+        if j == 0 {
+            1.0
+        } else {
+            controls[j - 1].as_ref()[i]
+        }
+    });
+    let residuals = |values: &[f64]| -> Option<Vec<f64>> {
+        let coefficients = design.least_squares(values)?;
+        let residuals = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| v - dot(design.row(i), &coefficients).unwrap())
+            .collect();
+        Some(residuals)
+    };
+    pearson(&residuals(xs)?, &residuals(ys)?)
+}
@@ -0,0 +1,237 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Kernel density estimation: a smooth estimate of the
+//! density from which input values were drawn, which unlike
+//! a histogram does not depend on the placement of bins.
+//!
+//! Each value contributes a bump of the chosen [`Kernel`]
+//! shape, centered on the value and of width set by the
+//! bandwidth; the estimate is the average of the bumps. The
+//! bandwidth matters much more than the kernel: too narrow
+//! and the estimate is noisy, too wide and it smooths away
+//! real features.
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::{has_nan, iqr, sample_stddev, sorted};
+
+/// Shape of the bump each value contributes to a [`kde`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Kernel {
+    /// The standard normal density, which gives the
+    /// smoothest estimates.
+    #[default]
+    Gaussian,
+    /// The parabola `3/4 (1 - u²)` on `-1.0..=1.0`, which is
+    /// the most efficient kernel and is zero beyond one
+    /// bandwidth of every value.
+    Epanechnikov,
+}
+
+impl Kernel {
+    /// Height of the kernel at `u` bandwidths from its
+    /// center.
+    fn weight(self, u: f64) -> f64 {
+        match self {
+            Kernel::Gaussian => (-0.5 * u * u).exp() / (2.0 * core::f64::consts::PI).sqrt(),
+            Kernel::Epanechnikov if u.abs() <= 1.0 => 0.75 * (1.0 - u * u),
+            Kernel::Epanechnikov => 0.0,
+        }
+    }
+
+    /// Number of bandwidths from its center beyond which the
+    /// kernel is zero or negligible.
+    fn reach(self) -> f64 {
+        match self {
+            Kernel::Gaussian => 3.0,
+            Kernel::Epanechnikov => 1.0,
+        }
+    }
+}
+
+/// Choice of bandwidth for a [`kde`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bandwidth {
+    /// [`silverman_bandwidth`], robust to outliers and to
+    /// some skew.
+    #[default]
+    Silverman,
+    /// [`scott_bandwidth`], best for normally-distributed
+    /// values.
+    Scott,
+    /// A given bandwidth.
+    Fixed(f64),
+}
+
+/// Silverman's rule-of-thumb bandwidth for a [`kde`] with
+/// the [`Gaussian`](Kernel::Gaussian) kernel of input values:
+/// `0.9 min(s, IQR / 1.34) n^(-1/5)` for sample standard
+/// deviation `s` and interquartile range `IQR`. The
+/// standard deviation alone is used when the interquartile
+/// range is zero. The bandwidth is undefined for fewer than
+/// two values, for values containing NaN, and for values
+/// that are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::density::*;
+/// let h = silverman_bandwidth(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+/// // IQR / 1.34 = 2.0 / 1.34 is less than s = 1.5811...
+/// assert!((h - 0.9 * (2.0 / 1.34) * 5.0f64.powf(-0.2)).abs() < 1e-15);
+/// assert_eq!(None, silverman_bandwidth(&[1.0, 1.0]));
+/// ```
+pub fn silverman_bandwidth(nums: &[f64]) -> Option<f64> {
+    let s = spread(nums)?;
+    let iqr = iqr(nums)? / 1.34;
+    let scale = if iqr > 0.0 { s.min(iqr) } else { s };
+    Some(0.9 * scale * (nums.len() as f64).powf(-0.2))
+}
+
+/// Scott's rule-of-thumb bandwidth for a [`kde`] with the
+/// [`Gaussian`](Kernel::Gaussian) kernel of input values:
+/// `1.06 s n^(-1/5)` for sample standard deviation `s`,
+/// which is optimal for normally-distributed values. The
+/// bandwidth is undefined as for [`silverman_bandwidth`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::density::*;
+/// let h = scott_bandwidth(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+/// assert!((h - 1.06 * 2.5f64.sqrt() * 5.0f64.powf(-0.2)).abs() < 1e-15);
+/// ```
+pub fn scott_bandwidth(nums: &[f64]) -> Option<f64> {
+    let s = spread(nums)?;
+    Some(1.06 * s * (nums.len() as f64).powf(-0.2))
+}
+
+/// Sample standard deviation of input values, or `None`
+/// unless it is finite and positive.
+fn spread(nums: &[f64]) -> Option<f64> {
+    if has_nan(nums) {
+        return None;
+    }
+    let s = sample_stddev(nums)?;
+    if !s.is_finite() || s <= 0.0 {
+        return None;
+    }
+    Some(s)
+}
+
+/// Kernel density estimate of input values, as computed by
+/// [`kde`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kde {
+    nums: Vec<f64>,
+    bandwidth: f64,
+    kernel: Kernel,
+}
+
+impl Kde {
+    /// The bandwidth.
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// The kernel.
+    pub fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Estimated density at `x`.
+    pub fn density(&self, x: f64) -> f64 {
+        let h = self.bandwidth;
+        //only the values within reach of x contribute
+        let reach = self.kernel.reach() * h;
+        let (low, high) = if self.kernel == Kernel::Gaussian {
+            (0, self.nums.len())
+        } else {
+            (
+                self.nums.partition_point(|&v| v < x - reach),
+                self.nums.partition_point(|&v| v <= x + reach),
+            )
+        };
+        let total: f64 = self.nums[low..high]
+            .iter()
+            .map(|&v| self.kernel.weight((x - v) / h))
+            .sum();
+        total / (self.nums.len() as f64 * h)
+    }
+
+    /// The estimated density at `points` evenly spaced
+    /// values, as pairs `(x, density)`, spanning the input
+    /// values and the reach of the kernel beyond them: one
+    /// bandwidth for the [`Epanechnikov`] kernel, outside
+    /// which the estimate is zero, and three for the
+    /// [`Gaussian`]. These are the points to plot.
+    ///
+    /// [`Epanechnikov`]: Kernel::Epanechnikov
+    /// [`Gaussian`]: Kernel::Gaussian
+    pub fn grid(&self, points: usize) -> Vec<(f64, f64)> {
+        let reach = self.kernel.reach() * self.bandwidth;
+        let low = self.nums[0] - reach;
+        let high = self.nums[self.nums.len() - 1] + reach;
+        let step = (high - low) / (points.max(2) - 1) as f64;
+        (0..points)
+            .map(|i| {
+                let x = low + i as f64 * step;
+                (x, self.density(x))
+            })
+            .collect()
+    }
+}
+
+/// Kernel density estimate of input values with the given
+/// bandwidth and kernel. The estimate is undefined for an
+/// empty list, for values containing NaN or infinities, and
+/// for a bandwidth that is undefined for the values or is
+/// not finite and positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::density::*;
+/// let single = kde(&[0.0], Bandwidth::Fixed(1.0), Kernel::Gaussian).unwrap();
+/// assert!((single.density(0.0) - 0.3989422804014327).abs() < 1e-15);
+/// assert_eq!(None, kde(&[0.0], Bandwidth::Silverman, Kernel::Gaussian));
+/// ```
+/// ```
+/// # use stats::density::*;
+/// let xs = [1.0, 2.0, 2.5, 3.0, 7.0];
+/// let estimate = kde(&xs, Bandwidth::Fixed(1.0), Kernel::Epanechnikov).unwrap();
+/// assert!((estimate.density(2.0) - (0.75 + 0.5625 + 0.0) / 5.0).abs() < 1e-15);
+/// let grid = estimate.grid(81);
+/// assert_eq!((0.0, 0.0), grid[0]);
+/// assert_eq!(8.0, grid[80].0);
+/// // The estimate is a density: it integrates to 1.
+/// let area: f64 = grid.iter().map(|&(_, y)| y * 0.1).sum();
+/// assert!((area - 1.0).abs() < 1e-2);
+/// ```
+pub fn kde(nums: &[f64], bandwidth: Bandwidth, kernel: Kernel) -> Option<Kde> {
+    if nums.is_empty() || !nums.iter().all(|x| x.is_finite()) {
+        return None;
+    }
+    let bandwidth = match bandwidth {
+        Bandwidth::Silverman => silverman_bandwidth(nums)?,
+        Bandwidth::Scott => scott_bandwidth(nums)?,
+        Bandwidth::Fixed(h) => h,
+    };
+    if !bandwidth.is_finite() || bandwidth <= 0.0 {
+        return None;
+    }
+    Some(Kde {
+        nums: sorted(nums),
+        bandwidth,
+        kernel,
+    })
+}
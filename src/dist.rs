@@ -0,0 +1,1332 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Probability distributions.
+//!
+//! Every continuous distribution implements [`Continuous`],
+//! so code computing probabilities or critical values need
+//! not know which distribution it has, and every discrete
+//! distribution on the counts `0, 1, 2, ...` implements
+//! [`Discrete`]. Constructors return `None` for parameters
+//! outside the distribution's domain.
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::intervals::half_alpha;
+use crate::special::{
+    beta_inc, beta_inc_inv, f_cdf, gamma_inc, gamma_inc_inv, gamma_inc_upper, ln_beta, ln_gamma,
+    normal_cdf, normal_quantile, student_t_cdf, student_t_pdf, student_t_quantile,
+};
+use crate::{has_nan, mean, quantile_sorted, sorted, sum, variance, QuantileMethod};
+
+/// A continuous probability distribution on the real line.
+pub trait Continuous {
+    /// Probability density at `x`.
+    fn pdf(&self, x: f64) -> f64;
+
+    /// Cumulative distribution function: the probability of
+    /// a value at most `x`.
+    fn cdf(&self, x: f64) -> f64;
+
+    /// Quantile function, the inverse of the [`cdf`]: the
+    /// value below which lies probability `p`. `None` unless
+    /// `p` is in `0.0..=1.0`; the quantiles 0.0 and 1.0 are
+    /// the ends of the distribution's support, which may be
+    /// infinite.
+    ///
+    /// [`cdf`]: Continuous::cdf
+    fn inv_cdf(&self, p: f64) -> Option<f64>;
+
+    /// Natural logarithm of the probability density at `x`,
+    /// which may keep its digits where the [`pdf`]
+    /// underflows.
+    ///
+    /// [`pdf`]: Continuous::pdf
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+
+    /// A value drawn at random from the distribution, by
+    /// inverting the [`cdf`] at a uniform random
+    /// probability.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let wait = Exponential::new(2.0).unwrap();
+    /// let xs = wait.sample_n(&mut rng, 10_000);
+    /// assert!(xs.iter().all(|&x| x >= 0.0));
+    /// assert!((stats::mean(&xs).unwrap() - 0.5).abs() < 0.02);
+    /// ```
+    ///
+    /// [`cdf`]: Continuous::cdf
+    #[cfg(feature = "rand")]
+    fn sample<R: rand::RngCore + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.inv_cdf(uniform(rng)).unwrap()
+    }
+
+    /// `n` values drawn independently at random from the
+    /// distribution, as by [`sample`](Continuous::sample).
+    #[cfg(feature = "rand")]
+    fn sample_n<R: rand::RngCore + ?Sized>(&self, rng: &mut R, n: usize) -> Vec<f64> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// Uniform random probability strictly between 0.0 and 1.0,
+/// so that its quantile is finite.
+#[cfg(feature = "rand")]
+fn uniform<R: rand::RngCore + ?Sized>(rng: &mut R) -> f64 {
+    //the midpoints of 2⁵³ equal steps
+    ((rng.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+}
+
+/// A distribution fitted to a sample, with the measures of
+/// fit that compare it with candidate distributions fitted
+/// to the same sample: the larger the log-likelihood, or the
+/// smaller the AIC or BIC, the better the fit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fit<D> {
+    distribution: D,
+    log_likelihood: f64,
+    params: usize,
+    count: usize,
+}
+
+impl<D: Continuous> Fit<D> {
+    /// Measure the fit of a distribution with `params` free
+    /// parameters to `nums`.
+    fn new(distribution: D, params: usize, nums: &[f64]) -> Fit<D> {
+        let ln_pdfs: Vec<f64> = nums.iter().map(|&x| distribution.ln_pdf(x)).collect();
+        Fit {
+            distribution,
+            log_likelihood: sum(&ln_pdfs),
+            params,
+            count: nums.len(),
+        }
+    }
+}
+
+impl<D> Fit<D> {
+    /// The fitted distribution.
+    pub fn distribution(&self) -> &D {
+        &self.distribution
+    }
+
+    /// Natural logarithm of the likelihood of the sample
+    /// under the fitted distribution.
+    pub fn log_likelihood(&self) -> f64 {
+        self.log_likelihood
+    }
+
+    /// Number of parameters fitted.
+    pub fn params(&self) -> usize {
+        self.params
+    }
+
+    /// Number of values in the sample.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Akaike information criterion, `2k - 2 ln L` for `k`
+    /// parameters.
+    pub fn aic(&self) -> f64 {
+        2.0 * self.params as f64 - 2.0 * self.log_likelihood
+    }
+
+    /// Bayesian information criterion, `k ln n - 2 ln L` for
+    /// `k` parameters and `n` values, which penalizes
+    /// parameters more than the [`aic`](Fit::aic) for all but
+    /// the smallest samples.
+    pub fn bic(&self) -> f64 {
+        self.params as f64 * (self.count as f64).ln() - 2.0 * self.log_likelihood
+    }
+}
+
+/// Maximum-likelihood mean and standard deviation of input
+/// values: the mean and the population standard deviation.
+/// `None` for values containing NaN or infinities or that
+/// are all equal, which have no finite positive deviation.
+fn normal_mle(nums: &[f64]) -> Option<(f64, f64)> {
+    if has_nan(nums) {
+        return None;
+    }
+    let mu = mean(nums)?;
+    let sigma = variance(nums)?.sqrt();
+    if !mu.is_finite() || !sigma.is_finite() || sigma <= 0.0 {
+        return None;
+    }
+    Some((mu, sigma))
+}
+
+/// A discrete probability distribution on the counts
+/// `0, 1, 2, ...`.
+pub trait Discrete {
+    /// Probability mass function: the probability of the
+    /// count `k`.
+    fn pmf(&self, k: u64) -> f64;
+
+    /// Cumulative distribution function: the probability of
+    /// a count at most `k`.
+    fn cdf(&self, k: u64) -> f64;
+
+    /// Quantile function: the least count `k` whose [`cdf`]
+    /// is at least `p`. `None` unless `p` is in `0.0..=1.0`;
+    /// the quantile 1.0 of a distribution with unbounded
+    /// support is `u64::MAX`.
+    ///
+    /// [`cdf`]: Discrete::cdf
+    fn inv_cdf(&self, p: f64) -> Option<u64>;
+
+    /// A count drawn at random from the distribution, by
+    /// inverting the [`cdf`] at a uniform random
+    /// probability.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    /// let mut rng = SmallRng::seed_from_u64(7);
+    /// let dice = Binomial::new(6, 0.5).unwrap();
+    /// let counts = dice.sample_n(&mut rng, 1000);
+    /// assert!(counts.iter().all(|&k| k <= 6));
+    /// ```
+    ///
+    /// [`cdf`]: Discrete::cdf
+    #[cfg(feature = "rand")]
+    fn sample<R: rand::RngCore + ?Sized>(&self, rng: &mut R) -> u64 {
+        self.inv_cdf(uniform(rng)).unwrap()
+    }
+
+    /// `n` counts drawn independently at random from the
+    /// distribution, as by [`sample`](Discrete::sample).
+    #[cfg(feature = "rand")]
+    fn sample_n<R: rand::RngCore + ?Sized>(&self, rng: &mut R, n: usize) -> Vec<u64> {
+        (0..n).map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// The normal distribution with mean `mu` and standard
+/// deviation `sigma`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let iq = Normal::new(100.0, 15.0).unwrap();
+/// assert!((iq.cdf(130.0) - 0.97724986805182).abs() < 1e-14);
+/// assert!((iq.inv_cdf(0.975).unwrap() - 129.399459768).abs() < 1e-9);
+/// assert!((iq.pdf(100.0) - 0.0265961520).abs() < 1e-10);
+/// assert_eq!(None, Normal::new(0.0, 0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normal {
+    mu: f64,
+    sigma: f64,
+}
+
+impl Normal {
+    /// Normal distribution with mean `mu` and standard
+    /// deviation `sigma`, or `None` unless `mu` is finite
+    /// and `sigma` is finite and positive.
+    pub fn new(mu: f64, sigma: f64) -> Option<Normal> {
+        if !mu.is_finite() || !sigma.is_finite() || sigma <= 0.0 {
+            return None;
+        }
+        Some(Normal { mu, sigma })
+    }
+
+    /// The standard normal distribution, with mean 0.0 and
+    /// standard deviation 1.0.
+    pub fn standard() -> Normal {
+        Normal {
+            mu: 0.0,
+            sigma: 1.0,
+        }
+    }
+
+    /// Maximum-likelihood fit to input values: the normal
+    /// distribution with their mean and population standard
+    /// deviation. Undefined for values containing NaN and
+    /// for values that are all equal.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// let fit = Normal::fit(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    /// assert_eq!(3.0, fit.distribution().mu());
+    /// assert!((fit.distribution().sigma() - 2.0f64.sqrt()).abs() < 1e-15);
+    /// assert!((fit.log_likelihood() + 8.827560617423227).abs() < 1e-13);
+    /// assert!((fit.aic() - 21.655121234846454).abs() < 1e-13);
+    /// assert!((fit.bic() - 20.873997059714655).abs() < 1e-13);
+    /// assert_eq!(None, Normal::fit(&[2.0, 2.0]));
+    /// ```
+    pub fn fit(nums: &[f64]) -> Option<Fit<Normal>> {
+        let (mu, sigma) = normal_mle(nums)?;
+        Some(Fit::new(Normal { mu, sigma }, 2, nums))
+    }
+
+    /// The mean.
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// The standard deviation.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+}
+
+impl Continuous for Normal {
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        let z = (x - self.mu) / self.sigma;
+        -0.5 * z * z - self.sigma.ln() - 0.5 * (2.0 * core::f64::consts::PI).ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        normal_cdf((x - self.mu) / self.sigma)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, f64::NEG_INFINITY, f64::INFINITY, |p| {
+            self.mu + self.sigma * normal_quantile(p)
+        })
+    }
+}
+
+/// The exponential distribution with rate `rate`: the
+/// distribution of the waiting time between events occurring
+/// independently at that rate, with mean `1 / rate`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let wait = Exponential::new(0.5).unwrap();
+/// assert!((wait.cdf(2.0) - (1.0 - (-1.0f64).exp())).abs() < 1e-15);
+/// assert!((wait.inv_cdf(0.5).unwrap() - 2.0 * 2.0f64.ln()).abs() < 1e-15);
+/// assert_eq!(0.0, wait.pdf(-1.0));
+/// assert_eq!(None, Exponential::new(-1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exponential {
+    rate: f64,
+}
+
+impl Exponential {
+    /// Exponential distribution with rate `rate`, or `None`
+    /// unless `rate` is finite and positive.
+    pub fn new(rate: f64) -> Option<Exponential> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return None;
+        }
+        Some(Exponential { rate })
+    }
+
+    /// Maximum-likelihood fit to input values: the
+    /// exponential distribution with their mean. Undefined
+    /// for an empty list, for values containing NaN or
+    /// negative values, and for values that are all zero.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// let fit = Exponential::fit(&[1.0, 2.0, 3.0]).unwrap();
+    /// assert_eq!(0.5, fit.distribution().rate());
+    /// assert!((fit.log_likelihood() + 5.079441541679836).abs() < 1e-14);
+    /// assert_eq!(None, Exponential::fit(&[1.0, -2.0]));
+    /// ```
+    pub fn fit(nums: &[f64]) -> Option<Fit<Exponential>> {
+        if !nums.iter().all(|&x| x >= 0.0) {
+            return None;
+        }
+        let exponential = Exponential::new(1.0 / mean(nums)?)?;
+        Some(Fit::new(exponential, 1, nums))
+    }
+
+    /// The rate.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+}
+
+impl Continuous for Exponential {
+    fn pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        self.rate * (-self.rate * x).exp()
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        self.rate.ln() - self.rate * x
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        -(-self.rate * x).exp_m1()
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, 0.0, f64::INFINITY, |p| -(-p).ln_1p() / self.rate)
+    }
+}
+
+/// The log-normal distribution: the distribution of `eˣ`
+/// for `x` normally distributed with mean `mu` and standard
+/// deviation `sigma`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let sizes = LogNormal::new(0.0, 1.0).unwrap();
+/// assert_eq!(0.5, sizes.cdf(1.0));
+/// assert!((sizes.inv_cdf(0.975).unwrap() - 7.099071384).abs() < 1e-8);
+/// assert_eq!(0.0, sizes.pdf(0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogNormal {
+    mu: f64,
+    sigma: f64,
+}
+
+impl LogNormal {
+    /// Log-normal distribution whose logarithm has mean `mu`
+    /// and standard deviation `sigma`, or `None` unless `mu`
+    /// is finite and `sigma` is finite and positive.
+    pub fn new(mu: f64, sigma: f64) -> Option<LogNormal> {
+        if !mu.is_finite() || !sigma.is_finite() || sigma <= 0.0 {
+            return None;
+        }
+        Some(LogNormal { mu, sigma })
+    }
+
+    /// Maximum-likelihood fit to input values: the
+    /// log-normal distribution whose logarithm has the mean
+    /// and population standard deviation of their
+    /// logarithms. Undefined for values containing NaN or
+    /// values that are not positive, and for values that are
+    /// all equal.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// use std::f64::consts::E;
+    /// let fit = LogNormal::fit(&[1.0, E, E * E]).unwrap();
+    /// assert!((fit.distribution().mu() - 1.0).abs() < 1e-15);
+    /// assert!((fit.log_likelihood() + 6.648617937451771).abs() < 1e-14);
+    /// assert_eq!(None, LogNormal::fit(&[0.0, 1.0]));
+    /// ```
+    pub fn fit(nums: &[f64]) -> Option<Fit<LogNormal>> {
+        if !nums.iter().all(|&x| x > 0.0) {
+            return None;
+        }
+        let logs: Vec<f64> = nums.iter().map(|x| x.ln()).collect();
+        let (mu, sigma) = normal_mle(&logs)?;
+        Some(Fit::new(LogNormal { mu, sigma }, 2, nums))
+    }
+
+    /// The mean of the logarithm.
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// The standard deviation of the logarithm.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+}
+
+impl Continuous for LogNormal {
+    fn pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let ln_x = x.ln();
+        let normal = Normal {
+            mu: self.mu,
+            sigma: self.sigma,
+        };
+        normal.ln_pdf(ln_x) - ln_x
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        normal_cdf((x.ln() - self.mu) / self.sigma)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, 0.0, f64::INFINITY, |p| {
+            (self.mu + self.sigma * normal_quantile(p)).exp()
+        })
+    }
+}
+
+/// `x ln y`, taken to be 0.0 when `x` is 0.0 whatever `y`,
+/// as the densities need at the ends of their support.
+fn x_ln_y(x: f64, y: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        x * y.ln()
+    }
+}
+
+/// Mean and population variance of input values, the
+/// moments that the method-of-moments fits match. `None`
+/// for values containing NaN or infinities.
+fn moments(nums: &[f64]) -> Option<(f64, f64)> {
+    if has_nan(nums) {
+        return None;
+    }
+    let (m, v) = (mean(nums)?, variance(nums)?);
+    if !m.is_finite() || !v.is_finite() {
+        return None;
+    }
+    Some((m, v))
+}
+
+/// The gamma distribution with shape `shape` and scale
+/// `scale`: the distribution of the waiting time for `shape`
+/// events occurring independently at rate `1 / scale`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let gamma = Gamma::new(2.0, 1.0).unwrap();
+/// assert!((gamma.cdf(1.0) - (1.0 - 2.0 / 1.0f64.exp())).abs() < 1e-15);
+/// assert!((gamma.pdf(1.0) - 1.0 / 1.0f64.exp()).abs() < 1e-15);
+/// assert!((gamma.cdf(gamma.inv_cdf(0.9).unwrap()) - 0.9).abs() < 1e-14);
+/// assert_eq!(None, Gamma::new(0.0, 1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gamma {
+    shape: f64,
+    scale: f64,
+}
+
+impl Gamma {
+    /// Gamma distribution with shape `shape` and scale
+    /// `scale`, or `None` unless both are finite and
+    /// positive.
+    pub fn new(shape: f64, scale: f64) -> Option<Gamma> {
+        if !shape.is_finite() || !scale.is_finite() || shape <= 0.0 || scale <= 0.0 {
+            return None;
+        }
+        Some(Gamma { shape, scale })
+    }
+
+    /// Method-of-moments fit to input values: the gamma
+    /// distribution with their mean and population variance,
+    /// of shape `mean² / variance` and scale
+    /// `variance / mean`. Closed-form and so cheap, though
+    /// less efficient than a maximum-likelihood fit.
+    /// Undefined for values containing NaN or negative
+    /// values, and for values that are all equal.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// let fit = Gamma::fit_moments(&[1.0, 2.0, 3.0, 4.0, 5.0]).unwrap();
+    /// assert!((fit.distribution().shape() - 4.5).abs() < 1e-14);
+    /// assert!((fit.distribution().scale() - 2.0 / 3.0).abs() < 1e-15);
+    /// assert!(fit.log_likelihood() < 0.0);
+    /// assert_eq!(None, Gamma::fit_moments(&[2.0, 2.0]));
+    /// ```
+    pub fn fit_moments(nums: &[f64]) -> Option<Fit<Gamma>> {
+        if !nums.iter().all(|&x| x >= 0.0) {
+            return None;
+        }
+        let (m, v) = moments(nums)?;
+        let gamma = Gamma::new(m * m / v, v / m)?;
+        Some(Fit::new(gamma, 2, nums))
+    }
+
+    /// The shape.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// The scale.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl Continuous for Gamma {
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return f64::NEG_INFINITY;
+        }
+        let (k, theta) = (self.shape, self.scale);
+        x_ln_y(k - 1.0, x) - x / theta - ln_gamma(k) - k * theta.ln()
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        gamma_inc(self.shape, x / self.scale)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, 0.0, f64::INFINITY, |p| {
+            self.scale * gamma_inc_inv(self.shape, p)
+        })
+    }
+}
+
+/// The beta distribution with shapes `alpha` and `beta`, on
+/// `0.0..=1.0`: a common model of proportions.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let beta = Beta::new(2.0, 3.0).unwrap();
+/// assert!((beta.cdf(0.5) - 0.6875).abs() < 1e-15);
+/// assert!((beta.pdf(0.5) - 1.5).abs() < 1e-14);
+/// assert_eq!(Some(1.0), beta.inv_cdf(1.0));
+/// assert_eq!(0.0, beta.pdf(1.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Beta {
+    alpha: f64,
+    beta: f64,
+}
+
+impl Beta {
+    /// Beta distribution with shapes `alpha` and `beta`, or
+    /// `None` unless both are finite and positive.
+    pub fn new(alpha: f64, beta: f64) -> Option<Beta> {
+        if !alpha.is_finite() || !beta.is_finite() || alpha <= 0.0 || beta <= 0.0 {
+            return None;
+        }
+        Some(Beta { alpha, beta })
+    }
+
+    /// Method-of-moments fit to input values: the beta
+    /// distribution with their mean `m` and population
+    /// variance `v`, whose shapes are `m` and `1 - m` times
+    /// `m (1 - m) / v - 1`. Undefined for values containing
+    /// NaN or values outside `0.0..=1.0`, and for values
+    /// that are all equal or whose variance is at least
+    /// `m (1 - m)`, which no beta distribution has.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::dist::*;
+    /// let fit = Beta::fit_moments(&[0.1, 0.2, 0.3, 0.4]).unwrap();
+    /// assert!((fit.distribution().alpha() - 3.5).abs() < 1e-12);
+    /// assert!((fit.distribution().beta() - 10.5).abs() < 1e-12);
+    /// assert_eq!(None, Beta::fit_moments(&[0.0, 1.0]));
+    /// ```
+    pub fn fit_moments(nums: &[f64]) -> Option<Fit<Beta>> {
+        if !nums.iter().all(|x| (0.0..=1.0).contains(x)) {
+            return None;
+        }
+        let (m, v) = moments(nums)?;
+        let common = m * (1.0 - m) / v - 1.0;
+        let beta = Beta::new(m * common, (1.0 - m) * common)?;
+        Some(Fit::new(beta, 2, nums))
+    }
+
+    /// The first shape.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The second shape.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+}
+
+impl Continuous for Beta {
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn ln_pdf(&self, x: f64) -> f64 {
+        if !(0.0..=1.0).contains(&x) {
+            return f64::NEG_INFINITY;
+        }
+        let (a, b) = (self.alpha, self.beta);
+        x_ln_y(a - 1.0, x) + x_ln_y(b - 1.0, 1.0 - x) - ln_beta(a, b)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        beta_inc(self.alpha, self.beta, x.clamp(0.0, 1.0))
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, 0.0, 1.0, |p| beta_inc_inv(self.alpha, self.beta, p))
+    }
+}
+
+/// Quantile `p` of a distribution with support `low..high`,
+/// with the ends of the support for `p` of 0.0 and 1.0 and
+/// `quantile` for the values between.
+fn inv_cdf_with<F: FnOnce(f64) -> f64>(p: f64, low: f64, high: f64, quantile: F) -> Option<f64> {
+    if !(0.0..=1.0).contains(&p) {
+        None
+    } else if p == 0.0 {
+        Some(low)
+    } else if p == 1.0 {
+        Some(high)
+    } else {
+        Some(quantile(p))
+    }
+}
+
+/// Student's t distribution with `df` degrees of freedom,
+/// the distribution of the t statistics of
+/// [`tests`](crate::tests).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let t = StudentT::new(10.0).unwrap();
+/// // The two-sided 95% critical value.
+/// assert!((t.inv_cdf(0.975).unwrap() - 2.228138851986).abs() < 1e-11);
+/// assert!((t.cdf(-2.0) - 0.036694017385370).abs() < 1e-14);
+/// assert_eq!(None, StudentT::new(0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StudentT {
+    df: f64,
+}
+
+impl StudentT {
+    /// Student's t distribution with `df` degrees of freedom,
+    /// or `None` unless `df` is finite and positive.
+    pub fn new(df: f64) -> Option<StudentT> {
+        if !df.is_finite() || df <= 0.0 {
+            return None;
+        }
+        Some(StudentT { df })
+    }
+
+    /// The degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl Continuous for StudentT {
+    fn pdf(&self, x: f64) -> f64 {
+        student_t_pdf(x, self.df)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        student_t_cdf(x, self.df)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, f64::NEG_INFINITY, f64::INFINITY, |p| {
+            student_t_quantile(p, self.df)
+        })
+    }
+}
+
+/// The chi-square distribution with `df` degrees of freedom:
+/// the distribution of the sum of the squares of `df`
+/// independent standard normal values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let chi2 = ChiSquared::new(3.0).unwrap();
+/// assert!((chi2.inv_cdf(0.95).unwrap() - 7.814727903251).abs() < 1e-10);
+/// assert!((chi2.cdf(1.0) - 0.198748043098799).abs() < 1e-14);
+/// assert_eq!(0.0, chi2.pdf(-1.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChiSquared {
+    df: f64,
+}
+
+impl ChiSquared {
+    /// Chi-square distribution with `df` degrees of freedom,
+    /// or `None` unless `df` is finite and positive.
+    pub fn new(df: f64) -> Option<ChiSquared> {
+        if !df.is_finite() || df <= 0.0 {
+            return None;
+        }
+        Some(ChiSquared { df })
+    }
+
+    /// The degrees of freedom.
+    pub fn df(&self) -> f64 {
+        self.df
+    }
+}
+
+impl Continuous for ChiSquared {
+    fn pdf(&self, x: f64) -> f64 {
+        let k = 0.5 * self.df;
+        if x < 0.0 {
+            0.0
+        } else if x == 0.0 {
+            match k.partial_cmp(&1.0) {
+                Some(core::cmp::Ordering::Less) => f64::INFINITY,
+                Some(core::cmp::Ordering::Equal) => 0.5,
+                _ => 0.0,
+            }
+        } else {
+            ((k - 1.0) * (0.5 * x).ln() - 0.5 * x - ln_gamma(k)).exp() / 2.0
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        gamma_inc(0.5 * self.df, 0.5 * x)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        inv_cdf_with(p, 0.0, f64::INFINITY, |p| {
+            2.0 * gamma_inc_inv(0.5 * self.df, p)
+        })
+    }
+}
+
+/// The F distribution with `d1` and `d2` degrees of freedom:
+/// the distribution of the ratio of independent chi-square
+/// values, each divided by its degrees of freedom, as of the
+/// F statistic of [`anova_one_way`](crate::tests::anova_one_way).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let f = FisherF::new(2.0, 15.0).unwrap();
+/// assert!((f.inv_cdf(0.95).unwrap() - 3.682320344).abs() < 1e-8);
+/// assert!((1.0 - f.cdf(9.264706) - 0.002398777203).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FisherF {
+    d1: f64,
+    d2: f64,
+}
+
+impl FisherF {
+    /// F distribution with `d1` degrees of freedom in the
+    /// numerator and `d2` in the denominator, or `None`
+    /// unless both are finite and positive.
+    pub fn new(d1: f64, d2: f64) -> Option<FisherF> {
+        if !d1.is_finite() || !d2.is_finite() || d1 <= 0.0 || d2 <= 0.0 {
+            return None;
+        }
+        Some(FisherF { d1, d2 })
+    }
+
+    /// The degrees of freedom of the numerator.
+    pub fn d1(&self) -> f64 {
+        self.d1
+    }
+
+    /// The degrees of freedom of the denominator.
+    pub fn d2(&self) -> f64 {
+        self.d2
+    }
+}
+
+impl Continuous for FisherF {
+    fn pdf(&self, x: f64) -> f64 {
+        let (d1, d2) = (self.d1, self.d2);
+        if x < 0.0 {
+            0.0
+        } else if x == 0.0 {
+            match d1.partial_cmp(&2.0) {
+                Some(core::cmp::Ordering::Less) => f64::INFINITY,
+                Some(core::cmp::Ordering::Equal) => 1.0,
+                _ => 0.0,
+            }
+        } else {
+            let ln_pdf = 0.5 * d1 * (d1 * x).ln() + 0.5 * d2 * d2.ln()
+                - 0.5 * (d1 + d2) * (d1 * x + d2).ln()
+                - x.ln()
+                - ln_beta(0.5 * d1, 0.5 * d2);
+            ln_pdf.exp()
+        }
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        f_cdf(x, self.d1, self.d2)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<f64> {
+        let (d1, d2) = (self.d1, self.d2);
+        inv_cdf_with(p, 0.0, f64::INFINITY, |p| {
+            //invert whichever tail keeps the digits of x or 1 - x
+            if p < 0.5 {
+                let x = beta_inc_inv(0.5 * d1, 0.5 * d2, p);
+                d2 * x / (d1 * (1.0 - x))
+            } else {
+                let y = beta_inc_inv(0.5 * d2, 0.5 * d1, 1.0 - p);
+                d2 * (1.0 - y) / (d1 * y)
+            }
+        })
+    }
+}
+
+/// Least count in `low..=high` whose `cdf` is at least `p`,
+/// by bisection, given that `cdf(high)` is at least `p`.
+fn discrete_search<F: Fn(u64) -> f64>(p: f64, mut low: u64, mut high: u64, cdf: F) -> u64 {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if cdf(mid) >= p {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    low
+}
+
+/// The binomial distribution: the number of successes in `n`
+/// independent trials that each succeed with probability
+/// `p`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let coin = Binomial::new(10, 0.5).unwrap();
+/// assert!((coin.pmf(5) - 0.24609375).abs() < 1e-15);
+/// assert!((coin.cdf(2) - 0.0546875).abs() < 1e-15);
+/// assert_eq!(Some(5), coin.inv_cdf(0.5));
+/// assert_eq!(1.0, coin.cdf(10));
+/// assert_eq!(None, Binomial::new(10, 1.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Binomial {
+    n: u64,
+    p: f64,
+}
+
+impl Binomial {
+    /// Binomial distribution of `n` trials each succeeding
+    /// with probability `p`, or `None` unless `p` is in
+    /// `0.0..=1.0`.
+    pub fn new(n: u64, p: f64) -> Option<Binomial> {
+        if !(0.0..=1.0).contains(&p) {
+            return None;
+        }
+        Some(Binomial { n, p })
+    }
+
+    /// The number of trials.
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// The probability of success of each trial.
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+}
+
+impl Discrete for Binomial {
+    fn pmf(&self, k: u64) -> f64 {
+        let (n, p) = (self.n, self.p);
+        if k > n {
+            return 0.0;
+        }
+        //the degenerate cases, where 0 ln 0 would be NaN
+        if p == 0.0 {
+            return if k == 0 { 1.0 } else { 0.0 };
+        }
+        if p == 1.0 {
+            return if k == n { 1.0 } else { 0.0 };
+        }
+        let (k, n) = (k as f64, n as f64);
+        let ln_choose = ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0);
+        (ln_choose + k * p.ln() + (n - k) * (-p).ln_1p()).exp()
+    }
+
+    fn cdf(&self, k: u64) -> f64 {
+        if k >= self.n {
+            return 1.0;
+        }
+        //P(X <= k) = I_{1-p}(n - k, k + 1)
+        beta_inc((self.n - k) as f64, k as f64 + 1.0, 1.0 - self.p)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<u64> {
+        if !(0.0..=1.0).contains(&p) {
+            return None;
+        }
+        Some(discrete_search(p, 0, self.n, |k| self.cdf(k)))
+    }
+}
+
+/// The Poisson distribution with mean `lambda`: the number
+/// of events in an interval when they occur independently
+/// at a constant rate.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let arrivals = Poisson::new(3.0).unwrap();
+/// assert!((arrivals.pmf(2) - 0.224041807655388).abs() < 1e-15);
+/// assert!((arrivals.cdf(5) - 0.916082057968696).abs() < 1e-14);
+/// assert_eq!(Some(6), arrivals.inv_cdf(0.95));
+/// assert_eq!(None, Poisson::new(0.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Poisson {
+    lambda: f64,
+}
+
+impl Poisson {
+    /// Poisson distribution with mean `lambda`, or `None`
+    /// unless `lambda` is finite and positive.
+    pub fn new(lambda: f64) -> Option<Poisson> {
+        if !lambda.is_finite() || lambda <= 0.0 {
+            return None;
+        }
+        Some(Poisson { lambda })
+    }
+
+    /// The mean.
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl Discrete for Poisson {
+    fn pmf(&self, k: u64) -> f64 {
+        let k = k as f64;
+        (k * self.lambda.ln() - self.lambda - ln_gamma(k + 1.0)).exp()
+    }
+
+    fn cdf(&self, k: u64) -> f64 {
+        //P(X <= k) = Q(k + 1, λ)
+        gamma_inc_upper(k as f64 + 1.0, self.lambda)
+    }
+
+    fn inv_cdf(&self, p: f64) -> Option<u64> {
+        if !(0.0..=1.0).contains(&p) {
+            return None;
+        }
+        if p == 1.0 {
+            return Some(u64::MAX);
+        }
+        //double an upper bound until it is one
+        let mut high = self.lambda.ceil() as u64;
+        while self.cdf(high) < p {
+            high = high.saturating_mul(2).max(1);
+            if high == u64::MAX {
+                break;
+            }
+        }
+        Some(discrete_search(p, 0, high, |k| self.cdf(k)))
+    }
+}
+
+/// Clopper–Pearson confidence interval for the probability
+/// of success from `successes` in `trials`, at confidence
+/// `level` (such as 0.95). The interval is exact: it is
+/// built from the [`Binomial`] distribution itself, so it
+/// covers the true probability at least `level` of the time
+/// however few the trials. `None` for more successes than
+/// trials, no trials, or a `level` outside `0.0..1.0`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let (low, high) = clopper_pearson(3, 20, 0.95).unwrap();
+/// assert!((low - 0.0320709372).abs() < 1e-10);
+/// assert!((high - 0.3789268265).abs() < 1e-10);
+/// assert_eq!(0.0, clopper_pearson(0, 20, 0.95).unwrap().0);
+/// ```
+pub fn clopper_pearson(successes: u64, trials: u64, level: f64) -> Option<(f64, f64)> {
+    let tail = half_alpha(level)?;
+    if trials == 0 || successes > trials {
+        return None;
+    }
+    let (x, n) = (successes as f64, trials as f64);
+    let low = if successes == 0 {
+        0.0
+    } else {
+        beta_inc_inv(x, n - x + 1.0, tail)
+    };
+    let high = if successes == trials {
+        1.0
+    } else {
+        beta_inc_inv(x + 1.0, n - x, 1.0 - tail)
+    };
+    Some((low, high))
+}
+
+/// Garwood confidence interval for the mean of a
+/// [`Poisson`] distribution from an observed `count`, at
+/// confidence `level` (such as 0.95). Like
+/// [`clopper_pearson`] the interval is exact, and it stays
+/// so for counts too small for the normal approximation.
+/// Divide the interval by the exposure (such as the time
+/// observed) for an interval for the rate. `None` for a
+/// `level` outside `0.0..1.0`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let (low, high) = garwood(4, 0.95).unwrap();
+/// assert!((low - 1.0898653736).abs() < 1e-9);
+/// assert!((high - 10.2415886754).abs() < 1e-9);
+/// assert_eq!(0.0, garwood(0, 0.95).unwrap().0);
+/// ```
+pub fn garwood(count: u64, level: f64) -> Option<(f64, f64)> {
+    let tail = half_alpha(level)?;
+    let k = count as f64;
+    //χ² quantiles on 2k and 2k + 2 degrees of freedom, halved
+    let low = if count == 0 {
+        0.0
+    } else {
+        gamma_inc_inv(k, tail)
+    };
+    let high = gamma_inc_inv(k + 1.0, 1.0 - tail);
+    Some((low, high))
+}
+
+/// Empirical cumulative distribution function of a sample:
+/// the distribution putting probability `1 / n` on each of
+/// its `n` values. It estimates the distribution the values
+/// were drawn from without assuming its form, as the
+/// Kolmogorov–Smirnov tests and QQ plots compare.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let ecdf = Ecdf::new(&[3.0, 1.0, 2.0, 2.0]).unwrap();
+/// assert_eq!(0.0, ecdf.eval(0.5));
+/// assert_eq!(0.75, ecdf.eval(2.0));
+/// assert_eq!(1.0, ecdf.eval(10.0));
+/// assert_eq!(Some(2.0), ecdf.inverse(0.5));
+/// assert_eq!(Some(3.0), ecdf.inverse(0.8));
+/// let tenths: Vec<f64> = (1..=10).map(f64::from).collect();
+/// assert_eq!(Some(3.0), Ecdf::new(&tenths).unwrap().inverse(0.3));
+/// let steps: Vec<(f64, f64)> = ecdf.steps().collect();
+/// assert_eq!(vec![(1.0, 0.25), (2.0, 0.75), (3.0, 1.0)], steps);
+/// assert_eq!(None, Ecdf::new(&[]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ecdf {
+    sorted: Vec<f64>,
+}
+
+impl Ecdf {
+    /// Empirical distribution of input values, or `None` for
+    /// an empty list or values containing NaN.
+    pub fn new(nums: &[f64]) -> Option<Ecdf> {
+        if nums.is_empty() || has_nan(nums) {
+            return None;
+        }
+        Some(Ecdf {
+            sorted: sorted(nums),
+        })
+    }
+
+    /// Number of values in the sample.
+    pub fn count(&self) -> usize {
+        self.sorted.len()
+    }
+
+    /// The sample's values, sorted.
+    pub fn values(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    /// Fraction of the values at most `x`: the percentile
+    /// rank of `x`, as a proportion.
+    pub fn eval(&self, x: f64) -> f64 {
+        let below = self.sorted.partition_point(|&v| v <= x);
+        below as f64 / self.sorted.len() as f64
+    }
+
+    /// The least of the values at which the [`eval`] is at
+    /// least `q`, which for `q` of 0.0 is the least value.
+    /// `None` unless `q` is in `0.0..=1.0`.
+    ///
+    /// [`eval`]: Ecdf::eval
+    pub fn inverse(&self, q: f64) -> Option<f64> {
+        if !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        let n = self.sorted.len();
+        let fraction = |i: usize| i as f64 / n as f64;
+        //the least i with i / n >= q, computed as eval
+        //computes it, which q * n can round past
+        let mut i = ((q * n as f64).ceil() as usize).clamp(1, n);
+        while i > 1 && fraction(i - 1) >= q {
+            i -= 1;
+        }
+        while i < n && fraction(i) < q {
+            i += 1;
+        }
+        Some(self.sorted[i - 1])
+    }
+
+    /// The steps of the function, in increasing order: each
+    /// distinct value paired with the [`eval`] at it, the
+    /// fraction of the values at most it.
+    ///
+    /// [`eval`]: Ecdf::eval
+    pub fn steps(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        let n = self.sorted.len();
+        self.sorted
+            .iter()
+            .enumerate()
+            .filter(move |&(i, v)| i + 1 == n || self.sorted[i + 1] != *v)
+            .map(move |(i, &v)| (v, (i + 1) as f64 / n as f64))
+    }
+}
+
+/// Plotting position of the `i`th of `n` sorted values, from
+/// 0: the probability at which to compare it with a
+/// distribution's quantile. This is R's `ppoints`, `(i + 1 -
+/// a) / (n + 1 - 2a)` with `a` of 3/8 for up to ten values
+/// and 1/2 for more.
+fn plotting_position(i: usize, n: usize) -> f64 {
+    let a = if n <= 10 { 0.375 } else { 0.5 };
+    (i as f64 + 1.0 - a) / (n as f64 + 1.0 - 2.0 * a)
+}
+
+/// Points of a quantile-quantile plot of input values
+/// against a distribution, as pairs `(theoretical,
+/// sample)`: each sorted value paired with the distribution's
+/// quantile at its plotting position. The points lie near a
+/// line when the values come from a distribution of the same
+/// family, on the identity line when from the distribution
+/// itself. Undefined for an empty list and for values
+/// containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let points = qq_points(&[0.3, -1.2, 0.8], &Normal::standard()).unwrap();
+/// assert_eq!(3, points.len());
+/// assert!((points[0].0 + 0.8694238).abs() < 1e-6);
+/// assert_eq!((0.0, 0.3), points[1]);
+/// assert_eq!(-1.2, points[0].1);
+/// ```
+pub fn qq_points<D: Continuous + ?Sized>(nums: &[f64], dist: &D) -> Option<Vec<(f64, f64)>> {
+    if nums.is_empty() || has_nan(nums) {
+        return None;
+    }
+    let n = nums.len();
+    let points = sorted(nums)
+        .into_iter()
+        .enumerate()
+        .map(|(i, x)| (dist.inv_cdf(plotting_position(i, n)).unwrap(), x))
+        .collect();
+    Some(points)
+}
+
+/// Points of a quantile-quantile plot of two groups of
+/// values against each other, as pairs `(x, y)` of
+/// corresponding quantiles. There is a point for each value
+/// of the smaller group, paired with the quantile of the
+/// larger group interpolated at the same fraction of the
+/// way through it, as R's `qqplot` does; for groups of the
+/// same size the points are the sorted values paired. The
+/// points lie on the identity line when the groups have the
+/// same distribution. Undefined for an empty group and for
+/// values containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::dist::*;
+/// let points = qq_points_two_sample(&[3.0, 1.0, 2.0], &[10.0, 30.0, 20.0]).unwrap();
+/// assert_eq!(vec![(1.0, 10.0), (2.0, 20.0), (3.0, 30.0)], points);
+/// let points = qq_points_two_sample(&[1.0, 2.0, 3.0, 4.0, 5.0], &[0.0, 1.0, 2.0]).unwrap();
+/// assert_eq!(vec![(1.0, 0.0), (3.0, 1.0), (5.0, 2.0)], points);
+/// ```
+pub fn qq_points_two_sample(xs: &[f64], ys: &[f64]) -> Option<Vec<(f64, f64)>> {
+    if xs.is_empty() || ys.is_empty() || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let (xs, ys) = (sorted(xs), sorted(ys));
+    //the quantiles of the larger group at the fractions
+    //through the smaller
+    let matched = |larger: &[f64], m: usize| -> Vec<f64> {
+        (0..m)
+            .map(|j| {
+                let q = if m == 1 {
+                    0.5
+                } else {
+                    j as f64 / (m - 1) as f64
+                };
+                quantile_sorted(larger, q, QuantileMethod::Linear).unwrap()
+            })
+            .collect()
+    };
+    let points = if xs.len() > ys.len() {
+        matched(&xs, ys.len()).into_iter().zip(ys).collect()
+    } else if ys.len() > xs.len() {
+        let m = xs.len();
+        xs.into_iter().zip(matched(&ys, m)).collect()
+    } else {
+        xs.into_iter().zip(ys).collect()
+    };
+    Some(points)
+}
@@ -0,0 +1,112 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Effect sizes for comparing two groups of values: how
+//! large the difference between the groups is, on a scale
+//! that does not depend on the units of the values or on
+//! the number of them, which a p-value from the
+//! [`tests`](crate::tests) does not say.
+//!
+//! Each effect size is positive when the first group tends
+//! to be larger, and is undefined for input containing NaN.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::special::ln_gamma;
+use crate::{has_nan, mean, sample_variance, sorted};
+
+/// Difference of the means of two groups and their pooled
+/// standard deviation, with its degrees of freedom.
+fn standardized(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+    if has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let (xvar, yvar) = (sample_variance(xs)?, sample_variance(ys)?);
+    let (nx, ny) = (xs.len() as f64, ys.len() as f64);
+    let df = nx + ny - 2.0;
+    let pooled = (((nx - 1.0) * xvar + (ny - 1.0) * yvar) / df).sqrt();
+    if pooled <= 0.0 {
+        return None;
+    }
+    Some(((mean(xs)? - mean(ys)?) / pooled, df))
+}
+
+/// Cohen's d of two groups of values: the difference of
+/// their means in units of their pooled sample standard
+/// deviation. By Cohen's rule of thumb, 0.2 is a small
+/// effect, 0.5 medium and 0.8 large. The estimate is biased
+/// away from zero for small groups, which [`hedges_g`]
+/// corrects. Undefined unless each group has at least two
+/// values, and when the values within both groups are all
+/// equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::effect::*;
+/// let d = cohens_d(&[1.0, 2.0, 3.0, 4.0, 5.0], &[3.0, 4.0, 5.0, 6.0, 7.0]).unwrap();
+/// assert!((d + 2.0 / 2.5f64.sqrt()).abs() < 1e-12);
+/// assert_eq!(None, cohens_d(&[1.0], &[3.0, 4.0]));
+/// ```
+pub fn cohens_d(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    standardized(xs, ys).map(|(d, _)| d)
+}
+
+/// Hedges' g of two groups of values: [`cohens_d`] times the
+/// exact correction `Γ(m/2) / (√(m/2) Γ((m - 1)/2))`, for
+/// `m` the degrees of freedom of the pooled standard
+/// deviation, that makes it an unbiased estimate of the
+/// standardized difference of the means of normal
+/// distributions. Undefined as for `cohens_d`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::effect::*;
+/// let g = hedges_g(&[1.0, 2.0, 3.0, 4.0, 5.0], &[3.0, 4.0, 5.0, 6.0, 7.0]).unwrap();
+/// assert!((g + 1.1418394343377738).abs() < 1e-12);
+/// ```
+pub fn hedges_g(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let (d, df) = standardized(xs, ys)?;
+    let half = 0.5 * df;
+    let correction = (ln_gamma(half) - ln_gamma(half - 0.5)).exp() / half.sqrt();
+    Some(d * correction)
+}
+
+/// Cliff's delta of two groups of values: the probability
+/// that a value from the first group is larger than one
+/// from the second, less the probability that it is
+/// smaller. It ranges from -1.0 to 1.0 and depends only on
+/// the order of the values, so it needs no assumption about
+/// their distribution and is unmoved by outliers. By
+/// Romano's rule of thumb, magnitudes of 0.147, 0.33 and
+/// 0.474 mark small, medium and large effects. Undefined for
+/// an empty group.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::effect::*;
+/// let delta = cliffs_delta(&[1.0, 2.0, 3.0, 4.0, 5.0], &[3.0, 4.0, 5.0, 6.0, 7.0]).unwrap();
+/// assert!((delta + 0.64).abs() < 1e-15);
+/// assert_eq!(Some(1.0), cliffs_delta(&[10.0, 1e9], &[1.0, 2.0]));
+/// ```
+pub fn cliffs_delta(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.is_empty() || ys.is_empty() || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let ys = sorted(ys);
+    //for each x, the ys below it less the ys above it
+    let dominance: i64 = xs
+        .iter()
+        .map(|&x| {
+            let below = ys.partition_point(|&y| y < x);
+            let above = ys.len() - ys.partition_point(|&y| y <= x);
+            below as i64 - above as i64
+        })
+        .sum();
+    Some(dominance as f64 / (xs.len() as f64 * ys.len() as f64))
+}
@@ -0,0 +1,48 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Errors explaining why a statistic is undefined.
+
+use core::fmt;
+
+/// Reason a statistic could not be computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatsError {
+    /// The input list was empty.
+    EmptyInput,
+    /// The input list contained a NaN.
+    ContainsNaN,
+    /// The statistic needs more values than were given.
+    InsufficientData {
+        /// Smallest number of values for which the
+        /// statistic is defined.
+        needed: usize,
+        /// Number of values given.
+        found: usize,
+    },
+    /// The input values were all equal, so a statistic
+    /// normalized by their spread is undefined.
+    ZeroVariance,
+    /// A parameter was out of its valid range. The payload
+    /// names the parameter.
+    InvalidParameter(&'static str),
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StatsError::EmptyInput => write!(f, "empty input"),
+            StatsError::ContainsNaN => write!(f, "input contains NaN"),
+            StatsError::InsufficientData { needed, found } => {
+                write!(f, "need at least {} values, found {}", needed, found)
+            }
+            StatsError::ZeroVariance => write!(f, "input has zero variance"),
+            StatsError::InvalidParameter(name) => write!(f, "invalid parameter {}", name),
+        }
+    }
+}
+
+impl core::error::Error for StatsError {}
@@ -0,0 +1,134 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Method-call syntax for the statistics functions.
+
+use crate::{extrema, QuantileMethod, Summary};
+
+/// Statistics as methods on a slice of floating-point
+/// numbers. Each method returns the same result as the free
+/// function of the same name. Through auto-deref the methods
+/// are also available on `Vec<f64>` and arrays.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = vec![1.0, 2.0, 3.0, 4.0];
+/// assert_eq!(Some(2.5), nums.mean());
+/// assert_eq!(Some(2.5), nums.median());
+/// assert_eq!(Some(4.0), nums.max());
+/// ```
+/// ```
+/// # use stats::*;
+/// let squares: Vec<f64> = (1..=3).map(|i| (i * i) as f64).collect();
+/// assert_eq!(Some(9.0), squares.quantile(1.0));
+/// ```
+pub trait StatsExt {
+    /// See [`mean`](crate::mean).
+    fn mean(&self) -> Option<f64>;
+    /// See [`variance`](crate::variance).
+    fn variance(&self) -> Option<f64>;
+    /// See [`sample_variance`](crate::sample_variance).
+    fn sample_variance(&self) -> Option<f64>;
+    /// See [`stddev`](crate::stddev).
+    fn stddev(&self) -> Option<f64>;
+    /// See [`sample_stddev`](crate::sample_stddev).
+    fn sample_stddev(&self) -> Option<f64>;
+    /// See [`skewness`](crate::skewness).
+    fn skewness(&self) -> Option<f64>;
+    /// See [`kurtosis`](crate::kurtosis).
+    fn kurtosis(&self) -> Option<f64>;
+    /// See [`median`](crate::median).
+    fn median(&self) -> Option<f64>;
+    /// See [`quantile`](crate::quantile).
+    fn quantile(&self, q: f64) -> Option<f64>;
+    /// See [`quantile_with`](crate::quantile_with).
+    fn quantile_with(&self, q: f64, method: QuantileMethod) -> Option<f64>;
+    /// See [`iqr`](crate::iqr).
+    fn iqr(&self) -> Option<f64>;
+    /// See [`mode`](crate::mode).
+    fn mode(&self, tolerance: f64) -> Option<f64>;
+    /// See [`l2`](crate::l2).
+    fn l2(&self) -> Option<f64>;
+    /// See [`extrema::min`].
+    fn min(&self) -> Option<f64>;
+    /// See [`extrema::max`].
+    fn max(&self) -> Option<f64>;
+    /// See [`extrema::range`].
+    fn range(&self) -> Option<f64>;
+    /// See [`Summary::new`].
+    fn summary(&self) -> Option<Summary>;
+}
+
+impl StatsExt for [f64] {
+    fn mean(&self) -> Option<f64> {
+        crate::mean(self)
+    }
+
+    fn variance(&self) -> Option<f64> {
+        crate::variance(self)
+    }
+
+    fn sample_variance(&self) -> Option<f64> {
+        crate::sample_variance(self)
+    }
+
+    fn stddev(&self) -> Option<f64> {
+        crate::stddev(self)
+    }
+
+    fn sample_stddev(&self) -> Option<f64> {
+        crate::sample_stddev(self)
+    }
+
+    fn skewness(&self) -> Option<f64> {
+        crate::skewness(self)
+    }
+
+    fn kurtosis(&self) -> Option<f64> {
+        crate::kurtosis(self)
+    }
+
+    fn median(&self) -> Option<f64> {
+        crate::median(self)
+    }
+
+    fn quantile(&self, q: f64) -> Option<f64> {
+        crate::quantile(self, q)
+    }
+
+    fn quantile_with(&self, q: f64, method: QuantileMethod) -> Option<f64> {
+        crate::quantile_with(self, q, method)
+    }
+
+    fn iqr(&self) -> Option<f64> {
+        crate::iqr(self)
+    }
+
+    fn mode(&self, tolerance: f64) -> Option<f64> {
+        crate::mode(self, tolerance)
+    }
+
+    fn l2(&self) -> Option<f64> {
+        crate::l2(self)
+    }
+
+    fn min(&self) -> Option<f64> {
+        extrema::min(self)
+    }
+
+    fn max(&self) -> Option<f64> {
+        extrema::max(self)
+    }
+
+    fn range(&self) -> Option<f64> {
+        extrema::range(self)
+    }
+
+    fn summary(&self) -> Option<Summary> {
+        Summary::new(self)
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Extreme values of a slice of floating-point numbers.
+//!
+//! NaN is unordered, so a list containing a NaN has no
+//! minimum or maximum: every function here returns `None`
+//! for such a list, just as for an empty list. Infinities
+//! are ordinary values. When the extreme value occurs more
+//! than once, `argmin` and `argmax` report the first
+//! occurrence.
+
+/// Index and value of the smallest and largest input values,
+/// found in a single pass.
+fn extremes(nums: &[f64]) -> Option<((usize, f64), (usize, f64))> {
+    let first = *nums.first()?;
+    let mut lo = (0, first);
+    let mut hi = (0, first);
+    for (i, &x) in nums.iter().enumerate() {
+        if x.is_nan() {
+            return None;
+        }
+        if x < lo.1 {
+            lo = (i, x);
+        }
+        if x > hi.1 {
+            hi = (i, x);
+        }
+    }
+    Some((lo, hi))
+}
+
+/// Smallest input value.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(None, min(&[1.0, f64::NAN]));
+/// ```
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(-2.0), min(&[1.0, -2.0, 3.0]));
+/// ```
+pub fn min(nums: &[f64]) -> Option<f64> {
+    extremes(nums).map(|(lo, _)| lo.1)
+}
+
+/// Largest input value.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(None, max(&[]));
+/// ```
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(3.0), max(&[1.0, -2.0, 3.0]));
+/// ```
+pub fn max(nums: &[f64]) -> Option<f64> {
+    extremes(nums).map(|(_, hi)| hi.1)
+}
+
+/// Index of the first occurrence of the smallest input
+/// value.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(1), argmin(&[1.0, -2.0, 3.0, -2.0]));
+/// ```
+pub fn argmin(nums: &[f64]) -> Option<usize> {
+    extremes(nums).map(|(lo, _)| lo.0)
+}
+
+/// Index of the first occurrence of the largest input
+/// value.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(2), argmax(&[1.0, -2.0, 3.0, 3.0]));
+/// ```
+pub fn argmax(nums: &[f64]) -> Option<usize> {
+    extremes(nums).map(|(_, hi)| hi.0)
+}
+
+/// Range (largest minus smallest) of input values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(0.0), range(&[7.0]));
+/// ```
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(5.0), range(&[1.0, -2.0, 3.0]));
+/// ```
+pub fn range(nums: &[f64]) -> Option<f64> {
+    extremes(nums).map(|(lo, hi)| hi.1 - lo.1)
+}
+
+/// Midrange (average of largest and smallest) of input
+/// values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::extrema::*;
+/// assert_eq!(Some(0.5), midrange(&[1.0, -2.0, 3.0]));
+/// ```
+pub fn midrange(nums: &[f64]) -> Option<f64> {
+    extremes(nums).map(|(lo, hi)| (hi.1 + lo.1) / 2.0)
+}
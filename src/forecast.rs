@@ -0,0 +1,385 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Forecasting by exponential smoothing, which tracks the
+//! level of a series, and optionally its trend and additive
+//! seasonality, as averages whose weights decay
+//! exponentially into the past.
+//!
+//! Each model is fitted to a series with given smoothing
+//! parameters, or by `fit`, which chooses the parameters in
+//! `0.0..=1.0` minimizing the sum of squared errors (SSE) of
+//! the model's one-step-ahead forecasts over the series. A
+//! parameter near 1.0 makes its component follow the latest
+//! values; near 0.0, it barely changes. The fitted model
+//! forecasts any number of steps past the end of the series.
+//! Everything is undefined for values that are NaN or
+//! infinite and for too short a series.
+
+use alloc::vec::Vec;
+
+use crate::all_finite;
+use crate::optimize::minimize_unit_box;
+
+/// True if `p` is a valid smoothing parameter.
+fn is_parameter(p: f64) -> bool {
+    (0.0..=1.0).contains(&p)
+}
+
+/// Simple exponential smoothing, for series with neither
+/// trend nor seasonality. The level starts at the first
+/// value, and each value `x` moves it by `alpha (x -
+/// level)`; every forecast is the final level.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::forecast::*;
+/// let model = SimpleSmoothing::with_alpha(&[10.0, 20.0, 20.0], 0.5).unwrap();
+/// assert_eq!(17.5, model.level());
+/// assert_eq!(125.0, model.sse());
+/// assert_eq!(vec![17.5, 17.5], model.forecast(2));
+///
+/// let xs = [3.0, 5.0, 4.0, 6.0, 5.0, 7.0, 6.0];
+/// let fitted = SimpleSmoothing::fit(&xs).unwrap();
+/// for alpha in [0.1, 0.3, 0.5, 0.7, 0.9] {
+///     assert!(fitted.sse() <= SimpleSmoothing::with_alpha(&xs, alpha).unwrap().sse());
+/// }
+/// assert_eq!(None, SimpleSmoothing::fit(&[1.0, f64::INFINITY, 2.0]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleSmoothing {
+    alpha: f64,
+    level: f64,
+    sse: f64,
+}
+
+impl SimpleSmoothing {
+    /// Model of input values with the `alpha` minimizing the
+    /// SSE. Undefined for fewer than two values.
+    pub fn fit(nums: &[f64]) -> Option<SimpleSmoothing> {
+        SimpleSmoothing::with_alpha(nums, 0.3)?;
+        let best = minimize_unit_box(|p| simple_run(nums, p[0]).1, &[0.3]);
+        SimpleSmoothing::with_alpha(nums, best[0])
+    }
+
+    /// Model of input values with the given `alpha`.
+    /// Undefined for fewer than two values and for an `alpha`
+    /// outside `0.0..=1.0`.
+    pub fn with_alpha(nums: &[f64], alpha: f64) -> Option<SimpleSmoothing> {
+        if nums.len() < 2 || !all_finite(nums) || !is_parameter(alpha) {
+            return None;
+        }
+        let (level, sse) = simple_run(nums, alpha);
+        Some(SimpleSmoothing { alpha, level, sse })
+    }
+
+    /// The smoothing parameter of the level.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The level at the end of the series.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Sum of squared one-step-ahead forecast errors.
+    pub fn sse(&self) -> f64 {
+        self.sse
+    }
+
+    /// Forecasts of the next `steps` values.
+    pub fn forecast(&self, steps: usize) -> Vec<f64> {
+        (0..steps).map(|_| self.level).collect()
+    }
+}
+
+/// Final level and SSE of simple exponential smoothing.
+fn simple_run(nums: &[f64], alpha: f64) -> (f64, f64) {
+    let mut level = nums[0];
+    let mut sse = 0.0;
+    for &x in &nums[1..] {
+        let error = x - level;
+        sse += error * error;
+        level += alpha * error;
+    }
+    (level, sse)
+}
+
+/// Holt's linear method, double exponential smoothing, for
+/// series with a trend but no seasonality. The level starts
+/// at the second value and the trend at the difference of
+/// the first two. Each value updates the level with weight
+/// `alpha`, then the change in level updates the trend with
+/// weight `beta`; the forecast `h` steps ahead is `level + h
+/// trend`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::forecast::*;
+/// let model = Holt::fit(&[1.0, 3.0, 5.0, 7.0, 9.0]).unwrap();
+/// assert_eq!(0.0, model.sse());
+/// assert_eq!(vec![11.0, 13.0, 15.0], model.forecast(3));
+///
+/// let model = Holt::with_params(&[1.0, 3.0, 4.0], 0.5, 0.5).unwrap();
+/// assert_eq!((4.5, 1.75), (model.level(), model.trend()));
+/// assert_eq!(1.0, model.sse());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Holt {
+    alpha: f64,
+    beta: f64,
+    level: f64,
+    trend: f64,
+    sse: f64,
+}
+
+impl Holt {
+    /// Model of input values with the `alpha` and `beta`
+    /// minimizing the SSE. Undefined for fewer than three
+    /// values.
+    pub fn fit(nums: &[f64]) -> Option<Holt> {
+        Holt::with_params(nums, 0.3, 0.1)?;
+        let best = minimize_unit_box(|p| holt_run(nums, p[0], p[1]).2, &[0.3, 0.1]);
+        Holt::with_params(nums, best[0], best[1])
+    }
+
+    /// Model of input values with the given `alpha` and
+    /// `beta`. Undefined for fewer than three values and for
+    /// parameters outside `0.0..=1.0`.
+    pub fn with_params(nums: &[f64], alpha: f64, beta: f64) -> Option<Holt> {
+        if nums.len() < 3 || !all_finite(nums) || !is_parameter(alpha) || !is_parameter(beta) {
+            return None;
+        }
+        let (level, trend, sse) = holt_run(nums, alpha, beta);
+        Some(Holt {
+            alpha,
+            beta,
+            level,
+            trend,
+            sse,
+        })
+    }
+
+    /// The smoothing parameter of the level.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The smoothing parameter of the trend.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// The level at the end of the series.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// The trend, the change in level per step, at the end
+    /// of the series.
+    pub fn trend(&self) -> f64 {
+        self.trend
+    }
+
+    /// Sum of squared one-step-ahead forecast errors.
+    pub fn sse(&self) -> f64 {
+        self.sse
+    }
+
+    /// Forecasts of the next `steps` values.
+    pub fn forecast(&self, steps: usize) -> Vec<f64> {
+        (1..=steps)
+            .map(|h| self.level + h as f64 * self.trend)
+            .collect()
+    }
+}
+
+/// Final level and trend, and SSE, of Holt's method.
+fn holt_run(nums: &[f64], alpha: f64, beta: f64) -> (f64, f64, f64) {
+    let mut level = nums[1];
+    let mut trend = nums[1] - nums[0];
+    let mut sse = 0.0;
+    for &x in &nums[2..] {
+        let predicted = level + trend;
+        let error = x - predicted;
+        sse += error * error;
+        let previous = level;
+        level = predicted + alpha * error;
+        trend += beta * (level - previous - trend);
+    }
+    (level, trend, sse)
+}
+
+/// Holt–Winters method, triple exponential smoothing, for
+/// series with a trend and additive seasonality of
+/// `period` steps. Starting from the first two periods, the
+/// trend is the change in mean per step between them, the
+/// level is the mean of the first carried along that trend
+/// from the middle of the first period to its end, and the
+/// seasonal components are the deviations of the first
+/// period from the trend line through its mean. Each
+/// value updates the level with weight `alpha`, the trend
+/// with weight `beta`, and the seasonal component for its
+/// season with weight `gamma`; the forecast `h` steps ahead
+/// is `level + h trend` plus the component for that season.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::forecast::*;
+/// let season = [5.0, -2.0, 1.0, -4.0];
+/// let xs: Vec<f64> = (0..12).map(|t| 0.5 * t as f64 + season[t % 4]).collect();
+/// let model = HoltWinters::fit(&xs, 4).unwrap();
+/// assert!(model.sse() < 1e-20);
+/// for (h, y) in model.forecast(5).iter().enumerate() {
+///     let t = 12 + h;
+///     assert!((y - (0.5 * t as f64 + season[t % 4])).abs() < 1e-9);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HoltWinters {
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    level: f64,
+    trend: f64,
+    seasonals: Vec<f64>,
+    sse: f64,
+}
+
+impl HoltWinters {
+    /// Model of input values with seasonality of `period`
+    /// steps, with the `alpha`, `beta` and `gamma` minimizing
+    /// the SSE. Undefined for a `period` less than two and
+    /// for fewer than two periods of values.
+    pub fn fit(nums: &[f64], period: usize) -> Option<HoltWinters> {
+        HoltWinters::with_params(nums, period, 0.3, 0.1, 0.1)?;
+        let sse = |p: &[f64]| winters_run(nums, period, p[0], p[1], p[2]).3;
+        let best = minimize_unit_box(sse, &[0.3, 0.1, 0.1]);
+        HoltWinters::with_params(nums, period, best[0], best[1], best[2])
+    }
+
+    /// Model of input values with seasonality of `period`
+    /// steps and the given `alpha`, `beta` and `gamma`.
+    /// Undefined for a `period` less than two, for fewer
+    /// than two periods of values, and for parameters outside
+    /// `0.0..=1.0`.
+    pub fn with_params(
+        nums: &[f64],
+        period: usize,
+        alpha: f64,
+        beta: f64,
+        gamma: f64,
+    ) -> Option<HoltWinters> {
+        if period < 2 || nums.len() / 2 < period || !all_finite(nums) {
+            return None;
+        }
+        if !is_parameter(alpha) || !is_parameter(beta) || !is_parameter(gamma) {
+            return None;
+        }
+        let (level, trend, seasonals, sse) = winters_run(nums, period, alpha, beta, gamma);
+        Some(HoltWinters {
+            alpha,
+            beta,
+            gamma,
+            level,
+            trend,
+            seasonals,
+            sse,
+        })
+    }
+
+    /// The smoothing parameter of the level.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// The smoothing parameter of the trend.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// The smoothing parameter of the seasonal components.
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// The number of steps in a season.
+    pub fn period(&self) -> usize {
+        self.seasonals.len()
+    }
+
+    /// The level at the end of the series.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// The trend, the change in level per step, at the end
+    /// of the series.
+    pub fn trend(&self) -> f64 {
+        self.trend
+    }
+
+    /// The seasonal components of the next `period` steps
+    /// after the end of the series.
+    pub fn seasonals(&self) -> &[f64] {
+        &self.seasonals
+    }
+
+    /// Sum of squared one-step-ahead forecast errors.
+    pub fn sse(&self) -> f64 {
+        self.sse
+    }
+
+    /// Forecasts of the next `steps` values.
+    pub fn forecast(&self, steps: usize) -> Vec<f64> {
+        let period = self.period();
+        (1..=steps)
+            .map(|h| self.level + h as f64 * self.trend + self.seasonals[(h - 1) % period])
+            .collect()
+    }
+}
+
+/// Final level, trend and seasonal components, the latter
+/// in order from the step after the series, and SSE, of the
+/// Holt–Winters method.
+fn winters_run(
+    nums: &[f64],
+    period: usize,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+) -> (f64, f64, Vec<f64>, f64) {
+    let m = period as f64;
+    let first = nums[..period].iter().sum::<f64>() / m;
+    let second = nums[period..2 * period].iter().sum::<f64>() / m;
+    let mut trend = (second - first) / m;
+    //centered at the middle of the first period; moved to its end
+    let middle = 0.5 * (m - 1.0);
+    let mut level = first + middle * trend;
+    let mut seasonals: Vec<f64> = nums[..period]
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x - (first + (i as f64 - middle) * trend))
+        .collect();
+    let mut sse = 0.0;
+    for (t, &x) in nums.iter().enumerate().skip(period) {
+        let season = &mut seasonals[t % period];
+        let error = x - (level + trend + *season);
+        sse += error * error;
+        let previous = level;
+        level = alpha * (x - *season) + (1.0 - alpha) * (level + trend);
+        trend = beta * (level - previous) + (1.0 - beta) * trend;
+        *season = gamma * (x - level) + (1.0 - gamma) * *season;
+    }
+    seasonals.rotate_left(nums.len() % period);
+    (level, trend, seasonals, sse)
+}
@@ -0,0 +1,133 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Imputation of missing values, filling the holes in a
+//! list of values so that statistics can be computed from
+//! it.
+//!
+//! Missing values are either `None` in a list of options or
+//! NaN, which also counts as missing inside `Some`. Each
+//! function returns the filled values with the number of
+//! them that were filled. Filling is undefined for a list
+//! with missing values but no others to fill them from.
+//! Imputed values understate the spread of the data, so
+//! statistics of filled data should be read with the
+//! number imputed in mind.
+
+use alloc::vec::Vec;
+
+use crate::{mean, median};
+
+/// How [`fill`] chooses the value for each hole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Strategy {
+    /// The mean of the values present.
+    #[default]
+    Mean,
+    /// The median of the values present, which is robust to
+    /// outliers among them.
+    Median,
+    /// The last value present before the hole, as for a
+    /// reading that holds until the next; holes at the start
+    /// take the first value present.
+    ForwardFill,
+    /// Linear interpolation by position between the values
+    /// present either side of the hole; holes at either end
+    /// take the nearest value present.
+    Linear,
+}
+
+/// Fill the missing values of `values` by the given
+/// `strategy`, returning the filled values and the number
+/// filled.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::impute::*;
+/// let values = [None, Some(1.0), None, None, Some(4.0), Some(f64::NAN)];
+/// let (filled, count) = fill(&values, Strategy::Linear).unwrap();
+/// assert_eq!(vec![1.0, 1.0, 2.0, 3.0, 4.0, 4.0], filled);
+/// assert_eq!(4, count);
+/// let (filled, _) = fill(&values, Strategy::ForwardFill).unwrap();
+/// assert_eq!(vec![1.0, 1.0, 1.0, 1.0, 4.0, 4.0], filled);
+/// let (filled, _) = fill(&values, Strategy::Mean).unwrap();
+/// assert_eq!(vec![2.5, 1.0, 2.5, 2.5, 4.0, 2.5], filled);
+/// assert_eq!(None, fill(&[None, None], Strategy::Mean));
+/// ```
+pub fn fill(values: &[Option<f64>], strategy: Strategy) -> Option<(Vec<f64>, usize)> {
+    let present = |v: &Option<f64>| v.filter(|x| !x.is_nan());
+    let known: Vec<(usize, f64)> = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| present(v).map(|x| (i, x)))
+        .collect();
+    let count = values.len() - known.len();
+    if count == 0 {
+        return Some((known.into_iter().map(|(_, x)| x).collect(), 0));
+    }
+    let (first, last) = (known.first()?.1, known.last()?.1);
+    let filled = match strategy {
+        Strategy::Mean | Strategy::Median => {
+            let xs: Vec<f64> = known.iter().map(|&(_, x)| x).collect();
+            let center = match strategy {
+                Strategy::Mean => mean(&xs)?,
+                _ => median(&xs)?,
+            };
+            values
+                .iter()
+                .map(|v| present(v).unwrap_or(center))
+                .collect()
+        }
+        Strategy::ForwardFill => {
+            let mut previous = first;
+            values
+                .iter()
+                .map(|v| {
+                    previous = present(v).unwrap_or(previous);
+                    previous
+                })
+                .collect()
+        }
+        Strategy::Linear => {
+            //known[next] is the first value present at or after i
+            let mut next = 0;
+            (0..values.len())
+                .map(|i| {
+                    if next < known.len() && known[next].0 < i {
+                        next += 1;
+                    }
+                    if next == known.len() {
+                        return last;
+                    }
+                    let (j, x) = known[next];
+                    if j == i || next == 0 {
+                        return x;
+                    }
+                    let (h, w) = known[next - 1];
+                    w + (x - w) * (i - h) as f64 / (j - h) as f64
+                })
+                .collect()
+        }
+    };
+    Some((filled, count))
+}
+
+/// Fill the NaN values of `nums` by the given `strategy`,
+/// returning the filled values and the number filled, as
+/// for [`fill`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::impute::*;
+/// let (filled, count) = fill_nan(&[3.0, f64::NAN, 1.0, 2.0], Strategy::Median).unwrap();
+/// assert_eq!((vec![3.0, 2.0, 1.0, 2.0], 1), (filled, count));
+/// ```
+pub fn fill_nan(nums: &[f64], strategy: Strategy) -> Option<(Vec<f64>, usize)> {
+    let values: Vec<Option<f64>> = nums.iter().map(|&x| Some(x)).collect();
+    fill(&values, strategy)
+}
@@ -0,0 +1,75 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics of a slice of integers, computed exactly
+//! before a single final rounding to `f64`.
+//!
+//! Values are accumulated as `i128`, so sums of any
+//! realistic number of 64-bit integers cannot overflow.
+
+/// Exact sum of input values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::integer::*;
+/// assert_eq!(0, sum::<i32>(&[]));
+/// ```
+/// ```
+/// # use stats::integer::*;
+/// assert_eq!(2 * i64::MAX as i128, sum(&[i64::MAX, i64::MAX]));
+/// ```
+pub fn sum<T: Copy + Into<i128>>(nums: &[T]) -> i128 {
+    nums.iter().map(|&x| x.into()).sum()
+}
+
+/// Arithmetic mean of input values, rounded once from the
+/// exact sum. The mean of an empty list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::integer::*;
+/// assert_eq!(Some(0.0), mean::<u8>(&[]));
+/// ```
+/// ```
+/// # use stats::integer::*;
+/// assert_eq!(Some(i64::MAX as f64), mean(&[i64::MAX, i64::MAX]));
+/// ```
+pub fn mean<T: Copy + Into<i128>>(nums: &[T]) -> Option<f64> {
+    if nums.is_empty() {
+        return Some(0.0);
+    }
+    Some(sum(nums) as f64 / nums.len() as f64)
+}
+
+/// Median value of input values, averaging the two middle
+/// values of an even-length list. The median of an empty
+/// list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::integer::*;
+/// assert_eq!(None, median::<i16>(&[]));
+/// ```
+/// ```
+/// # use stats::integer::*;
+/// assert_eq!(Some(2.5), median(&[4u32, 1, 3, 2]));
+/// ```
+pub fn median<T: Copy + Ord + Into<i128>>(nums: &[T]) -> Option<f64> {
+    let mut nums = nums.to_vec();
+    nums.sort_unstable();
+    let n = nums.len();
+    if n == 0 {
+        return None;
+    }
+    let hi: i128 = nums[n / 2].into();
+    if n % 2 == 1 {
+        return Some(hi as f64);
+    }
+    let lo: i128 = nums[n / 2 - 1].into();
+    Some((lo + hi) as f64 / 2.0)
+}
@@ -0,0 +1,114 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Parametric confidence intervals.
+//!
+//! Each interval is returned as a pair `(lower, upper)` at
+//! a confidence `level` such as 0.95, and is undefined for a
+//! `level` outside `0.0..1.0` or input containing NaN.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::special::{gamma_inc_inv, normal_quantile, student_t_quantile};
+use crate::{has_nan, mean, sample_variance};
+
+/// `None` unless the confidence `level` is strictly between
+/// 0.0 and 1.0, else the probability `alpha / 2` in each
+/// tail outside the interval.
+pub(crate) fn half_alpha(level: f64) -> Option<f64> {
+    if level.is_nan() || level <= 0.0 || level >= 1.0 {
+        return None;
+    }
+    Some(0.5 * (1.0 - level))
+}
+
+/// Confidence interval for the mean of the distribution
+/// input values were drawn from, from Student's t
+/// distribution. It assumes the values are roughly normal,
+/// or numerous enough for their mean to be. Undefined for
+/// fewer than two values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::intervals::*;
+/// let (low, high) = mean_ci(&[1.0, 2.0, 3.0, 4.0, 5.0], 0.95).unwrap();
+/// let margin = 2.7764451051977987 * 0.5f64.sqrt();
+/// assert!((low - (3.0 - margin)).abs() < 1e-12);
+/// assert!((high - (3.0 + margin)).abs() < 1e-12);
+/// assert_eq!(None, mean_ci(&[1.0], 0.95));
+/// ```
+pub fn mean_ci(nums: &[f64], level: f64) -> Option<(f64, f64)> {
+    let tail = half_alpha(level)?;
+    if has_nan(nums) {
+        return None;
+    }
+    let (m, s2) = (mean(nums)?, sample_variance(nums)?);
+    let n = nums.len() as f64;
+    let margin = student_t_quantile(1.0 - tail, n - 1.0) * (s2 / n).sqrt();
+    Some((m - margin, m + margin))
+}
+
+/// Confidence interval for the variance of the distribution
+/// input values were drawn from, from the chi-square
+/// distribution of the sample variance. Unlike
+/// [`mean_ci`], it relies on the values being normal,
+/// however many there are. Undefined for fewer than two
+/// values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::intervals::*;
+/// let (low, high) = variance_ci(&[1.0, 2.0, 3.0, 4.0, 5.0], 0.95).unwrap();
+/// assert!((low - 0.897401296022).abs() < 1e-10);
+/// assert!((high - 20.643304955357).abs() < 1e-9);
+/// ```
+pub fn variance_ci(nums: &[f64], level: f64) -> Option<(f64, f64)> {
+    let tail = half_alpha(level)?;
+    if has_nan(nums) {
+        return None;
+    }
+    let s2 = sample_variance(nums)?;
+    let df = nums.len() as f64 - 1.0;
+    //χ² quantiles on df degrees of freedom
+    let chi2 = |p: f64| 2.0 * gamma_inc_inv(0.5 * df, p);
+    Some((df * s2 / chi2(1.0 - tail), df * s2 / chi2(tail)))
+}
+
+/// Wilson score confidence interval for the probability of
+/// success from `successes` in `trials`. It stays within
+/// `0.0..=1.0` and keeps close to its nominal coverage even
+/// for few trials or proportions near 0 or 1, where the
+/// textbook normal interval fails. For an interval
+/// guaranteed to cover at least the nominal proportion of
+/// the time, at the cost of width, use the exact
+/// [`clopper_pearson`](crate::dist::clopper_pearson)
+/// interval. Undefined for no trials or more successes than
+/// trials.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::intervals::*;
+/// let (low, high) = proportion_ci(3, 20, 0.95).unwrap();
+/// assert!((low - 0.0523687458962).abs() < 1e-12);
+/// assert!((high - 0.3604188647408).abs() < 1e-12);
+/// assert_eq!(None, proportion_ci(3, 2, 0.95));
+/// ```
+pub fn proportion_ci(successes: u64, trials: u64, level: f64) -> Option<(f64, f64)> {
+    let tail = half_alpha(level)?;
+    if trials == 0 || successes > trials {
+        return None;
+    }
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z = normal_quantile(1.0 - tail);
+    let z2n = z * z / n;
+    let center = (p + 0.5 * z2n) / (1.0 + z2n);
+    let margin = z / (1.0 + z2n) * (p * (1.0 - p) / n + 0.25 * z2n / n).sqrt();
+    Some(((center - margin).max(0.0), (center + margin).min(1.0)))
+}
@@ -0,0 +1,114 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics computed directly from an iterator in a single
+//! streaming pass, without collecting into a slice.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::welford;
+
+/// Welford accumulator for the count, mean and sum of
+/// squared deviations of a sequence.
+struct Moments {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl Moments {
+    fn of<I: Iterator<Item = f64>>(iter: I) -> Moments {
+        let (count, mean, m2) = welford(iter);
+        Moments { count, mean, m2 }
+    }
+
+    fn variance_ddof(&self, ddof: usize) -> Option<f64> {
+        if self.count <= ddof {
+            return None;
+        }
+        Some(self.m2 / (self.count - ddof) as f64)
+    }
+}
+
+/// Fold an iterator down to its smallest or largest value
+/// (as chosen by `keep`), returning `None` if it is empty or
+/// yields a NaN.
+fn extreme<I: Iterator<Item = f64>>(mut iter: I, keep: fn(f64, f64) -> bool) -> Option<f64> {
+    let mut best = iter.next()?;
+    if best.is_nan() {
+        return None;
+    }
+    for x in iter {
+        if x.is_nan() {
+            return None;
+        }
+        if keep(x, best) {
+            best = x;
+        }
+    }
+    Some(best)
+}
+
+/// Statistics of the `f64` values yielded by an
+/// iterator. Each method consumes the iterator in one pass
+/// with constant memory and follows the conventions of the
+/// slice function of the same name.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(5.5), (1..=10).map(f64::from).mean());
+/// ```
+/// ```
+/// # use stats::*;
+/// let evens = (0..1000).filter(|i| i % 2 == 0).map(f64::from);
+/// assert_eq!(Some(998.0), evens.maximum());
+/// ```
+pub trait IterStats: Iterator<Item = f64> + Sized {
+    /// Arithmetic mean; 0.0 for an empty iterator. See
+    /// [`mean`](crate::mean).
+    fn mean(self) -> Option<f64> {
+        Some(Moments::of(self).mean)
+    }
+
+    /// Population variance. See [`variance`](crate::variance).
+    fn variance(self) -> Option<f64> {
+        Moments::of(self).variance_ddof(0)
+    }
+
+    /// Sample variance. See
+    /// [`sample_variance`](crate::sample_variance).
+    fn sample_variance(self) -> Option<f64> {
+        Moments::of(self).variance_ddof(1)
+    }
+
+    /// Population standard deviation. See
+    /// [`stddev`](crate::stddev).
+    fn stddev(self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Sample standard deviation. See
+    /// [`sample_stddev`](crate::sample_stddev).
+    fn sample_stddev(self) -> Option<f64> {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    /// Smallest value, with the NaN handling of
+    /// [`extrema::min`](crate::extrema::min).
+    fn minimum(self) -> Option<f64> {
+        extreme(self, |x, best| x < best)
+    }
+
+    /// Largest value, with the NaN handling of
+    /// [`extrema::max`](crate::extrema::max).
+    fn maximum(self) -> Option<f64> {
+        extreme(self, |x, best| x > best)
+    }
+}
+
+impl<I: Iterator<Item = f64>> IterStats for I {}
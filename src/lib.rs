@@ -3,15 +3,259 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
-///! Functions to compute various statistics on a slice of
-///! floating-point numbers.
+//! Functions to compute various statistics on a slice of
+//! floating-point numbers.
+//!
+//! The core statistics (mean, variance, standard deviation,
+//! quantiles and the L2 norm) are generic over any
+//! [`num_traits::Float`] type, so `f32` data can be used
+//! directly; the [`integer`] module has exact paths for
+//! integer data. Everything else works on `f64`.
+//!
+//! The crate is `no_std` (needing only `alloc`) when built
+//! without its default `std` feature. The `simd` feature
+//! vectorizes the summation underlying [`sum`], [`mean`],
+//! the variance and standard deviation, [`l2`] and [`dot`].
+//! The `parallel` feature adds the multithreaded `parallel`
+//! module. The `serde` feature makes the accumulators,
+//! sketches and summaries serializable. The `rand` feature
+//! draws random samples from the [`dist`] distributions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod batch;
+pub mod bayes;
+mod checked;
+pub mod chunked;
+mod compactor;
+mod config;
+pub mod correlation;
+pub mod density;
+pub mod dist;
+pub mod effect;
+mod error;
+mod ext;
+pub mod extrema;
+pub mod forecast;
+pub mod impute;
+pub mod integer;
+pub mod intervals;
+mod iter;
+pub mod matrix;
+pub mod means;
+pub mod multivariate;
+mod nan;
+mod optimize;
+pub mod outliers;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+mod partial;
+pub mod power;
+pub mod quality;
+mod random;
+mod registry;
+pub mod regression;
+pub mod resampling;
+pub mod robust;
+pub mod sketches;
+pub mod smoothing;
+pub mod spc;
+mod special;
+pub mod streaming;
+pub mod summary;
+pub mod tests;
+pub mod timeseries;
+pub mod transform;
+pub mod weighted;
+
+pub use checked::*;
+pub use config::{Description, StatsConfig};
+pub use error::StatsError;
+pub use ext::StatsExt;
+pub use iter::IterStats;
+pub use nan::NanPolicy;
+pub use partial::PartialStats;
+pub use registry::{lookup, names, registry};
+pub use summary::Summary;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::Float;
+
+/// Convert a count or other small value to a float type.
+fn float<T: Float, N: num_traits::ToPrimitive>(n: N) -> T {
+    T::from(n).unwrap()
+}
 
 /// Type of statistics function. If the statistic
 /// is ill-defined, `None` will be returned.
 pub type StatFn = fn(&[f64]) -> Option<f64>;
 
+/// Running sum with Neumaier's compensation: the rounding
+/// error of each addition is recovered exactly and summed
+/// separately, so the error of the total does not grow with
+/// the number of terms.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Neumaier<T> {
+    sum: T,
+    compensation: T,
+}
+
+impl<T: Float> Neumaier<T> {
+    fn new() -> Neumaier<T> {
+        Neumaier {
+            sum: T::zero(),
+            compensation: T::zero(),
+        }
+    }
+
+    fn add(&mut self, x: T) {
+        //https://en.wikipedia.org/wiki/Kahan_summation_algorithm#Further_enhancements
+        let t = self.sum + x;
+        let (big, small) = if self.sum.abs() >= x.abs() {
+            (self.sum, x)
+        } else {
+            (x, self.sum)
+        };
+        self.compensation = self.compensation + ((big - t) + small);
+        self.sum = t;
+    }
+
+    /// Add in another partial sum, keeping both
+    /// compensations.
+    fn merge(&mut self, other: Neumaier<T>) {
+        self.add(other.sum);
+        self.compensation = self.compensation + other.compensation;
+    }
+
+    fn total(&self) -> T {
+        self.sum + self.compensation
+    }
+}
+
+/// Number of independent running sums in the summation
+/// kernel. With the `simd` feature, consecutive terms go to
+/// separate lanes, which the compiler keeps in vector
+/// registers; the result then differs from the scalar sum
+/// only in the order of rounding.
+const LANES: usize = if cfg!(feature = "simd") { 8 } else { 1 };
+
+/// Compensated running sums, one per lane.
+struct Lanes<T> {
+    sums: [T; LANES],
+    compensations: [T; LANES],
+}
+
+impl<T: Float> Lanes<T> {
+    fn new() -> Lanes<T> {
+        Lanes {
+            sums: [T::zero(); LANES],
+            compensations: [T::zero(); LANES],
+        }
+    }
+
+    /// Add one term to each lane, as in [`Neumaier::add`].
+    fn add(&mut self, terms: [T; LANES]) {
+        let lanes = self.sums.iter_mut().zip(self.compensations.iter_mut());
+        for ((sum, compensation), &x) in lanes.zip(terms.iter()) {
+            let t = *sum + x;
+            let (big, small) = if sum.abs() >= x.abs() {
+                (*sum, x)
+            } else {
+                (x, *sum)
+            };
+            *compensation = *compensation + ((big - t) + small);
+            *sum = t;
+        }
+    }
+
+    fn total(&self) -> Neumaier<T> {
+        let mut total = Neumaier::new();
+        for (&sum, &compensation) in self.sums.iter().zip(self.compensations.iter()) {
+            total.merge(Neumaier { sum, compensation });
+        }
+        total
+    }
+}
+
+/// Inputs longer than this are summed pairwise: split in
+/// half recursively until each block is at most this long.
+const PAIRWISE_BLOCK: usize = 128;
+
+/// Compensated sum of `f(x)` over input values. Long inputs
+/// are summed pairwise, so that the partial sums of the
+/// halves being combined have similar magnitudes and each
+/// block's working set stays in cache.
+fn pairwise<T: Float, F: Fn(T) -> T + Copy>(nums: &[T], f: F) -> Neumaier<T> {
+    pairwise_zip(nums, nums, move |x, _| f(x))
+}
+
+/// Compensated sum of `f(x, y)` over pairs of values from
+/// two slices of the same length, summed pairwise as in
+/// [`pairwise`].
+fn pairwise_zip<T: Float, F: Fn(T, T) -> T + Copy>(xs: &[T], ys: &[T], f: F) -> Neumaier<T> {
+    //https://en.wikipedia.org/wiki/Pairwise_summation
+    debug_assert_eq!(xs.len(), ys.len());
+    if xs.len() > PAIRWISE_BLOCK {
+        let mid = xs.len() / 2;
+        let mut sum = pairwise_zip(&xs[..mid], &ys[..mid], f);
+        sum.merge(pairwise_zip(&xs[mid..], &ys[mid..], f));
+        return sum;
+    }
+    let (xchunks, ychunks) = (xs.chunks_exact(LANES), ys.chunks_exact(LANES));
+    let rest = xchunks.remainder().iter().zip(ychunks.remainder());
+    let mut lanes = Lanes::new();
+    for (xchunk, ychunk) in xchunks.zip(ychunks) {
+        let mut terms = [T::zero(); LANES];
+        for (term, (&x, &y)) in terms.iter_mut().zip(xchunk.iter().zip(ychunk)) {
+            *term = f(x, y);
+        }
+        lanes.add(terms);
+    }
+    let mut sum = lanes.total();
+    for (&x, &y) in rest {
+        sum.add(f(x, y));
+    }
+    sum
+}
+
+/// Sum of input values, using compensated (Kahan-Neumaier)
+/// summation to avoid the error that naive summation
+/// accumulates when adding many values or values of very
+/// different magnitudes. Long inputs are summed pairwise in
+/// blocks. The sum of an empty list is 0.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [1.0, 1e100, 1.0, -1e100];
+/// assert_eq!(0.0, nums.iter().sum::<f64>());
+/// assert_eq!(2.0, sum(&nums));
+/// ```
+/// ```
+/// # use stats::*;
+/// let tenths = [0.1; 10];
+/// assert_ne!(1.0, tenths.iter().sum::<f64>());
+/// assert_eq!(1.0, sum(&tenths));
+/// ```
+/// ```
+/// # use stats::*;
+/// let tenths = vec![0.1f32; 1_000_000];
+/// assert_ne!(100_000.0, tenths.iter().sum::<f32>());
+/// assert_eq!(100_000.0, sum(&tenths));
+/// ```
+pub fn sum<T: Float>(nums: &[T]) -> T {
+    pairwise(nums, |x| x).total()
+}
+
 /// Arithmetic mean of input values. The mean of an empty
-/// list is 0.0.
+/// list is 0.0. The values are added with compensated
+/// summation; see [`sum`].
 ///
 /// # Examples:
 ///
@@ -23,21 +267,147 @@ pub type StatFn = fn(&[f64]) -> Option<f64>;
 /// # use stats::*;
 /// assert_eq!(Some(0.0), mean(&[-1.0, 1.0]));
 /// ```
-pub fn mean(nums: &[f64]) -> Option<f64> {
-    let sum: f64 = nums.iter().sum();
-    // array iter() trait method sum:  https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.count
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.5f32), mean(&[1.0f32, 2.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.5), mean(&[1.0, 1e100, 1.0, -1e100]));
+/// ```
+pub fn mean<T: Float>(nums: &[T]) -> Option<T> {
+    let sum = sum(nums);
 
-    let counter = nums.len() as f64;
-    //saw "as" keyword under "hints" section on hw1 page
+    let counter: T = float(nums.len());
 
-    let result = if sum == 0.0 { 0 } else { 1 }; //conditional assignment for match since it cant match on floats
+    let result = if sum == T::zero() { 0 } else { 1 }; //conditional assignment for match since it cant match on floats
 
     match result {
-        0 => Some(0.0),
+        0 => Some(T::zero()),
         _ => Some(sum / counter),
     }
 }
 
+/// Count, mean and sum of squared deviations from the mean
+/// of a sequence, in a single pass by Welford's algorithm.
+/// Updating the mean incrementally avoids the catastrophic
+/// cancellation of the textbook `Σx² - (Σx)²/n` formula on
+/// data far from zero, and the squared deviations are added
+/// with compensated summation.
+fn welford<T: Float, I: IntoIterator<Item = T>>(nums: I) -> (usize, T, T) {
+    //https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm
+    let (mut count, mut mean, mut m2) = (0, T::zero(), Neumaier::new());
+    for x in nums {
+        count += 1;
+        let delta = x - mean;
+        mean = mean + delta / float(count);
+        m2.add(delta * (x - mean));
+    }
+    (count, mean, m2.total())
+}
+
+/// Variance of input values with `ddof` "delta degrees of
+/// freedom": the sum of squared deviations from the mean is
+/// divided by `n - ddof`. A `ddof` of 0 gives the
+/// population variance; a `ddof` of 1 gives the
+/// Bessel-corrected sample variance. The variance is
+/// undefined when there are no more than `ddof` values.
+///
+/// The variance is computed in a single pass with Welford's
+/// algorithm, which stays accurate for values with a large
+/// common offset. Long inputs instead use the corrected
+/// two-pass algorithm with pairwise summation, which is as
+/// accurate and faster.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, variance_ddof(&[1.0], 1));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), variance_ddof(&[1.0, 3.0], 1));
+/// ```
+/// ```
+/// # use stats::*;
+/// // The sum-of-squares formula loses every digit here.
+/// let nums = [1e9 + 4.0, 1e9 + 7.0, 1e9 + 13.0, 1e9 + 16.0];
+/// assert_eq!(Some(30.0), variance_ddof(&nums, 1));
+/// let nums: Vec<f64> = nums.iter().map(|x| x + 1e8).collect();
+/// assert_eq!(Some(30.0), variance_ddof(&nums, 1));
+/// ```
+/// ```
+/// # use stats::*;
+/// let nums: Vec<f64> = (0..100_000).map(|i| 1e9 + [4.0, 7.0, 13.0, 16.0][i % 4]).collect();
+/// assert_eq!(Some(22.5), variance(&nums));
+/// ```
+pub fn variance_ddof<T: Float>(nums: &[T], ddof: usize) -> Option<T> {
+    let count = nums.len();
+    if count <= ddof {
+        return None;
+    }
+    let m2 = if count <= PAIRWISE_BLOCK {
+        welford(nums.iter().copied()).2
+    } else {
+        //corrected two-pass algorithm: https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Two-pass_algorithm
+        let meanvalue = sum(nums) / float(count);
+        let deviation = pairwise(nums, |x| x - meanvalue).total();
+        let squares = pairwise(nums, |x| (x - meanvalue).powi(2)).total();
+        squares - deviation * deviation / float(count)
+    };
+    Some(m2 / float(count - ddof))
+}
+
+/// Population variance of input values. The variance of an
+/// empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, variance::<f64>(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), variance(&[1.0, 3.0]));
+/// ```
+pub fn variance<T: Float>(nums: &[T]) -> Option<T> {
+    variance_ddof(nums, 0)
+}
+
+/// Sample variance of input values, using Bessel's
+/// correction. The sample variance of a list with fewer
+/// than two values is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_variance(&[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), sample_variance(&[1.0, 3.0]));
+/// ```
+pub fn sample_variance<T: Float>(nums: &[T]) -> Option<T> {
+    variance_ddof(nums, 1)
+}
+
+/// Standard deviation of input values with `ddof` "delta
+/// degrees of freedom": the square root of
+/// [`variance_ddof`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), stddev_ddof(&[1.0, 5.0], 0));
+/// ```
+pub fn stddev_ddof<T: Float>(nums: &[T], ddof: usize) -> Option<T> {
+    variance_ddof(nums, ddof).map(T::sqrt)
+}
+
 /// Population standard deviation of input values. The
 /// standard deviation of an empty list is undefined.
 ///
@@ -45,71 +415,623 @@ pub fn mean(nums: &[f64]) -> Option<f64> {
 ///
 /// ```
 /// # use stats::*;
-/// assert_eq!(None, stddev(&[]));
+/// assert_eq!(None, stddev::<f64>(&[]));
 /// ```
 /// ```
 /// # use stats::*;
 /// assert_eq!(Some(0.0), stddev(&[1.0, 1.0]));
 /// ```
-pub fn stddev(nums: &[f64]) -> Option<f64> {
-    //algorithm found here: https://www.mathsisfun.com/data/standard-deviation-formulas.html
+pub fn stddev<T: Float>(nums: &[T]) -> Option<T> {
+    stddev_ddof(nums, 0)
+}
+
+/// Sample standard deviation of input values, using
+/// Bessel's correction. The sample standard deviation of a
+/// list with fewer than two values is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_stddev(&[1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), sample_stddev(&[1.0, 3.0, 5.0]));
+/// ```
+pub fn sample_stddev<T: Float>(nums: &[T]) -> Option<T> {
+    stddev_ddof(nums, 1)
+}
 
-    let meanvalue = mean(nums); //determine mean
-    let count = nums.len() as f64; //determine array length
-                                   //saw "as" keyword under "hints" section on hw1 page
+/// Second, third and fourth central moments of the input
+/// values, or `None` for an empty list.
+fn central_moments(nums: &[f64]) -> Option<(f64, f64, f64)> {
+    if nums.is_empty() {
+        return None;
+    }
+    let meanvalue = mean(nums)?;
+    let count = nums.len() as f64;
 
-    let mut sum = 0.0;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
     for j in nums {
-        //Subtract the mean from each value and square result
-        sum += (j - meanvalue.unwrap()).powf(2.0); //sum all of those values together,
+        let d = j - meanvalue;
+        let d2 = d * d;
+        m2 += d2;
+        m3 += d2 * d;
+        m4 += d2 * d2;
+    }
+    Some((m2 / count, m3 / count, m4 / count))
+}
+
+/// Population skewness (the Fisher-Pearson coefficient
+/// g1) of input values. The skewness is undefined for an
+/// empty list or for a list with zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(0.0), skewness(&[1.0, 2.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let g1 = skewness(&[0.0, 0.0, 0.0, 1.0]).unwrap();
+/// assert!((g1 - 2.0 / 3.0f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn skewness(nums: &[f64]) -> Option<f64> {
+    //formulas referenced on: https://en.wikipedia.org/wiki/Skewness#Sample_skewness
+    let (m2, m3, _) = central_moments(nums)?;
+    if m2 == 0.0 {
+        return None;
     }
+    Some(m3 / m2.powf(1.5))
+}
 
-    sum = (sum / count).sqrt(); //calc Variance and then square it for stand. dev.
+/// Bias-corrected sample skewness (the adjusted
+/// Fisher-Pearson coefficient G1) of input values. The
+/// sample skewness is undefined for fewer than three
+/// values or for zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_skewness(&[1.0, 2.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let g1 = sample_skewness(&[0.0, 0.0, 0.0, 1.0]).unwrap();
+/// assert!((g1 - 2.0).abs() < 1e-12);
+/// ```
+pub fn sample_skewness(nums: &[f64]) -> Option<f64> {
+    let n = nums.len() as f64;
+    if n < 3.0 {
+        return None;
+    }
+    let g1 = skewness(nums)?;
+    Some(g1 * (n * (n - 1.0)).sqrt() / (n - 2.0))
+}
 
-    let result = if count == 0.0 { 0 } else { 1 }; //if count is 0.0 we have a empty list
-    match result {
-        //match can't pattern match on floats
-        0 => None,
-        1 => Some(sum),
-        _ => None,
+/// Population kurtosis (Pearson's definition, which is 3.0
+/// for a normal distribution) of input values. The
+/// kurtosis is undefined for an empty list or for a list
+/// with zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, kurtosis(&[2.0, 2.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.0), kurtosis(&[-1.0, 1.0]));
+/// ```
+pub fn kurtosis(nums: &[f64]) -> Option<f64> {
+    //formulas referenced on: https://en.wikipedia.org/wiki/Kurtosis#Sample_kurtosis
+    let (m2, _, m4) = central_moments(nums)?;
+    if m2 == 0.0 {
+        return None;
+    }
+    Some(m4 / (m2 * m2))
+}
+
+/// Population excess kurtosis (Fisher's definition, which
+/// is 0.0 for a normal distribution) of input values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-2.0), excess_kurtosis(&[-1.0, 1.0]));
+/// ```
+pub fn excess_kurtosis(nums: &[f64]) -> Option<f64> {
+    kurtosis(nums).map(|k| k - 3.0)
+}
+
+/// Bias-corrected sample excess kurtosis (G2) of input
+/// values. The sample excess kurtosis is undefined for
+/// fewer than four values or for zero variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, sample_excess_kurtosis(&[-1.0, 0.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(-6.0), sample_excess_kurtosis(&[-1.0, -1.0, 1.0, 1.0]));
+/// ```
+pub fn sample_excess_kurtosis(nums: &[f64]) -> Option<f64> {
+    let n = nums.len() as f64;
+    if n < 4.0 {
+        return None;
+    }
+    let g2 = excess_kurtosis(nums)?;
+    Some(((n + 1.0) * g2 + 6.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0)))
+}
+
+/// Interpolation scheme used by [`quantile_with`] when the
+/// requested quantile falls between two data points. With
+/// `n` sorted values, the quantile `q` sits at fractional
+/// index `h = (n - 1) * q` between `lo = floor(h)` and
+/// `hi = ceil(h)`. These match the NumPy `method`s of the
+/// same names; `Linear` is R's type 7.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuantileMethod {
+    /// Interpolate linearly between the values at `lo` and
+    /// `hi`.
+    #[default]
+    Linear,
+    /// Take the value at `lo`.
+    Lower,
+    /// Take the value at `hi`.
+    Higher,
+    /// Take the value at the index nearest `h`, rounding
+    /// halves to even.
+    Nearest,
+    /// Take the average of the values at `lo` and `hi`.
+    Midpoint,
+}
+
+/// Total order on floats that puts NaNs last.
+fn nan_last<T: Float>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Make a sorted copy of the input floats. NaNs are sorted
+/// to the end of the list rather than panicking the sort.
+fn sorted<T: Float>(nums: &[T]) -> Vec<T> {
+    let mut nums = nums.to_vec();
+    nums.sort_by(nan_last);
+    nums
+}
+
+/// True if any input value is NaN.
+fn has_nan<T: Float>(nums: &[T]) -> bool {
+    nums.iter().any(|x| x.is_nan())
+}
+
+/// True if no input value is NaN or infinite.
+fn all_finite<T: Float>(nums: &[T]) -> bool {
+    nums.iter().all(|x| x.is_finite())
+}
+
+/// Position of quantile `q` among `n` sorted values under
+/// `method`: the index `lo` of the first value needed, and the
+/// fraction `t` of the way to the next value. A `t` of 0.0 means
+/// the value at `lo` is the quantile.
+fn quantile_position(n: usize, q: f64, method: QuantileMethod) -> (usize, f64) {
+    //interpolation schemes referenced on: https://numpy.org/doc/stable/reference/generated/numpy.quantile.html
+    let h = (n - 1) as f64 * q;
+    let lo = h.floor() as usize;
+    let t = h - lo as f64;
+    if t == 0.0 {
+        return (lo, 0.0);
+    }
+    match method {
+        QuantileMethod::Linear => (lo, t),
+        QuantileMethod::Lower => (lo, 0.0),
+        QuantileMethod::Higher => (lo + 1, 0.0),
+        QuantileMethod::Nearest if t == 0.5 => (lo + lo % 2, 0.0),
+        QuantileMethod::Nearest => (h.round() as usize, 0.0),
+        QuantileMethod::Midpoint => (lo, 0.5),
+    }
+}
+
+/// Combine the value at a [`quantile_position`] with the
+/// next value, which is only computed if needed.
+fn interpolate<T: Float, F: FnOnce() -> T>(method: QuantileMethod, x: T, next: F, t: f64) -> T {
+    if t == 0.0 {
+        return x;
+    }
+    let y = next();
+    match method {
+        QuantileMethod::Midpoint => (x + y) / float(2),
+        _ => x + float::<T, _>(t) * (y - x),
+    }
+}
+
+/// Quantile `q` of input values that have already been
+/// sorted in ascending order, using the given interpolation
+/// `method`. The quantile is undefined for an empty list, for
+/// a list containing NaN, or for `q` outside `[0, 1]`. NaNs
+/// are looked for only at the ends of the list: this crate
+/// sorts them after all other values, and sorting with
+/// `total_cmp` puts negative NaNs first and positive ones
+/// last.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let sorted = [1.0, 2.0, 3.0, 4.0];
+/// assert_eq!(Some(2.5), quantile_sorted(&sorted, 0.5, QuantileMethod::Linear));
+/// ```
+pub fn quantile_sorted<T: Float>(sorted: &[T], q: f64, method: QuantileMethod) -> Option<T> {
+    let (first, last) = (*sorted.first()?, *sorted.last()?);
+    if first.is_nan() || last.is_nan() || !(0.0..=1.0).contains(&q) {
+        return None;
     }
+    let (lo, t) = quantile_position(sorted.len(), q, method);
+    Some(interpolate(method, sorted[lo], || sorted[lo + 1], t))
 }
 
-/// Median value of input values, taking the value closer
-/// to the beginning to break ties. The median
-/// of an empty list is undefined.
+/// Quantile `q` of input values using the given
+/// interpolation `method`, reordering the values in place
+/// rather than copying them. This selects the needed values
+/// in expected O(n) time without sorting. The quantile is
+/// undefined for an empty list, for a list containing NaN, or
+/// for `q` outside `[0, 1]`.
 ///
 /// # Examples:
 ///
 /// ```
 /// # use stats::*;
-/// assert_eq!(None, median(&[]));
+/// let mut nums = [5.0, 1.0, 4.0, 2.0, 3.0];
+/// assert_eq!(Some(4.6), quantile_mut(&mut nums, 0.9, QuantileMethod::Linear));
+/// ```
+pub fn quantile_mut<T: Float>(nums: &mut [T], q: f64, method: QuantileMethod) -> Option<T> {
+    if nums.is_empty() || has_nan(nums) || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+    let (lo, t) = quantile_position(nums.len(), q, method);
+    let (_, &mut x, above) = nums.select_nth_unstable_by(lo, nan_last);
+    // The next order statistic is the smallest value above.
+    let next = || above.iter().copied().fold(T::infinity(), T::min);
+    Some(interpolate(method, x, next, t))
+}
+
+/// Quantile `q` of input values using the given
+/// interpolation `method`. The quantile is undefined for an
+/// empty list, for a list containing NaN, or for `q` outside
+/// `[0, 1]`. This works on a copy of the input with
+/// [`quantile_mut`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [4.0, 1.0, 3.0, 2.0];
+/// assert_eq!(Some(2.0), quantile_with(&nums, 0.5, QuantileMethod::Lower));
+/// assert_eq!(Some(3.0), quantile_with(&nums, 0.5, QuantileMethod::Higher));
+/// assert_eq!(Some(2.5), quantile_with(&nums, 0.5, QuantileMethod::Midpoint));
+/// assert_eq!(Some(3.0), quantile_with(&nums, 0.5, QuantileMethod::Nearest));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, quantile_with(&[1.0], 1.5, QuantileMethod::Nearest));
+/// ```
+pub fn quantile_with<T: Float>(nums: &[T], q: f64, method: QuantileMethod) -> Option<T> {
+    quantile_mut(&mut nums.to_vec(), q, method)
+}
+
+/// Quantile `q` of input values, interpolating linearly
+/// between data points. The quantile is undefined for an
+/// empty list, for a list containing NaN, or for `q` outside
+/// `[0, 1]`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, quantile::<f64>(&[], 0.5));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(9.1), quantile(&[0.0, 10.0], 0.91));
+/// ```
+pub fn quantile<T: Float>(nums: &[T], q: f64) -> Option<T> {
+    quantile_with(nums, q, QuantileMethod::Linear)
+}
+
+/// Median value of input values, averaging the two middle
+/// values of an even-length list. The median of an empty
+/// list, or of a list containing NaN, is undefined. This
+/// copies the input and selects the middle values in
+/// expected O(n) time; [`median_mut`] avoids the copy.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median::<f64>(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, median(&[1.0, f64::NAN, 2.0]));
 /// ```
 /// ```
 /// # use stats::*;
 /// assert_eq!(Some(0.25), median(&[0.0, 0.5, -1.0, 1.0]));
 /// ```
-pub fn median(nums: &[f64]) -> Option<f64> {
-    // Make a sorted copy of the input floats.
-    let mut nums = nums.to_owned();
-    // https://users.rust-lang.org/t/how-to-sort-a-vec-of-floats/2838/2
-    nums.sort_by(|a, b| a.partial_cmp(b).unwrap());
+pub fn median<T: Float>(nums: &[T]) -> Option<T> {
+    quantile(nums, 0.5)
+}
 
-    let mut index = nums.len();
+/// Median value of input values, reordering the values in
+/// place rather than copying them. The median of an empty
+/// list, or of a list containing NaN, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut nums = vec![4.0, 1.0, 3.0, 2.0];
+/// assert_eq!(Some(2.5), median_mut(&mut nums));
+/// ```
+pub fn median_mut<T: Float>(nums: &mut [T]) -> Option<T> {
+    quantile_mut(nums, 0.5, QuantileMethod::Linear)
+}
 
-    if index != 0 {
-        if index % 2 != 0 {
-            //odd length
-            index = (index - 1) / 2; //determine median index
-            Some(nums[index])
-        } else {
-            //even length
-            index /= 2;
-            Some((nums[index] + nums[index - 1]) / 2.0) //determine average of the 2 mid values
+/// Quantile `q` of input values using the given
+/// interpolation `method`, working on a copy of the values
+/// in the caller's `scratch` buffer. Once the buffer has
+/// grown to the length of the input, repeated calls do not
+/// allocate. The previous contents of `scratch` are
+/// discarded. See [`quantile_mut`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut scratch = Vec::with_capacity(4);
+/// for frame in [[4.0, 1.0, 3.0, 2.0], [8.0, 5.0, 7.0, 6.0]] {
+///     let q = quantile_in(&frame, &mut scratch, 0.25, QuantileMethod::Lower);
+///     assert_eq!(Some(frame[1]), q);
+/// }
+/// ```
+pub fn quantile_in<T: Float>(
+    nums: &[T],
+    scratch: &mut Vec<T>,
+    q: f64,
+    method: QuantileMethod,
+) -> Option<T> {
+    scratch.clear();
+    scratch.extend_from_slice(nums);
+    quantile_mut(scratch, q, method)
+}
+
+/// Median value of input values, working on a copy of the
+/// values in the caller's `scratch` buffer as in
+/// [`quantile_in`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut scratch = Vec::new();
+/// assert_eq!(Some(2.5), median_in(&[4.0, 1.0, 3.0, 2.0], &mut scratch));
+/// assert_eq!(None, median_in(&[], &mut scratch));
+/// ```
+pub fn median_in<T: Float>(nums: &[T], scratch: &mut Vec<T>) -> Option<T> {
+    quantile_in(nums, scratch, 0.5, QuantileMethod::Linear)
+}
+
+/// Tukey's five-number summary of a list of values, with
+/// quartiles computed by linear interpolation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FiveNumSummary {
+    /// Smallest value.
+    pub min: f64,
+    /// First quartile.
+    pub q1: f64,
+    /// Median.
+    pub median: f64,
+    /// Third quartile.
+    pub q3: f64,
+    /// Largest value.
+    pub max: f64,
+}
+
+impl FiveNumSummary {
+    /// Interquartile range `q3 - q1`.
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+}
+
+/// Five-number summary of input values. The summary of an
+/// empty list, or of a list containing NaN, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, five_number_summary(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// let s = five_number_summary(&[5.0, 1.0, 4.0, 2.0, 3.0]).unwrap();
+/// assert_eq!((1.0, 2.0, 3.0, 4.0, 5.0), (s.min, s.q1, s.median, s.q3, s.max));
+/// assert_eq!(2.0, s.iqr());
+/// ```
+pub fn five_number_summary(nums: &[f64]) -> Option<FiveNumSummary> {
+    five_number_summary_sorted(&sorted(nums))
+}
+
+/// Five-number summary of input values that have already
+/// been sorted in ascending order.
+fn five_number_summary_sorted(sorted: &[f64]) -> Option<FiveNumSummary> {
+    let quartile = |q| quantile_sorted(sorted, q, QuantileMethod::Linear);
+    Some(FiveNumSummary {
+        min: quartile(0.0)?,
+        q1: quartile(0.25)?,
+        median: quartile(0.5)?,
+        q3: quartile(0.75)?,
+        max: quartile(1.0)?,
+    })
+}
+
+/// Interquartile range of input values. The interquartile
+/// range of an empty list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, iqr(&[]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(1.5), iqr(&[1.0, 2.0, 3.0, 4.0]));
+/// ```
+pub fn iqr(nums: &[f64]) -> Option<f64> {
+    five_number_summary(nums).map(|s| s.iqr())
+}
+
+/// Number of values to cut from each tail of a list when
+/// trimming by `proportion`, or `None` if the list is empty
+/// or contains NaN, or `proportion` is outside `[0, 0.5)`.
+fn tail_count(nums: &[f64], proportion: f64) -> Option<usize> {
+    if nums.is_empty() || has_nan(nums) || !(0.0..0.5).contains(&proportion) {
+        return None;
+    }
+    Some((nums.len() as f64 * proportion).floor() as usize)
+}
+
+/// Trimmed mean of input values: the mean after discarding
+/// the `floor(n * proportion)` smallest and largest values.
+/// The trimmed mean of an empty list or a list containing
+/// NaN, or with `proportion` outside `[0, 0.5)`, is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, trimmed_mean(&[1.0, 2.0], 0.5));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.25), trimmed_mean(&[0.0, 1.0, 2.0, 6.0, 100.0, -50.0], 0.2));
+/// ```
+pub fn trimmed_mean(nums: &[f64], proportion: f64) -> Option<f64> {
+    let k = tail_count(nums, proportion)?;
+    let nums = sorted(nums);
+    mean(&nums[k..nums.len() - k])
+}
+
+/// Winsorized mean of input values: the mean after
+/// replacing the `floor(n * proportion)` smallest and
+/// largest values with the nearest remaining value. The
+/// winsorized mean of an empty list or a list containing NaN,
+/// or with `proportion` outside `[0, 0.5)`, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, winsorized_mean(&[], 0.1));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.5), winsorized_mean(&[0.0, 1.0, 2.0, 6.0, 100.0, -50.0], 0.2));
+/// ```
+pub fn winsorized_mean(nums: &[f64], proportion: f64) -> Option<f64> {
+    let k = tail_count(nums, proportion)?;
+    let mut nums = sorted(nums);
+    let n = nums.len();
+    let (lo, hi) = (nums[k], nums[n - 1 - k]);
+    for x in &mut nums[..k] {
+        *x = lo;
+    }
+    for x in &mut nums[n - k..] {
+        *x = hi;
+    }
+    mean(&nums)
+}
+
+/// Group sorted values into clusters, starting a new
+/// cluster whenever a value is more than `tolerance` above
+/// the first value of the current cluster. Returns the
+/// representative (middle value) and size of each cluster.
+fn clusters(sorted: &[f64], tolerance: f64) -> Vec<(f64, usize)> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    for i in 1..=sorted.len() {
+        if i == sorted.len() || sorted[i] - sorted[start] > tolerance {
+            let cluster = &sorted[start..i];
+            result.push((cluster[cluster.len() / 2], cluster.len()));
+            start = i;
         }
-    } else {
-        None
     }
+    result
+}
+
+/// All modes of input values: values are grouped into
+/// clusters spanning at most `tolerance`, and the middle
+/// value of every most-populous cluster is returned in
+/// ascending order. A `tolerance` of 0.0 finds the values
+/// occurring most often. The modes of an empty list or a
+/// list containing NaN, or with a negative or NaN
+/// `tolerance`, are undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, modes(&[], 0.0));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(vec![1.0, 3.0]), modes(&[3.0, 1.0, 2.0, 3.0, 1.0], 0.0));
+/// ```
+pub fn modes(nums: &[f64], tolerance: f64) -> Option<Vec<f64>> {
+    if nums.is_empty() || has_nan(nums) || tolerance.is_nan() || tolerance < 0.0 {
+        return None;
+    }
+    let clusters = clusters(&sorted(nums), tolerance);
+    let most = clusters.iter().map(|&(_, n)| n).max()?;
+    Some(
+        clusters
+            .into_iter()
+            .filter(|&(_, n)| n == most)
+            .map(|(x, _)| x)
+            .collect(),
+    )
+}
+
+/// Mode of input values: the smallest of the [`modes`]
+/// found with the given `tolerance`. The mode of an empty
+/// list is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, mode(&[], 0.1));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(2.0), mode(&[1.0, 1.99, 2.0, 2.01, 5.0], 0.05));
+/// ```
+pub fn mode(nums: &[f64], tolerance: f64) -> Option<f64> {
+    modes(nums, tolerance)?.first().copied()
 }
 
 /// L2 norm (Euclidean norm) of input values. The L2
@@ -125,19 +1047,37 @@ pub fn median(nums: &[f64]) -> Option<f64> {
 /// # use stats::*;
 /// assert_eq!(Some(5.0), l2(&[-3.0, 4.0]));
 /// ```
-pub fn l2(nums: &[f64]) -> Option<f64> {
+pub fn l2<T: Float>(nums: &[T]) -> Option<T> {
     //L2 norm referenced on: http://mathworld.wolfram.com/L2-Norm.html
 
-    let mut norm = 0.0;
-    for i in nums {
-        norm += i.powf(2.0); //square each value in array and sum all squared values
-    }
+    let mut norm = pairwise(nums, |i| i.powi(2)).total(); //square each value in array and sum all squared values
 
     norm = norm.sqrt(); //square root the sum
 
     let result = nums.len() as i64; //determine length of array to see if its empty or not
     match result {
-        0 => Some(0.0),
+        0 => Some(T::zero()),
         _ => Some(norm),
     }
 }
+
+/// Dot product of two lists of values, summed as in
+/// [`sum`]. The dot product is undefined for lists of
+/// different lengths, and is 0.0 for two empty lists.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert_eq!(None, dot(&[1.0], &[1.0, 2.0]));
+/// ```
+/// ```
+/// # use stats::*;
+/// assert_eq!(Some(11.0), dot(&[1.0, 2.0], &[3.0, 4.0]));
+/// ```
+pub fn dot<T: Float>(xs: &[T], ys: &[T]) -> Option<T> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    Some(pairwise_zip(xs, ys, |x, y| x * y).total())
+}
@@ -3,14 +3,15 @@
 // Please see the file LICENSE in the source
 // distribution of this software for license terms.
 
-use std::process::exit;
+//! Compute a statistic on numbers presented one-per-line on
+//! standard input.
 
-///! Compute a statistic on numbers presented one-per-line on
-///! standard input.
+use std::process::exit;
 
 /// Report proper usage and exit.
 fn usage() -> ! {
-    eprintln!("stats: usage: stats [--mean|--stddev|--median|--l2]");
+    let flags: Vec<String> = stats::names().map(|name| format!("--{}", name)).collect();
+    eprintln!("stats: usage: stats [{}]", flags.join("|"));
     exit(1);
 }
 
@@ -21,18 +22,10 @@ fn main() {
     if args.len() != 2 {
         usage();
     }
-    let target = &args[1];
-    let argdescs: &[(&str, stats::StatFn)] = &[
-        ("--mean", stats::mean),
-        ("--stddev", stats::stddev),
-        ("--median", stats::median),
-        ("--l2", stats::l2),
-    ];
-    let stat = argdescs
-        .iter()
-        .find(|(a, _)| a == target)
-        .unwrap_or_else(|| usage())
-        .1;
+    let stat = args[1]
+        .strip_prefix("--")
+        .and_then(stats::lookup)
+        .unwrap_or_else(|| usage());
 
     // Read the input.
     use std::io::BufRead;
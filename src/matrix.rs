@@ -0,0 +1,254 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! A small dense matrix of `f64`, as returned by the
+//! multivariate statistics.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Index;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Dense matrix of `f64` values stored in row-major order.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::matrix::*;
+/// let m = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+/// assert_eq!((2, 3), (m.rows(), m.cols()));
+/// assert_eq!(6.0, m[(1, 2)]);
+/// assert_eq!(&[4.0, 5.0, 6.0], m.row(1));
+/// assert_eq!(vec![vec![1.0, 4.0], vec![2.0, 5.0], vec![3.0, 6.0]], m.columns());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    /// Matrix of `rows` rows and `cols` columns holding `data`
+    /// in row-major order, or `None` if `data` does not have
+    /// `rows * cols` values. A table of observations with
+    /// one variable per column becomes a list of columns for
+    /// the multivariate statistics with
+    /// [`columns`](Matrix::columns).
+    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Option<Matrix> {
+        if rows.checked_mul(cols)? != data.len() {
+            return None;
+        }
+        Some(Matrix { rows, cols, data })
+    }
+
+    /// Matrix whose element `(i, j)` is `f(i, j)`.
+    pub fn from_fn<F: FnMut(usize, usize) -> f64>(rows: usize, cols: usize, mut f: F) -> Matrix {
+        let mut data = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(f(i, j));
+            }
+        }
+        Matrix { rows, cols, data }
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Row `i`. Panics if `i` is out of range.
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Copy of each column.
+    pub fn columns(&self) -> Vec<Vec<f64>> {
+        (0..self.cols)
+            .map(|j| (0..self.rows).map(|i| self[(i, j)]).collect())
+            .collect()
+    }
+
+    /// The values in row-major order.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    /// The values in row-major order.
+    pub fn into_vec(self) -> Vec<f64> {
+        self.data
+    }
+
+    /// Least-squares solution `x` minimizing `|self · x - b|`
+    /// for a matrix with at least as many rows as columns,
+    /// by Householder QR decomposition, or `None` if `b` has
+    /// the wrong length or the columns are linearly dependent
+    /// to within rounding.
+    pub(crate) fn least_squares(&self, b: &[f64]) -> Option<Vec<f64>> {
+        //Golub and Van Loan, Matrix Computations, §5.3
+        let (m, n) = (self.rows, self.cols);
+        if b.len() != m || m < n {
+            return None;
+        }
+        let mut a = self.data.clone();
+        let mut b = b.to_vec();
+        let column_norm = |a: &[f64], j: usize, from: usize| {
+            (from..m).map(|i| a[i * n + j].powi(2)).sum::<f64>().sqrt()
+        };
+        let norms: Vec<f64> = (0..n).map(|j| column_norm(&a, j, 0)).collect();
+        for k in 0..n {
+            //what is left of column k outside the span of the
+            //earlier columns
+            let norm = column_norm(&a, k, k);
+            if norm <= 1e-12 * norms[k] {
+                return None;
+            }
+            let alpha = if a[k * n + k] > 0.0 { -norm } else { norm };
+            //reflect column k onto alpha·e_k with v = a_k - alpha·e_k
+            let mut v: Vec<f64> = (k..m).map(|i| a[i * n + k]).collect();
+            v[0] -= alpha;
+            let vv: f64 = v.iter().map(|x| x * x).sum();
+            for j in k + 1..n {
+                let dot: f64 = (k..m).map(|i| v[i - k] * a[i * n + j]).sum();
+                let f = 2.0 * dot / vv;
+                for i in k..m {
+                    a[i * n + j] -= f * v[i - k];
+                }
+            }
+            let dot: f64 = (k..m).map(|i| v[i - k] * b[i]).sum();
+            let f = 2.0 * dot / vv;
+            for i in k..m {
+                b[i] -= f * v[i - k];
+            }
+            a[k * n + k] = alpha;
+        }
+        //back-substitute R x = Qᵀb
+        let mut x = vec![0.0; n];
+        for k in (0..n).rev() {
+            let tail: f64 = (k + 1..n).map(|j| a[k * n + j] * x[j]).sum();
+            x[k] = (b[k] - tail) / a[k * n + k];
+        }
+        Some(x)
+    }
+
+    /// Lower-triangular Cholesky factor `L` of a symmetric
+    /// positive-definite matrix, with `L · Lᵀ = self`, or
+    /// `None` if the matrix is not square or not positive
+    /// definite to within rounding.
+    pub(crate) fn cholesky(&self) -> Option<Matrix> {
+        let n = self.rows;
+        if self.cols != n {
+            return None;
+        }
+        let mut l = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..=i {
+                let dot: f64 = (0..j).map(|k| l[i * n + k] * l[j * n + k]).sum();
+                let rest = self[(i, j)] - dot;
+                if i == j {
+                    //the pivot must stay clear of rounding error
+                    if rest.is_nan() || rest <= 1e-12 * self[(i, i)].abs() {
+                        return None;
+                    }
+                    l[i * n + i] = rest.sqrt();
+                } else {
+                    l[i * n + j] = rest / l[j * n + j];
+                }
+            }
+        }
+        Matrix::new(n, n, l)
+    }
+
+    /// Solution `x` of `self · x = b` for a lower-triangular
+    /// matrix with nonzero diagonal, by forward substitution.
+    pub(crate) fn solve_lower(&self, b: &[f64]) -> Vec<f64> {
+        let n = self.rows;
+        let mut x = vec![0.0; n];
+        for i in 0..n {
+            let dot: f64 = (0..i).map(|k| self[(i, k)] * x[k]).sum();
+            x[i] = (b[i] - dot) / self[(i, i)];
+        }
+        x
+    }
+
+    /// Eigenvalues and unit eigenvectors of a symmetric
+    /// matrix by the cyclic Jacobi method, in decreasing
+    /// order of eigenvalue: eigenvector `k` is column `k` of
+    /// the returned matrix. `None` if the matrix is not
+    /// square or contains NaN.
+    pub(crate) fn symmetric_eigen(&self) -> Option<(Vec<f64>, Matrix)> {
+        //Golub and Van Loan, Matrix Computations, §8.5
+        let n = self.rows;
+        if self.cols != n || self.data.iter().any(|x| x.is_nan()) {
+            return None;
+        }
+        let mut a = self.data.clone();
+        let mut v = Matrix::from_fn(n, n, |i, j| if i == j { 1.0 } else { 0.0 }).data;
+        let norm: f64 = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+        for _ in 0..100 {
+            let off: f64 = (0..n)
+                .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+                .map(|(i, j)| a[i * n + j].powi(2))
+                .sum::<f64>()
+                .sqrt();
+            if off <= 1e-15 * norm {
+                break;
+            }
+            for p in 0..n {
+                for q in p + 1..n {
+                    let apq = a[p * n + q];
+                    if apq == 0.0 {
+                        continue;
+                    }
+                    //rotation by θ zeroing a[p][q]: t = tan θ
+                    let theta = (a[q * n + q] - a[p * n + p]) / (2.0 * apq);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                    let c = 1.0 / (t * t + 1.0).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                        a[k * n + p] = c * akp - s * akq;
+                        a[k * n + q] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                        a[p * n + k] = c * apk - s * aqk;
+                        a[q * n + k] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                        v[k * n + p] = c * vkp - s * vkq;
+                        v[k * n + q] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[j * n + j].total_cmp(&a[i * n + i]));
+        let values = order.iter().map(|&k| a[k * n + k]).collect();
+        let vectors = Matrix::from_fn(n, n, |i, j| v[i * n + order[j]]);
+        Some((values, vectors))
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    /// Element in row `i` and column `j`. Panics if either
+    /// is out of range.
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        assert!(j < self.cols, "column {} out of range", j);
+        &self.data[i * self.cols + j]
+    }
+}
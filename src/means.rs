@@ -0,0 +1,111 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Generalized means of a slice of non-negative
+//! floating-point numbers.
+//!
+//! These means are only defined for non-negative values: a
+//! list containing a negative value (or a NaN) has no mean,
+//! and `None` is returned. Zeros are allowed; they drive the
+//! geometric and harmonic means (and any power mean with
+//! negative exponent) to 0.0.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Check that a list is non-empty and contains only
+/// non-negative values.
+fn all_nonnegative(nums: &[f64]) -> bool {
+    !nums.is_empty() && nums.iter().all(|&x| x >= 0.0)
+}
+
+/// Geometric mean of input values. The geometric mean of an
+/// empty list, or of a list containing a negative value, is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::means::*;
+/// assert_eq!(None, geometric_mean(&[-1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::means::*;
+/// let g = geometric_mean(&[1.0, 2.0, 4.0]).unwrap();
+/// assert!((g - 2.0).abs() < 1e-12);
+/// ```
+pub fn geometric_mean(nums: &[f64]) -> Option<f64> {
+    if !all_nonnegative(nums) {
+        return None;
+    }
+    // Sum logarithms rather than multiplying to avoid
+    // overflow; ln(0) is -inf, which exp() takes back to 0.
+    let logsum: f64 = nums.iter().map(|x| x.ln()).sum();
+    Some((logsum / nums.len() as f64).exp())
+}
+
+/// Harmonic mean of input values. The harmonic mean of an
+/// empty list, or of a list containing a negative value, is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::means::*;
+/// assert_eq!(Some(0.0), harmonic_mean(&[0.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::means::*;
+/// assert_eq!(Some(2.0), harmonic_mean(&[1.0, 4.0, 4.0]));
+/// ```
+pub fn harmonic_mean(nums: &[f64]) -> Option<f64> {
+    if !all_nonnegative(nums) {
+        return None;
+    }
+    if nums.contains(&0.0) {
+        return Some(0.0);
+    }
+    let recipsum: f64 = nums.iter().map(|x| x.recip()).sum();
+    Some(nums.len() as f64 / recipsum)
+}
+
+/// Power (Hölder) mean of input values with exponent `p`:
+/// `(mean(x^p))^(1/p)`. This includes the harmonic (`p =
+/// -1`), geometric (`p = 0`), arithmetic (`p = 1`) and
+/// quadratic (`p = 2`) means; `p` of negative or positive
+/// infinity gives the minimum or maximum. The power mean of
+/// an empty list, of a list containing a negative value, or
+/// with NaN `p`, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::means::*;
+/// assert_eq!(Some(5.0), power_mean(&[1.0, 7.0], 2.0));
+/// ```
+/// ```
+/// # use stats::means::*;
+/// assert_eq!(Some(7.0), power_mean(&[1.0, 7.0], f64::INFINITY));
+/// ```
+pub fn power_mean(nums: &[f64], p: f64) -> Option<f64> {
+    //power mean referenced on: https://en.wikipedia.org/wiki/Generalized_mean
+    if !all_nonnegative(nums) || p.is_nan() {
+        return None;
+    }
+    if p == 0.0 {
+        return geometric_mean(nums);
+    }
+    if p == f64::INFINITY {
+        return nums.iter().copied().reduce(f64::max);
+    }
+    if p == f64::NEG_INFINITY {
+        return nums.iter().copied().reduce(f64::min);
+    }
+    if p < 0.0 && nums.contains(&0.0) {
+        return Some(0.0);
+    }
+    let powsum: f64 = nums.iter().map(|x| x.powf(p)).sum();
+    Some((powsum / nums.len() as f64).powf(p.recip()))
+}
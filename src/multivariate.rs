@@ -0,0 +1,227 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Analysis of several variables observed together, given
+//! as columns of equal length: `columns[j][i]` is the value
+//! of variable `j` in observation `i`.
+//!
+//! These are meant for a handful of variables; their cost
+//! grows with the cube of the number of columns.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::correlation::sample_cov_matrix;
+use crate::matrix::Matrix;
+use crate::{has_nan, mean};
+
+/// Principal components of several variables: the
+/// orthogonal directions of greatest variance, from the
+/// eigendecomposition of their sample covariance matrix.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pca {
+    means: Vec<f64>,
+    eigenvalues: Vec<f64>,
+    components: Matrix,
+}
+
+/// Principal component analysis of several variables by the
+/// Jacobi eigenvalue method. The analysis is undefined for
+/// no columns, for columns of different lengths or of fewer
+/// than two values, and for values containing NaN.
+///
+/// Variables on different scales should usually be
+/// standardized first, as otherwise the variables of largest
+/// variance dominate the components.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::multivariate::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let ys = [2.1, 3.9, 6.0, 8.1, 9.9];
+/// let pca = pca(&[xs, ys]).unwrap();
+/// let ratios = pca.explained_variance_ratio();
+/// assert!(ratios[0] > 0.999 && ratios[0] + ratios[1] == 1.0);
+/// // The first component points along y ≈ 2x.
+/// let first = pca.components().row(0);
+/// assert!((first[1] / first[0] - 2.0).abs() < 0.05);
+/// let scores = pca.transform(&[3.0, 6.0]).unwrap();
+/// assert!(scores[0].abs() < 1e-9);
+/// ```
+pub fn pca<C: AsRef<[f64]>>(columns: &[C]) -> Option<Pca> {
+    let covariance = sample_cov_matrix(columns)?;
+    let (eigenvalues, vectors) = covariance.symmetric_eigen()?;
+    let k = eigenvalues.len();
+    //make each component's largest loading positive, so that
+    //the result does not depend on rounding
+    let signs: Vec<f64> = (0..k)
+        .map(|c| {
+            let column = (0..k).map(|i| vectors[(i, c)]);
+            let largest = column.fold(0.0, |m: f64, x| if x.abs() > m.abs() { x } else { m });
+            if largest < 0.0 {
+                -1.0
+            } else {
+                1.0
+            }
+        })
+        .collect();
+    let components = Matrix::from_fn(k, k, |c, i| signs[c] * vectors[(i, c)]);
+    let means = columns
+        .iter()
+        .map(|c| mean(c.as_ref()))
+        .collect::<Option<Vec<f64>>>()?;
+    Some(Pca {
+        means,
+        //rounding can leave the eigenvalues of a singular
+        //matrix slightly negative
+        eigenvalues: eigenvalues.iter().map(|e| e.max(0.0)).collect(),
+        components,
+    })
+}
+
+impl Pca {
+    /// Variances along each principal component, largest
+    /// first.
+    pub fn eigenvalues(&self) -> &[f64] {
+        &self.eigenvalues
+    }
+
+    /// Fraction of the total variance along each principal
+    /// component, largest first. All 0.0 if the variables
+    /// do not vary.
+    pub fn explained_variance_ratio(&self) -> Vec<f64> {
+        let total: f64 = self.eigenvalues.iter().sum();
+        if total <= 0.0 {
+            return vec![0.0; self.eigenvalues.len()];
+        }
+        self.eigenvalues.iter().map(|e| e / total).collect()
+    }
+
+    /// Unit loadings of the principal components: row `k`
+    /// holds the weight of each variable in component `k`.
+    pub fn components(&self) -> &Matrix {
+        &self.components
+    }
+
+    /// Means of the variables, about which the components
+    /// are centered.
+    pub fn means(&self) -> &[f64] {
+        &self.means
+    }
+
+    /// Scores of an observation of the variables: its
+    /// coordinates along each principal component, relative
+    /// to the means. Undefined if `point` has the wrong
+    /// number of values.
+    pub fn transform(&self, point: &[f64]) -> Option<Vec<f64>> {
+        if point.len() != self.means.len() {
+            return None;
+        }
+        let centered: Vec<f64> = point.iter().zip(&self.means).map(|(x, m)| x - m).collect();
+        let scores = (0..self.components.rows())
+            .map(|k| {
+                let loadings = self.components.row(k);
+                loadings.iter().zip(&centered).map(|(l, x)| l * x).sum()
+            })
+            .collect();
+        Some(scores)
+    }
+}
+
+/// Means of the sample and the Cholesky factor of its
+/// sample covariance matrix.
+fn whitening<C: AsRef<[f64]>>(columns: &[C]) -> Option<(Vec<f64>, Matrix)> {
+    if columns.iter().any(|c| has_nan(c.as_ref())) {
+        return None;
+    }
+    let factor = sample_cov_matrix(columns)?.cholesky()?;
+    let means = columns
+        .iter()
+        .map(|c| mean(c.as_ref()))
+        .collect::<Option<Vec<f64>>>()?;
+    Some((means, factor))
+}
+
+/// Mahalanobis distance `√((p - μ)ᵀ S⁻¹ (p - μ))` of `p`
+/// from a sample with means `μ` and covariance factor `L`.
+fn distance(point: &[f64], means: &[f64], factor: &Matrix) -> Option<f64> {
+    if point.len() != means.len() {
+        return None;
+    }
+    let centered: Vec<f64> = point.iter().zip(means).map(|(x, m)| x - m).collect();
+    //with S = L·Lᵀ, the squared distance is |L⁻¹(p - μ)|²
+    let whitened = factor.solve_lower(&centered);
+    Some(whitened.iter().map(|z| z * z).sum::<f64>().sqrt())
+}
+
+/// Mahalanobis distance of a point from a sample of several
+/// variables: its distance from the sample means in units
+/// of the sample's spread in each direction, accounting for
+/// correlation between the variables. It is the multivariate
+/// analogue of the absolute z-score. The distance is
+/// undefined for columns that [`pca`] rejects, for a `point`
+/// with a different number of values than there are
+/// columns, and when the sample covariance matrix is
+/// singular, as when there are no more observations than
+/// variables or one variable is a linear combination of the
+/// others.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::multivariate::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+/// let ys = [2.0, 1.0, 4.0, 3.0, 6.0, 5.0];
+/// assert_eq!(Some(0.0), mahalanobis(&[3.5, 3.5], &[xs, ys]));
+/// // Off the trend is farther than along it.
+/// let along = mahalanobis(&[7.0, 7.0], &[xs, ys]).unwrap();
+/// let across = mahalanobis(&[5.5, 1.5], &[xs, ys]).unwrap();
+/// assert!(across > along);
+/// ```
+/// ```
+/// # use stats::multivariate::*;
+/// // With one variable it is the absolute z-score.
+/// let xs = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+/// let z = (9.0 - 5.0) / stats::sample_stddev(&xs).unwrap();
+/// assert!((mahalanobis(&[9.0], &[xs]).unwrap() - z).abs() < 1e-12);
+/// ```
+pub fn mahalanobis<C: AsRef<[f64]>>(point: &[f64], columns: &[C]) -> Option<f64> {
+    let (means, factor) = whitening(columns)?;
+    distance(point, &means, &factor)
+}
+
+/// Mahalanobis distances of several points from a sample,
+/// factoring its covariance matrix once. See
+/// [`mahalanobis`]. Undefined if any point has the wrong
+/// number of values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::multivariate::*;
+/// # use stats::matrix::Matrix;
+/// let table = Matrix::new(5, 2, vec![1.0, 2.0, 2.0, 1.0, 3.0, 4.0, 4.0, 3.0, 9.0, 0.0]).unwrap();
+/// let columns = table.columns();
+/// let rows: Vec<&[f64]> = (0..table.rows()).map(|i| table.row(i)).collect();
+/// let distances = mahalanobis_batch(&rows, &columns).unwrap();
+/// let farthest = (0..5).max_by(|&i, &j| distances[i].total_cmp(&distances[j]));
+/// assert_eq!(Some(4), farthest);
+/// ```
+pub fn mahalanobis_batch<P, C>(points: &[P], columns: &[C]) -> Option<Vec<f64>>
+where
+    P: AsRef<[f64]>,
+    C: AsRef<[f64]>,
+{
+    let (means, factor) = whitening(columns)?;
+    points
+        .iter()
+        .map(|p| distance(p.as_ref(), &means, &factor))
+        .collect()
+}
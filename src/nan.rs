@@ -0,0 +1,84 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Choice of how statistics treat NaN input values.
+
+use alloc::borrow::Cow;
+
+use crate::StatsError;
+
+/// How a statistic should treat NaNs in its input.
+///
+/// Without a policy, the arithmetic statistics (`mean`,
+/// `stddev` and so on) let NaN propagate into their result
+/// and the order statistics (`median`, `quantile` and so on)
+/// treat a NaN as making the statistic undefined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NanPolicy {
+    /// Any NaN in the input makes the result NaN.
+    #[default]
+    Propagate,
+    /// NaNs are dropped from the input before computing.
+    Ignore,
+    /// Any NaN in the input is reported as
+    /// [`StatsError::ContainsNaN`].
+    Error,
+}
+
+impl NanPolicy {
+    /// Prepare input values under this policy: `Ok(None)`
+    /// means the result must be NaN.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let nums = [1.0, f64::NAN, 3.0];
+    /// let cleaned = NanPolicy::Ignore.clean(&nums).unwrap().unwrap();
+    /// assert_eq!(&[1.0, 3.0], &cleaned[..]);
+    /// ```
+    pub fn clean(self, nums: &[f64]) -> Result<Option<Cow<'_, [f64]>>, StatsError> {
+        if !nums.iter().any(|x| x.is_nan()) {
+            return Ok(Some(Cow::Borrowed(nums)));
+        }
+        match self {
+            NanPolicy::Propagate => Ok(None),
+            NanPolicy::Ignore => Ok(Some(Cow::Owned(
+                nums.iter().copied().filter(|x| !x.is_nan()).collect(),
+            ))),
+            NanPolicy::Error => Err(StatsError::ContainsNaN),
+        }
+    }
+
+    /// Compute statistic `stat` of input values under this
+    /// policy. The outer `Result` reports a NaN rejected by
+    /// [`NanPolicy::Error`]; the inner `Option` is the
+    /// statistic's own result.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let nums = [3.0, f64::NAN, 1.0, 2.0];
+    /// assert!(NanPolicy::Propagate.apply(&nums, median).unwrap().unwrap().is_nan());
+    /// assert_eq!(Ok(Some(2.0)), NanPolicy::Ignore.apply(&nums, median));
+    /// assert_eq!(Err(StatsError::ContainsNaN), NanPolicy::Error.apply(&nums, median));
+    /// ```
+    /// ```
+    /// # use stats::*;
+    /// let nums = [0.0, 10.0, f64::NAN];
+    /// let p90 = NanPolicy::Ignore.apply(&nums, |nums| quantile(nums, 0.9));
+    /// assert_eq!(Ok(Some(9.0)), p90);
+    /// ```
+    pub fn apply<F>(self, nums: &[f64], stat: F) -> Result<Option<f64>, StatsError>
+    where
+        F: FnOnce(&[f64]) -> Option<f64>,
+    {
+        match self.clean(nums)? {
+            Some(nums) => Ok(stat(&nums)),
+            None => Ok(Some(f64::NAN)),
+        }
+    }
+}
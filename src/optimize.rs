@@ -0,0 +1,81 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Derivative-free minimization for fitting models whose
+//! parameters have no closed-form estimates.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Point of the unit box `[0, 1]^k` at which `f` is least,
+/// searched for by the Nelder–Mead simplex method from
+/// `start`, with trial points clamped into the box. Finds a
+/// local minimum, so `start` should be a sensible guess.
+pub(crate) fn minimize_unit_box<F: Fn(&[f64]) -> f64>(f: F, start: &[f64]) -> Vec<f64> {
+    const MAX_ITERATIONS: usize = 2000;
+    let k = start.len();
+    let clamp = |p: Vec<f64>| -> Vec<f64> { p.into_iter().map(|x| x.clamp(0.0, 1.0)).collect() };
+    let vertex = |p: Vec<f64>| {
+        let p = clamp(p);
+        let value = f(&p);
+        (p, value)
+    };
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(k + 1);
+    simplex.push(vertex(start.to_vec()));
+    //the other vertices step towards the middle of the box
+    for i in 0..k {
+        let mut p = clamp(start.to_vec());
+        p[i] += if p[i] <= 0.5 { 0.2 } else { -0.2 };
+        simplex.push(vertex(p));
+    }
+    //combination of two points, a + t (b - a)
+    let along = |a: &[f64], b: &[f64], t: f64| -> Vec<f64> {
+        a.iter().zip(b).map(|(x, y)| x + t * (y - x)).collect()
+    };
+    for _ in 0..MAX_ITERATIONS {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[k].1);
+        let diameter = simplex[1..]
+            .iter()
+            .flat_map(|(p, _)| p.iter().zip(&simplex[0].0).map(|(x, y)| (x - y).abs()))
+            .fold(0.0, f64::max);
+        if diameter <= 1e-10 || worst - best <= 1e-14 * best.abs() {
+            break;
+        }
+        let mut centroid = vec![0.0; k];
+        for (p, _) in &simplex[..k] {
+            for (c, x) in centroid.iter_mut().zip(p) {
+                *c += x / k as f64;
+            }
+        }
+        let reflected = vertex(along(&centroid, &simplex[k].0, -1.0));
+        if reflected.1 < best {
+            let expanded = vertex(along(&centroid, &simplex[k].0, -2.0));
+            simplex[k] = if expanded.1 < reflected.1 {
+                expanded
+            } else {
+                reflected
+            };
+        } else if reflected.1 < simplex[k - 1].1 {
+            simplex[k] = reflected;
+        } else {
+            let contracted = if reflected.1 < worst {
+                vertex(along(&centroid, &reflected.0, 0.5))
+            } else {
+                vertex(along(&centroid, &simplex[k].0, 0.5))
+            };
+            if contracted.1 < reflected.1.min(worst) {
+                simplex[k] = contracted;
+            } else {
+                let best = simplex[0].0.clone();
+                for v in &mut simplex[1..] {
+                    *v = vertex(along(&best, &v.0, 0.5));
+                }
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0).0
+}
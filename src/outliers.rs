@@ -0,0 +1,226 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Detection of outliers, values far from the bulk of the
+//! others, by the usual rules of thumb and by formal tests,
+//! and their removal from series.
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::robust::normalized_mad;
+use crate::special::student_t_quantile;
+use crate::{has_nan, mean, median, quantile, sample_stddev};
+
+/// Rule by which [`detect`] picks out outliers, with its
+/// threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Method {
+    /// Values more than the given number of sample standard
+    /// deviations from the mean, usually 3.0. The outliers
+    /// themselves inflate the standard deviation, so this
+    /// misses outliers in small samples: no value of `n` can
+    /// be more than `(n - 1) / √n` standard deviations out.
+    ZScore(f64),
+    /// Values whose modified z-score, their distance from
+    /// the median in units of the
+    /// [`normalized_mad`](crate::robust::normalized_mad), is
+    /// more than the given threshold, usually 3.5 following
+    /// Iglewicz and Hoaglin. It is robust to the outliers
+    /// themselves.
+    ModifiedZScore(f64),
+    /// Values beyond Tukey's fences, more than the given
+    /// multiple of the interquartile range below the first
+    /// quartile or above the third: usually 1.5, or 3.0 for
+    /// far outliers. It is the rule behind the whiskers of a
+    /// box plot.
+    Iqr(f64),
+}
+
+/// Positions of the outliers among input values by the
+/// given `method`, in increasing order. Undefined for values
+/// containing NaN, for a negative or NaN threshold, for
+/// fewer than two values for [`Method::ZScore`], and for a
+/// median absolute deviation of zero, as when more than half
+/// the values are equal, for [`Method::ModifiedZScore`]. A
+/// mask of the outliers comes from marking the positions.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::outliers::*;
+/// let xs = [10.0, 12.0, 11.0, 13.0, 12.0, 11.0, 40.0, 12.0, 10.0, 2.0];
+/// assert_eq!(Some(vec![]), detect(&xs, Method::ZScore(3.0)));
+/// assert_eq!(Some(vec![6]), detect(&xs, Method::ZScore(2.0)));
+/// assert_eq!(Some(vec![6, 9]), detect(&xs, Method::ModifiedZScore(3.5)));
+/// let outliers = detect(&xs, Method::Iqr(1.5)).unwrap();
+/// assert_eq!(vec![6, 9], outliers);
+/// let mut mask = vec![false; xs.len()];
+/// outliers.iter().for_each(|&i| mask[i] = true);
+/// assert!(mask[6] && mask[9] && !mask[0]);
+/// ```
+pub fn detect(nums: &[f64], method: Method) -> Option<Vec<usize>> {
+    let threshold = match method {
+        Method::ZScore(t) | Method::ModifiedZScore(t) | Method::Iqr(t) => t,
+    };
+    if has_nan(nums) || threshold.is_nan() || threshold < 0.0 {
+        return None;
+    }
+    let (low, high) = match method {
+        Method::ZScore(t) => {
+            let (center, scale) = (mean(nums)?, sample_stddev(nums)?);
+            (center - t * scale, center + t * scale)
+        }
+        Method::ModifiedZScore(t) => {
+            let (center, scale) = (median(nums)?, normalized_mad(nums)?);
+            if scale <= 0.0 {
+                return None;
+            }
+            (center - t * scale, center + t * scale)
+        }
+        Method::Iqr(k) => {
+            let (q1, q3) = (quantile(nums, 0.25)?, quantile(nums, 0.75)?);
+            (q1 - k * (q3 - q1), q3 + k * (q3 - q1))
+        }
+    };
+    let outliers = nums
+        .iter()
+        .enumerate()
+        .filter(|(_, &x)| x < low || x > high)
+        .map(|(i, _)| i)
+        .collect();
+    Some(outliers)
+}
+
+/// Grubbs' test for a single outlier among normally
+/// distributed values at significance level `alpha`: the
+/// position of the value farthest from the mean, if it is
+/// more sample standard deviations out than the values
+/// would plausibly be, and otherwise no position. It is
+/// [`gesd`] for at most one outlier, and undefined
+/// likewise.
+///
+/// A second outlier can mask the first by inflating the
+/// standard deviation, as in the example, where `gesd` finds
+/// three.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::outliers::*;
+/// // The NIST/SEMATECH e-Handbook's example of Rosner's data.
+/// let xs = [
+///     -0.25, 0.68, 0.94, 1.15, 1.20, 1.26, 1.26, 1.34, 1.38, 1.43, 1.49, 1.49, 1.55, 1.56,
+///     1.58, 1.65, 1.69, 1.70, 1.76, 1.77, 1.81, 1.91, 1.94, 1.96, 1.99, 2.06, 2.09, 2.10,
+///     2.14, 2.15, 2.23, 2.24, 2.26, 2.35, 2.37, 2.40, 2.47, 2.54, 2.62, 2.64, 2.90, 2.92,
+///     2.92, 2.93, 3.21, 3.26, 3.30, 3.59, 3.68, 4.30, 4.64, 5.34, 5.42, 6.01,
+/// ];
+/// assert_eq!(Some(vec![]), grubbs(&xs, 0.05));
+/// assert_eq!(Some(vec![53]), grubbs(&xs, 0.1));
+/// assert_eq!(Some(vec![51, 52, 53]), gesd(&xs, 10, 0.05));
+/// ```
+pub fn grubbs(nums: &[f64], alpha: f64) -> Option<Vec<usize>> {
+    gesd(nums, 1, alpha)
+}
+
+/// Rosner's generalized extreme studentized deviate (ESD)
+/// test for up to `max_outliers` outliers among normally
+/// distributed values at significance level `alpha`. It
+/// removes in turn the value farthest from the mean of those
+/// left, `max_outliers` times, and finds the most removals
+/// whose last removed value was more sample standard
+/// deviations out than is plausible; the values removed up
+/// to then are the outliers, whose positions are returned in
+/// increasing order. Unlike repeated [`grubbs`] tests, an
+/// outlier masked by more extreme ones is still found.
+/// Undefined for values containing NaN, for an `alpha`
+/// outside `0.0..1.0`, and unless `max_outliers` is at most
+/// the number of values less two.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::outliers::*;
+/// let xs = [10.0, 12.0, 11.0, 13.0, 12.0, 11.0, 40.0, 12.0, 10.0, 2.0];
+/// assert_eq!(Some(vec![6, 9]), gesd(&xs, 3, 0.05));
+/// assert_eq!(None, gesd(&xs, 9, 0.05));
+/// ```
+pub fn gesd(nums: &[f64], max_outliers: usize, alpha: f64) -> Option<Vec<usize>> {
+    let n = nums.len();
+    if has_nan(nums) || !(alpha > 0.0 && alpha < 1.0) || max_outliers + 2 > n {
+        return None;
+    }
+    let mut left: Vec<usize> = (0..n).collect();
+    let mut removed = Vec::with_capacity(max_outliers);
+    let mut outliers = 0;
+    for i in 1..=max_outliers {
+        let values: Vec<f64> = left.iter().map(|&j| nums[j]).collect();
+        let (center, scale) = (mean(&values)?, sample_stddev(&values)?);
+        if scale <= 0.0 {
+            break;
+        }
+        let (farthest, deviation) = values
+            .iter()
+            .map(|x| (x - center).abs())
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        removed.push(left.remove(farthest));
+        //critical value for the ith largest studentized deviate
+        let df = (n - i - 1) as f64;
+        let t = student_t_quantile(1.0 - alpha / (2.0 * (n - i + 1) as f64), df);
+        let critical = (n - i) as f64 * t / ((df + t * t) * (n - i + 1) as f64).sqrt();
+        if deviation / scale > critical {
+            outliers = i;
+        }
+    }
+    removed.truncate(outliers);
+    removed.sort_unstable();
+    Some(removed)
+}
+
+/// Hampel filter, which removes spikes from a series: each
+/// value more than `n_sigmas` scaled
+/// [`normalized_mad`](crate::robust::normalized_mad)s from
+/// the median of its window, the values up to `window`
+/// positions either side of it, is replaced by that median.
+/// Windows are cut short at the ends of the series, and
+/// are always of the original values. Returns the cleaned
+/// series with the positions replaced, in increasing order.
+/// Where more than half a window is equal, any other value
+/// in it is replaced. Undefined for values containing NaN,
+/// for a `window` of zero, and for a negative or NaN
+/// `n_sigmas`; 3.0 is usual.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::outliers::*;
+/// let xs = [1.0, 1.2, 0.9, 9.0, 1.1, 1.0, 0.8, -7.0, 1.1, 1.0];
+/// let (cleaned, replaced) = hampel_filter(&xs, 2, 3.0).unwrap();
+/// assert_eq!(vec![3, 7], replaced);
+/// assert_eq!(1.1, cleaned[3]);
+/// assert_eq!(1.0, cleaned[7]);
+/// assert_eq!(xs[4], cleaned[4]);
+/// ```
+pub fn hampel_filter(nums: &[f64], window: usize, n_sigmas: f64) -> Option<(Vec<f64>, Vec<usize>)> {
+    if has_nan(nums) || window == 0 || n_sigmas.is_nan() || n_sigmas < 0.0 {
+        return None;
+    }
+    let mut cleaned = nums.to_vec();
+    let mut replaced = Vec::new();
+    for (i, &x) in nums.iter().enumerate() {
+        let neighbors = &nums[i.saturating_sub(window)..nums.len().min(i + window + 1)];
+        let (center, scale) = (median(neighbors)?, normalized_mad(neighbors)?);
+        if (x - center).abs() > n_sigmas * scale {
+            cleaned[i] = center;
+            replaced.push(i);
+        }
+    }
+    Some((cleaned, replaced))
+}
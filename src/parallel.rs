@@ -0,0 +1,249 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Parallel versions of the core statistics, enabled by the
+//! `parallel` feature.
+//!
+//! Each function splits its input into one chunk per thread
+//! of the [rayon] thread pool, computes partial results in
+//! parallel and combines them. Inputs too short to be worth
+//! the threads are handed to the sequential function, so
+//! results for them are identical; for longer inputs they
+//! agree up to rounding.
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::{
+    float, interpolate, nan_last, pairwise, quantile_position, Neumaier, PartialStats,
+    QuantileMethod,
+};
+
+/// Inputs are not split into chunks shorter than this.
+const MIN_CHUNK: usize = 1 << 16;
+
+/// Number of threads to use for an input of length `len`.
+fn threads(len: usize) -> usize {
+    rayon::current_num_threads().min(len / MIN_CHUNK).max(1)
+}
+
+/// Compensated sum of `f(x)` over input values, with each
+/// chunk summed pairwise in parallel.
+fn par_pairwise<T, F>(nums: &[T], f: F) -> T
+where
+    T: Float + Send + Sync,
+    F: Fn(T) -> T + Copy + Send + Sync,
+{
+    let chunk = nums.len().div_ceil(threads(nums.len())).max(1);
+    nums.par_chunks(chunk)
+        .map(|c| pairwise(c, f))
+        .reduce(Neumaier::new, |mut a, b| {
+            a.merge(b);
+            a
+        })
+        .total()
+}
+
+/// Parallel [`sum`](crate::sum).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// let nums = vec![0.5; 1_000_000];
+/// assert_eq!(500_000.0, par_sum(&nums));
+/// ```
+pub fn par_sum<T: Float + Send + Sync>(nums: &[T]) -> T {
+    if threads(nums.len()) == 1 {
+        return crate::sum(nums);
+    }
+    par_pairwise(nums, |x| x)
+}
+
+/// Parallel [`mean`](crate::mean).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// assert_eq!(Some(0.0), par_mean::<f64>(&[]));
+/// ```
+/// ```
+/// # use stats::parallel::*;
+/// let nums: Vec<f64> = (0..1_000_000).map(f64::from).collect();
+/// assert_eq!(Some(499_999.5), par_mean(&nums));
+/// ```
+pub fn par_mean<T: Float + Send + Sync>(nums: &[T]) -> Option<T> {
+    if nums.is_empty() {
+        return Some(T::zero());
+    }
+    Some(par_sum(nums) / float(nums.len()))
+}
+
+/// Parallel [`variance_ddof`](crate::variance_ddof), using
+/// the corrected two-pass algorithm.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// let nums: Vec<f64> = (0..1_000_000).map(|i| 1e9 + [4.0, 7.0, 13.0, 16.0][i % 4]).collect();
+/// assert_eq!(Some(22.5), par_variance_ddof(&nums, 0));
+/// ```
+pub fn par_variance_ddof<T: Float + Send + Sync>(nums: &[T], ddof: usize) -> Option<T> {
+    let count = nums.len();
+    if threads(count) == 1 {
+        return crate::variance_ddof(nums, ddof);
+    }
+    if count <= ddof {
+        return None;
+    }
+    //corrected two-pass algorithm: https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Two-pass_algorithm
+    let meanvalue = par_sum(nums) / float(count);
+    let deviation = par_pairwise(nums, move |x| x - meanvalue);
+    let squares = par_pairwise(nums, move |x| (x - meanvalue).powi(2));
+    let m2 = squares - deviation * deviation / float(count);
+    Some(m2 / float(count - ddof))
+}
+
+/// Parallel [`variance`](crate::variance).
+pub fn par_variance<T: Float + Send + Sync>(nums: &[T]) -> Option<T> {
+    par_variance_ddof(nums, 0)
+}
+
+/// Parallel [`sample_variance`](crate::sample_variance).
+pub fn par_sample_variance<T: Float + Send + Sync>(nums: &[T]) -> Option<T> {
+    par_variance_ddof(nums, 1)
+}
+
+/// Parallel [`stddev`](crate::stddev).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// let nums: Vec<f64> = (0..1_000_000).map(|i| [1.0, 3.0][i % 2]).collect();
+/// assert_eq!(Some(1.0), par_stddev(&nums));
+/// ```
+pub fn par_stddev<T: Float + Send + Sync>(nums: &[T]) -> Option<T> {
+    par_variance(nums).map(T::sqrt)
+}
+
+/// Parallel [`sample_stddev`](crate::sample_stddev).
+pub fn par_sample_stddev<T: Float + Send + Sync>(nums: &[T]) -> Option<T> {
+    par_sample_variance(nums).map(T::sqrt)
+}
+
+/// Number of values sampled to bracket a quantile.
+const SAMPLE: usize = 4096;
+
+/// Parallel [`quantile_with`](crate::quantile_with). A
+/// sample of the values brackets the needed order
+/// statistics; the threads then count the values below the
+/// bracket and collect those inside it, and only those are
+/// searched. If the bracket misses, which is rare, this
+/// falls back to sequential selection.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// # use stats::QuantileMethod;
+/// let nums: Vec<f64> = (0..1_000_001).rev().map(f64::from).collect();
+/// assert_eq!(Some(250_000.0), par_quantile_with(&nums, 0.25, QuantileMethod::Linear));
+/// ```
+pub fn par_quantile_with<T: Float + Send + Sync>(
+    nums: &[T],
+    q: f64,
+    method: QuantileMethod,
+) -> Option<T> {
+    let n = nums.len();
+    if threads(n) == 1 || !(0.0..=1.0).contains(&q) {
+        return crate::quantile_with(nums, q, method);
+    }
+    let (lo, t) = quantile_position(n, q, method);
+    let hi = if t == 0.0 { lo } else { lo + 1 };
+
+    // Bracket ranks lo..=hi with sample values, widened by a
+    // few standard deviations of the sample rank.
+    let mut sample: Vec<T> = nums.iter().step_by(n / SAMPLE).copied().collect();
+    sample.sort_unstable_by(nan_last);
+    let m = sample.len();
+    let margin = 4 * (m as f64).sqrt() as usize;
+    let (rlo, rhi) = (lo * m / n, hi * m / n);
+    let low = rlo
+        .checked_sub(margin)
+        .map_or(T::neg_infinity(), |r| sample[r]);
+    let high = sample.get(rhi + margin).copied().unwrap_or(T::infinity());
+
+    let chunk = n.div_ceil(threads(n));
+    let parts: Vec<(usize, bool, Vec<T>)> = nums
+        .par_chunks(chunk)
+        .map(|c| {
+            let (mut below, mut nan, mut inside) = (0, false, Vec::new());
+            for &x in c {
+                if x < low {
+                    below += 1;
+                } else if x <= high {
+                    inside.push(x);
+                } else {
+                    nan |= x.is_nan();
+                }
+            }
+            (below, nan, inside)
+        })
+        .collect();
+    if parts.iter().any(|p| p.1) {
+        return None;
+    }
+    let below: usize = parts.iter().map(|p| p.0).sum();
+    let mut inside: Vec<T> = parts.into_iter().flat_map(|p| p.2).collect();
+    if lo < below || hi >= below + inside.len() {
+        return crate::quantile_with(nums, q, method);
+    }
+    let (_, &mut x, above) = inside.select_nth_unstable_by(lo - below, nan_last);
+    let next = || above.iter().copied().fold(T::infinity(), T::min);
+    Some(interpolate(method, x, next, t))
+}
+
+/// Parallel [`quantile`](crate::quantile).
+pub fn par_quantile<T: Float + Send + Sync>(nums: &[T], q: f64) -> Option<T> {
+    par_quantile_with(nums, q, QuantileMethod::default())
+}
+
+/// Parallel [`median`](crate::median).
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// let nums: Vec<f64> = (0..1_000_000).rev().map(f64::from).collect();
+/// assert_eq!(Some(499_999.5), par_median(&nums));
+/// ```
+pub fn par_median<T: Float + Send + Sync>(nums: &[T]) -> Option<T> {
+    par_quantile(nums, 0.5)
+}
+
+/// [`PartialStats`] of input values, with one partial
+/// aggregate per chunk, merged in parallel.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::parallel::*;
+/// let nums: Vec<f64> = (1..=1_000_000).map(f64::from).collect();
+/// let stats = par_partial_stats(&nums);
+/// assert_eq!(Some(500_000.5), stats.mean());
+/// assert_eq!(Some(1_000_000.0), stats.max());
+/// ```
+pub fn par_partial_stats(nums: &[f64]) -> PartialStats {
+    let chunk = nums.len().div_ceil(threads(nums.len())).max(1);
+    nums.par_chunks(chunk)
+        .map(PartialStats::from)
+        .reduce(PartialStats::new, |mut a, b| {
+            a.merge(&b);
+            a
+        })
+}
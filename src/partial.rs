@@ -0,0 +1,214 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Mergeable partial aggregates, for computing statistics of
+//! data split across chunks, threads or machines.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::Neumaier;
+
+/// Partial aggregate of a sequence of values: the count, the
+/// compensated sum, the mean and compensated sum of squared
+/// deviations from it (as in Welford's algorithm), and the
+/// extremes. The mean is kept relative to the first value,
+/// so that data far from zero loses no precision.
+///
+/// A `PartialStats` can be built for each chunk of some data
+/// and the chunks combined with [`merge`](PartialStats::merge)
+/// in any order and grouping; the result agrees, up to
+/// rounding, with the statistics of all the data at once.
+/// Accessors follow the conventions of the slice function of
+/// the same name in the crate root.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let mut left: PartialStats = [1.0, 2.0, 3.0].iter().copied().collect();
+/// let right = PartialStats::from(&[4.0, 5.0][..]);
+/// left.merge(&right);
+/// assert_eq!(5, left.count());
+/// assert_eq!(Some(3.0), left.mean());
+/// assert_eq!(Some(2.5), left.sample_variance());
+/// assert_eq!(Some(5.0), left.max());
+/// assert_eq!(Some(1.0), left.min());
+/// ```
+/// ```
+/// # use stats::*;
+/// let nums: Vec<f64> = (0..1000).map(|i| 1e9 + f64::from(i % 7)).collect();
+/// let mut merged = PartialStats::new();
+/// for shard in nums.chunks(37) {
+///     merged.merge(&PartialStats::from(shard));
+/// }
+/// let whole = variance(&nums).unwrap();
+/// assert!((merged.variance().unwrap() - whole).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialStats {
+    count: usize,
+    sum: Neumaier<f64>,
+    shift: f64,
+    mean: f64,
+    m2: Neumaier<f64>,
+    min: f64,
+    max: f64,
+    nan: bool,
+}
+
+impl Default for PartialStats {
+    fn default() -> Self {
+        PartialStats::new()
+    }
+}
+
+impl PartialStats {
+    /// The aggregate of no values.
+    pub fn new() -> PartialStats {
+        PartialStats {
+            count: 0,
+            sum: Neumaier::new(),
+            shift: 0.0,
+            mean: 0.0,
+            m2: Neumaier::new(),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            nan: false,
+        }
+    }
+
+    /// Add one value.
+    pub fn push(&mut self, x: f64) {
+        //https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm
+        if self.count == 0 {
+            self.shift = x;
+        }
+        self.count += 1;
+        self.sum.add(x);
+        let shifted = x - self.shift;
+        let delta = shifted - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2.add(delta * (shifted - self.mean));
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        self.nan |= x.is_nan();
+    }
+
+    /// Combine with the aggregate of another set of values.
+    pub fn merge(&mut self, other: &PartialStats) {
+        //parallel algorithm referenced on: https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Parallel_algorithm
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = (other.shift - self.shift) + (other.mean - self.mean);
+        let (na, nb) = (self.count as f64, other.count as f64);
+        self.mean += delta * nb / count as f64;
+        self.m2.merge(other.m2);
+        self.m2.add(delta * delta * na * nb / count as f64);
+        self.count = count;
+        self.sum.merge(other.sum);
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.nan |= other.nan;
+    }
+
+    /// Number of values.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Compensated sum of the values. See [`sum`](crate::sum).
+    pub fn sum(&self) -> f64 {
+        self.sum.total()
+    }
+
+    /// Arithmetic mean; 0.0 for no values. See
+    /// [`mean`](crate::mean).
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            return Some(0.0);
+        }
+        Some(self.sum() / self.count as f64)
+    }
+
+    /// Variance with `ddof` delta degrees of freedom. See
+    /// [`variance_ddof`](crate::variance_ddof).
+    pub fn variance_ddof(&self, ddof: usize) -> Option<f64> {
+        if self.count <= ddof {
+            return None;
+        }
+        Some(self.m2.total() / (self.count - ddof) as f64)
+    }
+
+    /// Population variance. See [`variance`](crate::variance).
+    pub fn variance(&self) -> Option<f64> {
+        self.variance_ddof(0)
+    }
+
+    /// Sample variance. See
+    /// [`sample_variance`](crate::sample_variance).
+    pub fn sample_variance(&self) -> Option<f64> {
+        self.variance_ddof(1)
+    }
+
+    /// Population standard deviation. See
+    /// [`stddev`](crate::stddev).
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Sample standard deviation. See
+    /// [`sample_stddev`](crate::sample_stddev).
+    pub fn sample_stddev(&self) -> Option<f64> {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    /// Smallest value; undefined for no values or if any
+    /// value was NaN. See [`min`](crate::extrema::min).
+    pub fn min(&self) -> Option<f64> {
+        if self.count == 0 || self.nan {
+            return None;
+        }
+        Some(self.min)
+    }
+
+    /// Largest value; undefined for no values or if any value
+    /// was NaN. See [`max`](crate::extrema::max).
+    pub fn max(&self) -> Option<f64> {
+        if self.count == 0 || self.nan {
+            return None;
+        }
+        Some(self.max)
+    }
+}
+
+impl From<&[f64]> for PartialStats {
+    fn from(nums: &[f64]) -> Self {
+        nums.iter().copied().collect()
+    }
+}
+
+impl Extend<f64> for PartialStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl core::iter::FromIterator<f64> for PartialStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = PartialStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
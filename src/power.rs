@@ -0,0 +1,157 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistical power and sample sizes, for planning a
+//! two-sided test before collecting the data.
+//!
+//! The power of a test is the probability that it rejects
+//! the null hypothesis at significance level `alpha` when
+//! the effect is as given. The sample size functions find
+//! the smallest number of values per group giving at least
+//! the requested power. Everything is undefined for an
+//! `alpha` or power outside `0.0..1.0` and for NaN.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::special::{noncentral_t_cdf, normal_cdf, normal_quantile, student_t_quantile};
+
+/// True if `p` is strictly between 0.0 and 1.0.
+fn is_probability(p: f64) -> bool {
+    p > 0.0 && p < 1.0
+}
+
+/// Power of the two-sided two-sample Student's t test at
+/// significance level `alpha`, with `n` values in each
+/// group, when the means of the groups differ by
+/// `effect_size` standard deviations (Cohen's d, as from
+/// [`cohens_d`](crate::effect::cohens_d)). It is computed
+/// exactly from the noncentral t distribution. Undefined for
+/// fewer than two values per group.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::power::*;
+/// let power = power_t_test(0.5, 64, 0.05).unwrap();
+/// assert!((power - 0.8014595579).abs() < 1e-9);
+/// assert!((power_t_test(0.0, 64, 0.05).unwrap() - 0.05).abs() < 1e-12);
+/// assert_eq!(None, power_t_test(0.5, 1, 0.05));
+/// ```
+pub fn power_t_test(effect_size: f64, n: usize, alpha: f64) -> Option<f64> {
+    if !is_probability(alpha) || effect_size.is_nan() || n < 2 {
+        return None;
+    }
+    let n = n as f64;
+    let df = 2.0 * n - 2.0;
+    let ncp = effect_size * (0.5 * n).sqrt();
+    let critical = student_t_quantile(1.0 - 0.5 * alpha, df);
+    let upper = 1.0 - noncentral_t_cdf(critical, df, ncp);
+    let lower = noncentral_t_cdf(-critical, df, ncp);
+    Some((upper + lower).min(1.0))
+}
+
+/// Smallest number of values per group for which the
+/// [`power_t_test`] reaches `power`. Undefined for an
+/// `effect_size` of zero, which no sample size detects, and
+/// for a size that does not fit in a `usize`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::power::*;
+/// assert_eq!(Some(64), sample_size_t_test(0.5, 0.8, 0.05));
+/// assert_eq!(Some(23), sample_size_t_test(1.0, 0.9, 0.05));
+/// assert_eq!(None, sample_size_t_test(0.0, 0.8, 0.05));
+/// ```
+pub fn sample_size_t_test(effect_size: f64, power: f64, alpha: f64) -> Option<usize> {
+    if !is_probability(power) || !effect_size.is_finite() || effect_size == 0.0 {
+        return None;
+    }
+    let reaches = |n: usize| power_t_test(effect_size, n, alpha).map(|p| p >= power);
+    //double an upper bound until it is one, then bisect
+    let mut high = 2;
+    while !reaches(high)? {
+        high = high.checked_mul(2)?;
+    }
+    if high == 2 {
+        return Some(2);
+    }
+    let mut low = high / 2 + 1;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if reaches(mid)? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Some(high)
+}
+
+/// Power of the two-sided test of equal proportions at
+/// significance level `alpha`, with `n` trials in each
+/// group, when the true proportions are `p1` and `p2`. It
+/// comes from the normal approximation, as R's
+/// `power.prop.test` does. Undefined for no trials and for
+/// proportions outside `0.0..=1.0`, or both 0.0 or both 1.0.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::power::*;
+/// let power = power_proportion_test(0.5, 0.75, 50, 0.05).unwrap();
+/// assert!((power - 0.7401659013).abs() < 1e-9);
+/// ```
+pub fn power_proportion_test(p1: f64, p2: f64, n: usize, alpha: f64) -> Option<f64> {
+    let (pooled, separate) = proportion_spreads(p1, p2)?;
+    if !is_probability(alpha) || n == 0 {
+        return None;
+    }
+    let z = normal_quantile(1.0 - 0.5 * alpha);
+    let shift = (n as f64).sqrt() * (p1 - p2).abs();
+    Some(normal_cdf((shift - z * pooled) / separate))
+}
+
+/// Smallest number of trials per group for which the
+/// [`power_proportion_test`] reaches `power`. Undefined for
+/// equal proportions, which no sample size tells apart.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::power::*;
+/// assert_eq!(Some(77), sample_size_proportion_test(0.5, 0.75, 0.9, 0.05));
+/// assert_eq!(None, sample_size_proportion_test(0.5, 0.5, 0.9, 0.05));
+/// ```
+pub fn sample_size_proportion_test(p1: f64, p2: f64, power: f64, alpha: f64) -> Option<usize> {
+    let (pooled, separate) = proportion_spreads(p1, p2)?;
+    if !is_probability(power) || !is_probability(alpha) || p1 == p2 {
+        return None;
+    }
+    let (z_alpha, z_beta) = (normal_quantile(1.0 - 0.5 * alpha), normal_quantile(power));
+    let root_n = (z_alpha * pooled + z_beta * separate) / (p1 - p2).abs();
+    let n = (root_n * root_n).ceil();
+    if n >= usize::MAX as f64 {
+        return None;
+    }
+    Some((n as usize).max(1))
+}
+
+/// Standard deviations of a difference of proportions `p1`
+/// and `p2` on one trial each: pooled, as under the null
+/// hypothesis, and separate, as under the alternative.
+fn proportion_spreads(p1: f64, p2: f64) -> Option<(f64, f64)> {
+    if !(0.0..=1.0).contains(&p1) || !(0.0..=1.0).contains(&p2) {
+        return None;
+    }
+    let (q1, q2) = (1.0 - p1, 1.0 - p2);
+    let pooled = (0.5 * (p1 + p2) * (q1 + q2)).sqrt();
+    let separate = (p1 * q1 + p2 * q2).sqrt();
+    if separate <= 0.0 {
+        return None;
+    }
+    Some((pooled, separate))
+}
@@ -0,0 +1,198 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Checks of the quality of data, to run before trusting the
+//! statistics computed from it, and profiles of how often
+//! its values repeat.
+
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+use crate::{clusters, has_nan, sorted};
+
+/// Whether a series of values moves in one direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Monotonicity {
+    /// Each value is greater than the one before.
+    StrictlyIncreasing,
+    /// No value is less than the one before, some are
+    /// equal, and some are greater.
+    Increasing,
+    /// The values are all equal, or there are fewer than
+    /// two.
+    Constant,
+    /// No value is greater than the one before, some are
+    /// equal, and some are less.
+    Decreasing,
+    /// Each value is less than the one before.
+    StrictlyDecreasing,
+    /// Some values are greater than the one before and some
+    /// less.
+    NotMonotonic,
+}
+
+/// Report on the quality of a list of values, as computed
+/// by [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataReport {
+    /// The number of values.
+    pub count: usize,
+    /// The number of NaN values.
+    pub nan_count: usize,
+    /// The number of values that are positive infinity.
+    pub pos_inf_count: usize,
+    /// The number of values that are negative infinity.
+    pub neg_inf_count: usize,
+    /// The number of subnormal values, too small in
+    /// magnitude for full precision, which often come from
+    /// underflow.
+    pub subnormal_count: usize,
+    /// The number of values that are not NaN and equal an
+    /// earlier value, so that `count - nan_count -
+    /// duplicate_count` values are distinct.
+    pub duplicate_count: usize,
+    /// True if there is a value that is not NaN and all such
+    /// values are equal, which leaves the spread of the
+    /// values zero and many statistics undefined.
+    pub is_constant: bool,
+    /// The direction in which the values that are not NaN
+    /// move, in order.
+    pub monotonicity: Monotonicity,
+}
+
+impl DataReport {
+    /// The number of finite values, neither NaN nor
+    /// infinite.
+    pub fn finite_count(&self) -> usize {
+        self.count - self.nan_count - self.pos_inf_count - self.neg_inf_count
+    }
+
+    /// True if every value is finite, as most statistics
+    /// require.
+    pub fn is_finite(&self) -> bool {
+        self.finite_count() == self.count
+    }
+}
+
+/// Report on the quality of input values: the counts of
+/// values that are NaN, infinite, subnormal and duplicated,
+/// and whether the values are constant or monotonic.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::quality::*;
+/// let report = validate(&[1.0, 2.0, f64::NAN, 2.0, f64::INFINITY, 1e-310]);
+/// assert_eq!(6, report.count);
+/// assert_eq!((1, 1, 0), (report.nan_count, report.pos_inf_count, report.neg_inf_count));
+/// assert_eq!(1, report.subnormal_count);
+/// assert_eq!(1, report.duplicate_count);
+/// assert_eq!(Monotonicity::NotMonotonic, report.monotonicity);
+/// assert_eq!(4, report.finite_count());
+/// assert!(!report.is_finite() && !report.is_constant);
+///
+/// let report = validate(&[1.0, 2.0, 2.0, 5.0]);
+/// assert_eq!(Monotonicity::Increasing, report.monotonicity);
+/// assert!(report.is_finite());
+/// ```
+pub fn validate(nums: &[f64]) -> DataReport {
+    let count_of = |f: fn(&f64) -> bool| nums.iter().filter(|x| f(x)).count();
+    let numbers: Vec<f64> = nums.iter().copied().filter(|x| !x.is_nan()).collect();
+    let sorted = sorted(&numbers);
+    let duplicate_count = sorted.windows(2).filter(|w| w[0] == w[1]).count();
+    let (mut rises, mut falls, mut ties) = (false, false, false);
+    for w in numbers.windows(2) {
+        rises |= w[1] > w[0];
+        falls |= w[1] < w[0];
+        ties |= w[1] == w[0];
+    }
+    let monotonicity = match (rises, falls, ties) {
+        (true, true, _) => Monotonicity::NotMonotonic,
+        (true, false, false) => Monotonicity::StrictlyIncreasing,
+        (true, false, true) => Monotonicity::Increasing,
+        (false, true, false) => Monotonicity::StrictlyDecreasing,
+        (false, true, true) => Monotonicity::Decreasing,
+        (false, false, _) => Monotonicity::Constant,
+    };
+    DataReport {
+        count: nums.len(),
+        nan_count: nums.len() - numbers.len(),
+        pos_inf_count: count_of(|&x| x == f64::INFINITY),
+        neg_inf_count: count_of(|&x| x == f64::NEG_INFINITY),
+        subnormal_count: count_of(|x| x.is_subnormal()),
+        duplicate_count,
+        is_constant: !numbers.is_empty() && duplicate_count + 1 == numbers.len(),
+        monotonicity,
+    }
+}
+
+/// Number of distinct input values. Undefined for values
+/// containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::quality::*;
+/// assert_eq!(Some(3), distinct_count(&[2.0, 1.0, 2.0, 3.0, 1.0]));
+/// assert_eq!(Some(0), distinct_count(&[]));
+/// ```
+pub fn distinct_count(nums: &[f64]) -> Option<usize> {
+    if has_nan(nums) {
+        return None;
+    }
+    let sorted = sorted(nums);
+    let duplicates = sorted.windows(2).filter(|w| w[0] == w[1]).count();
+    Some(nums.len() - duplicates)
+}
+
+/// Proportion of input values that repeat an earlier value,
+/// from 0.0 when all are distinct toward 1.0 when all are
+/// equal. Undefined for an empty list and for values
+/// containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::quality::*;
+/// assert_eq!(Some(0.4), duplicate_ratio(&[2.0, 1.0, 2.0, 3.0, 1.0]));
+/// assert_eq!(None, duplicate_ratio(&[]));
+/// ```
+pub fn duplicate_ratio(nums: &[f64]) -> Option<f64> {
+    let distinct = distinct_count(nums)?;
+    if nums.is_empty() {
+        return None;
+    }
+    Some((nums.len() - distinct) as f64 / nums.len() as f64)
+}
+
+/// The `n` most frequent input values with their counts,
+/// most frequent first and ties in ascending order of value.
+/// Values are grouped into clusters spanning at most
+/// `tolerance`, as for [`modes`](crate::modes), and each
+/// cluster is represented by its middle value; a `tolerance`
+/// of 0.0 counts equal values. Undefined for values
+/// containing NaN and for a negative or NaN `tolerance`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::quality::*;
+/// let xs = [3.0, 1.0, 2.0, 3.0, 1.0, 3.0, 5.0];
+/// assert_eq!(Some(vec![(3.0, 3), (1.0, 2)]), most_frequent(&xs, 2, 0.0));
+/// let readings = [0.98, 1.01, 1.0, 2.5, 2.49, 7.0];
+/// assert_eq!(Some(vec![(1.0, 3), (2.5, 2)]), most_frequent(&readings, 2, 0.05));
+/// ```
+pub fn most_frequent(nums: &[f64], n: usize, tolerance: f64) -> Option<Vec<(f64, usize)>> {
+    if has_nan(nums) || tolerance.is_nan() || tolerance < 0.0 {
+        return None;
+    }
+    let mut clusters = clusters(&sorted(nums), tolerance);
+    //stable, so ties stay in ascending order
+    clusters.sort_by_key(|&(_, count)| Reverse(count));
+    clusters.truncate(n);
+    Some(clusters)
+}
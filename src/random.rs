@@ -0,0 +1,69 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Small seedable pseudo-random number generator for the
+//! randomized parts of the crate. It is fast and
+//! reproducible across platforms, but not cryptographically
+//! secure.
+
+/// Vigna's xoshiro256** generator, seeded by SplitMix64.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Generator whose output is determined by `seed`.
+    pub(crate) fn new(seed: u64) -> Rng {
+        //https://prng.di.unimi.it/splitmix64.c
+        let mut z = seed;
+        let mut next = || {
+            z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut x = z;
+            x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            x ^ (x >> 31)
+        };
+        Rng {
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        //https://prng.di.unimi.it/xoshiro256starstar.c
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniformly distributed integer in `0..n`, for `n > 0`.
+    pub(crate) fn below(&mut self, n: u64) -> u64 {
+        //Lemire's multiply-and-reject: https://arxiv.org/abs/1805.10941
+        let threshold = n.wrapping_neg() % n;
+        loop {
+            let product = u128::from(self.next_u64()) * u128::from(n);
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Shuffle `items` into a uniformly random order.
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        //Fisher–Yates
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Lookup of statistics functions by name, for programs that
+//! choose a statistic at runtime.
+
+use crate::{extrema, means, robust, StatFn};
+
+/// Every named statistic, in a fixed order.
+static REGISTRY: &[(&str, StatFn)] = &[
+    ("mean", crate::mean),
+    ("gmean", means::geometric_mean),
+    ("hmean", means::harmonic_mean),
+    ("variance", crate::variance),
+    ("svariance", crate::sample_variance),
+    ("stddev", crate::stddev),
+    ("sstddev", crate::sample_stddev),
+    ("skewness", crate::skewness),
+    ("sskewness", crate::sample_skewness),
+    ("kurtosis", crate::kurtosis),
+    ("ekurtosis", crate::excess_kurtosis),
+    ("sekurtosis", crate::sample_excess_kurtosis),
+    ("median", crate::median),
+    ("iqr", crate::iqr),
+    ("mad", robust::mad),
+    ("nmad", robust::normalized_mad),
+    ("sn", robust::sn),
+    ("qn", robust::qn),
+    ("l2", crate::l2),
+    ("min", extrema::min),
+    ("max", extrema::max),
+    ("range", extrema::range),
+    ("midrange", extrema::midrange),
+];
+
+/// All named statistics as `(name, function)` pairs.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// let nums = [1.0, 2.0, 3.0];
+/// for &(name, stat) in registry() {
+///     if name == "max" {
+///         assert_eq!(Some(3.0), stat(&nums));
+///     }
+/// }
+/// ```
+pub fn registry() -> &'static [(&'static str, StatFn)] {
+    REGISTRY
+}
+
+/// Names of all named statistics.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(names().any(|name| name == "stddev"));
+/// ```
+pub fn names() -> impl Iterator<Item = &'static str> {
+    REGISTRY.iter().map(|&(name, _)| name)
+}
+
+/// The statistics function with the given name, if any.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::*;
+/// assert!(lookup("no-such-statistic").is_none());
+/// ```
+/// ```
+/// # use stats::*;
+/// let stddev = lookup("stddev").unwrap();
+/// assert_eq!(Some(1.0), stddev(&[1.0, 3.0]));
+/// ```
+pub fn lookup(name: &str) -> Option<StatFn> {
+    REGISTRY
+        .iter()
+        .find(|&&(n, _)| n == name)
+        .map(|&(_, stat)| stat)
+}
@@ -0,0 +1,626 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Fitting a line or curve to paired values `(xs[i],
+//! ys[i])`, by least squares or robustly.
+//!
+//! Every fit here returns `None` when `xs` and `ys` differ
+//! in length, or when the fit is otherwise ill-defined.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::correlation::{comoments, tie_runs};
+use crate::matrix::Matrix;
+use crate::random::Rng;
+use crate::special::{normal_quantile, student_t_quantile};
+use crate::{
+    all_finite, dot, has_nan, mean, median, pairwise, quantile_sorted, sum, QuantileMethod,
+};
+
+/// Least-squares line `y = intercept + slope * x` through
+/// paired values, with its goodness of fit and the
+/// uncertainty of its coefficients under the usual
+/// assumption of independent errors of equal variance.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearFit {
+    slope: f64,
+    intercept: f64,
+    count: usize,
+    r_squared: Option<f64>,
+    residuals: Vec<f64>,
+    residual_std_error: Option<f64>,
+    slope_std_error: Option<f64>,
+    intercept_std_error: Option<f64>,
+}
+
+/// Least-squares line through paired values. The fit is
+/// undefined for fewer than two pairs, for values containing
+/// NaN, and when the `xs` are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::regression::*;
+/// assert_eq!(None, linreg(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0]));
+/// ```
+/// ```
+/// # use stats::regression::*;
+/// let fit = linreg(&[0.0, 1.0, 2.0, 3.0], &[1.0, 3.0, 5.0, 7.0]).unwrap();
+/// assert_eq!((2.0, 1.0), (fit.slope(), fit.intercept()));
+/// assert_eq!(Some(1.0), fit.r_squared());
+/// assert_eq!(11.0, fit.predict(5.0));
+/// ```
+/// ```
+/// # use stats::regression::*;
+/// let fit = linreg(&[1.0, 2.0, 3.0, 4.0, 5.0], &[1.0, 3.0, 2.0, 5.0, 4.0]).unwrap();
+/// assert!((fit.slope() - 0.8).abs() < 1e-12);
+/// assert!((fit.intercept() - 0.6).abs() < 1e-12);
+/// assert!((fit.r_squared().unwrap() - 0.64).abs() < 1e-12);
+/// let se = fit.slope_std_error().unwrap();
+/// assert!((se - 0.34641016151377546).abs() < 1e-12);
+/// let (low, high) = fit.slope_confidence_interval(0.95).unwrap();
+/// assert!((low - (0.8 - 3.18244630528371 * se)).abs() < 1e-9);
+/// assert!((high - (0.8 + 3.18244630528371 * se)).abs() < 1e-9);
+/// ```
+pub fn linreg(xs: &[f64], ys: &[f64]) -> Option<LinearFit> {
+    let n = xs.len();
+    if n != ys.len() || n < 2 || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let (sxx, syy, sxy) = comoments(xs, ys);
+    let moments = LineMoments {
+        count: n,
+        weight: n as f64,
+        xmean: mean(xs)?,
+        ymean: mean(ys)?,
+        sxx,
+        syy,
+        sxy,
+    };
+    line_fit(xs, ys, None, moments)
+}
+
+/// Weighted least-squares line through paired values,
+/// minimizing `Σ weights[i] · residual[i]²`. For
+/// measurements of differing precision, each weight should
+/// be proportional to the inverse of that measurement's
+/// error variance. The R² is the weighted one, and the
+/// coefficient errors treat the weights as relative: the
+/// error variance is estimated from the weighted residuals,
+/// with degrees of freedom from the number of positive
+/// weights.
+///
+/// The fit is undefined for lists of different lengths, for
+/// negative or NaN weights, for values containing NaN, and
+/// unless at least two `xs` of positive weight differ. With
+/// all weights equal, it is the [`linreg`] fit.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::regression::*;
+/// assert_eq!(None, linreg_weighted(&[1.0, 2.0], &[1.0, 2.0], &[1.0, -1.0]));
+/// ```
+/// ```
+/// # use stats::regression::*;
+/// let (xs, ys) = ([0.0, 1.0, 2.0, 3.0], [0.0, 1.0, 2.0, 9.0]);
+/// // The last measurement is known to be very noisy.
+/// let fit = linreg_weighted(&xs, &ys, &[1.0, 1.0, 1.0, 0.0]).unwrap();
+/// assert!((fit.slope() - 1.0).abs() < 1e-12 && fit.intercept().abs() < 1e-12);
+/// assert_eq!(3, fit.count());
+/// assert!((fit.residuals()[3] - 6.0).abs() < 1e-12);
+/// let equal = linreg_weighted(&xs, &ys, &[2.0; 4]).unwrap();
+/// assert!((equal.slope() - linreg(&xs, &ys).unwrap().slope()).abs() < 1e-12);
+/// ```
+pub fn linreg_weighted(xs: &[f64], ys: &[f64], weights: &[f64]) -> Option<LinearFit> {
+    let n = xs.len();
+    if n != ys.len() || n != weights.len() || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    if !weights.iter().all(|&w| w >= 0.0) {
+        return None;
+    }
+    let weight = sum(weights);
+    if weight <= 0.0 {
+        return None;
+    }
+    let xmean = dot(weights, xs)? / weight;
+    let ymean = dot(weights, ys)? / weight;
+    let (dxs, dys): (Vec<f64>, Vec<f64>) = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - xmean, y - ymean))
+        .unzip();
+    let weighted = |a: &[f64], b: &[f64]| {
+        let products: Vec<f64> = a.iter().zip(b).map(|(a, b)| a * b).collect();
+        dot(weights, &products)
+    };
+    let moments = LineMoments {
+        count: weights.iter().filter(|&&w| w > 0.0).count(),
+        weight,
+        xmean,
+        ymean,
+        sxx: weighted(&dxs, &dxs)?,
+        syy: weighted(&dys, &dys)?,
+        sxy: weighted(&dxs, &dys)?,
+    };
+    line_fit(xs, ys, Some(weights), moments)
+}
+
+/// Weighted count, means and sums of squared and cross
+/// deviations of paired values.
+struct LineMoments {
+    count: usize,
+    weight: f64,
+    xmean: f64,
+    ymean: f64,
+    sxx: f64,
+    syy: f64,
+    sxy: f64,
+}
+
+/// Least-squares line through paired values with the given
+/// `moments`, the pairs being weighted by `weights` if any.
+fn line_fit(
+    xs: &[f64],
+    ys: &[f64],
+    weights: Option<&[f64]>,
+    moments: LineMoments,
+) -> Option<LinearFit> {
+    let LineMoments {
+        count,
+        weight,
+        xmean,
+        ymean,
+        sxx,
+        syy,
+        sxy,
+    } = moments;
+    if count < 2 || sxx.is_nan() || sxx <= 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    let intercept = ymean - slope * xmean;
+    let residuals: Vec<f64> = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| y - (intercept + slope * x))
+        .collect();
+    let sse = match weights {
+        Some(weights) => {
+            let squares: Vec<f64> = residuals.iter().map(|r| r * r).collect();
+            dot(weights, &squares)?
+        }
+        None => pairwise(&residuals, |r| r * r).total(),
+    };
+    let r_squared = if syy > 0.0 {
+        Some((1.0 - sse / syy).max(0.0))
+    } else {
+        None
+    };
+    let residual_std_error = if count > 2 {
+        Some((sse / (count - 2) as f64).sqrt())
+    } else {
+        None
+    };
+    Some(LinearFit {
+        slope,
+        intercept,
+        count,
+        r_squared,
+        residuals,
+        residual_std_error,
+        slope_std_error: residual_std_error.map(|s| s / sxx.sqrt()),
+        intercept_std_error: residual_std_error
+            .map(|s| s * (1.0 / weight + xmean * xmean / sxx).sqrt()),
+    })
+}
+
+impl LinearFit {
+    /// Slope of the line.
+    pub fn slope(&self) -> f64 {
+        self.slope
+    }
+
+    /// Value of the line at `x = 0`.
+    pub fn intercept(&self) -> f64 {
+        self.intercept
+    }
+
+    /// Value of the line at `x`.
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept + self.slope * x
+    }
+
+    /// Number of pairs fitted, not counting those given no
+    /// weight.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Coefficient of determination: the fraction of the
+    /// variance of the `ys` explained by the line. It is
+    /// undefined when the `ys` are all equal.
+    pub fn r_squared(&self) -> Option<f64> {
+        self.r_squared
+    }
+
+    /// Residuals `ys[i] - predict(xs[i])`, in input order.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+
+    /// Residual standard error: the estimated standard
+    /// deviation of the errors (of unit weight, for a
+    /// weighted fit), with `n - 2` degrees of freedom. It is
+    /// undefined for a fit of two pairs, which always passes
+    /// through both.
+    pub fn residual_std_error(&self) -> Option<f64> {
+        self.residual_std_error
+    }
+
+    /// Standard error of the slope. Undefined for a fit of
+    /// two pairs.
+    pub fn slope_std_error(&self) -> Option<f64> {
+        self.slope_std_error
+    }
+
+    /// Standard error of the intercept. Undefined for a fit
+    /// of two pairs.
+    pub fn intercept_std_error(&self) -> Option<f64> {
+        self.intercept_std_error
+    }
+
+    /// Confidence interval for the slope at confidence
+    /// `level` (such as 0.95), from Student's t distribution
+    /// with `n - 2` degrees of freedom. Undefined for a fit
+    /// of two pairs or a `level` outside `0.0..1.0`.
+    pub fn slope_confidence_interval(&self, level: f64) -> Option<(f64, f64)> {
+        self.confidence_interval(self.slope, self.slope_std_error?, level)
+    }
+
+    /// Confidence interval for the intercept, as for
+    /// [`slope_confidence_interval`](LinearFit::slope_confidence_interval).
+    pub fn intercept_confidence_interval(&self, level: f64) -> Option<(f64, f64)> {
+        self.confidence_interval(self.intercept, self.intercept_std_error?, level)
+    }
+
+    fn confidence_interval(&self, estimate: f64, se: f64, level: f64) -> Option<(f64, f64)> {
+        if level.is_nan() || level <= 0.0 || level >= 1.0 {
+            return None;
+        }
+        let df = (self.count() - 2) as f64;
+        let margin = student_t_quantile(0.5 + 0.5 * level, df) * se;
+        Some((estimate - margin, estimate + margin))
+    }
+}
+
+/// Least-squares polynomial through paired values, with its
+/// goodness of fit.
+///
+/// The fit is computed in terms of `x` centered on the mean
+/// of the `xs` and scaled to `-1.0..=1.0`, which keeps the
+/// least-squares problem well-conditioned, and
+/// [`predict`](PolyFit::predict) evaluates it the same way.
+/// The [`coefficients`](PolyFit::coefficients) in powers
+/// of `x` itself are derived from that fit.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolyFit {
+    coefficients: Vec<f64>,
+    center: f64,
+    scale: f64,
+    scaled: Vec<f64>,
+    r_squared: Option<f64>,
+    residuals: Vec<f64>,
+    residual_std_error: Option<f64>,
+}
+
+/// Least-squares polynomial of the given `degree` through
+/// paired values, found by QR decomposition. The fit is
+/// undefined for values containing NaN, and unless there
+/// are more than `degree` distinct `xs`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::regression::*;
+/// assert_eq!(None, polyfit(&[1.0, 2.0, 2.0], &[1.0, 2.0, 3.0], 2));
+/// ```
+/// ```
+/// # use stats::regression::*;
+/// let xs = [-1.0, 0.0, 1.0, 2.0, 3.0];
+/// let ys: Vec<f64> = xs.iter().map(|x| 2.0 - x + 0.5 * x * x).collect();
+/// let fit = polyfit(&xs, &ys, 2).unwrap();
+/// let c = fit.coefficients();
+/// assert!((c[0] - 2.0).abs() < 1e-12 && (c[1] + 1.0).abs() < 1e-12);
+/// assert!((c[2] - 0.5).abs() < 1e-12);
+/// assert!((fit.predict(4.0) - 6.0).abs() < 1e-12);
+/// assert!((fit.r_squared().unwrap() - 1.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::regression::*;
+/// // A line through offset data agrees with linreg.
+/// let xs = [1e6 + 1.0, 1e6 + 2.0, 1e6 + 3.0, 1e6 + 4.0, 1e6 + 5.0];
+/// let ys = [1.0, 3.0, 2.0, 5.0, 4.0];
+/// let (line, poly) = (linreg(&xs, &ys).unwrap(), polyfit(&xs, &ys, 1).unwrap());
+/// assert!((poly.predict(1e6 + 6.0) - line.predict(1e6 + 6.0)).abs() < 1e-9);
+/// assert!((poly.r_squared().unwrap() - 0.64).abs() < 1e-12);
+/// ```
+pub fn polyfit(xs: &[f64], ys: &[f64], degree: usize) -> Option<PolyFit> {
+    let n = xs.len();
+    if n != ys.len() || n <= degree || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let center = mean(xs)?;
+    let spread = xs.iter().map(|x| (x - center).abs()).fold(0.0, f64::max);
+    let scale = if spread > 0.0 { spread } else { 1.0 };
+    let ts: Vec<f64> = xs.iter().map(|x| (x - center) / scale).collect();
+    let design = Matrix::from_fn(n, degree + 1, |i, j| ts[i].powi(j as i32));
+    let scaled = design.least_squares(ys)?;
+
+    let horner = |t: f64| scaled.iter().rev().fold(0.0, |acc, c| acc * t + c);
+    let residuals: Vec<f64> = ts.iter().zip(ys).map(|(&t, y)| y - horner(t)).collect();
+    let sse = pairwise(&residuals, |r| r * r).total();
+    let (_, syy, _) = comoments(ys, ys);
+    let r_squared = if syy > 0.0 {
+        Some((1.0 - sse / syy).max(0.0))
+    } else {
+        None
+    };
+    let residual_std_error = if n > degree + 1 {
+        Some((sse / (n - degree - 1) as f64).sqrt())
+    } else {
+        None
+    };
+
+    //expand Σ bₖ ((x - c) / s)ᵏ into powers of x
+    let mut coefficients = vec![0.0; degree + 1];
+    for (k, &b) in scaled.iter().enumerate() {
+        let mut binomial = 1.0;
+        for (j, coefficient) in coefficients.iter_mut().enumerate().take(k + 1) {
+            //term C(k, j) xʲ (-c)ᵏ⁻ʲ of (x - c)ᵏ
+            *coefficient += b * binomial * (-center).powi((k - j) as i32) / scale.powi(k as i32);
+            binomial = binomial * (k - j) as f64 / (j + 1) as f64;
+        }
+    }
+
+    Some(PolyFit {
+        coefficients,
+        center,
+        scale,
+        scaled,
+        r_squared,
+        residuals,
+        residual_std_error,
+    })
+}
+
+impl PolyFit {
+    /// Coefficients of the polynomial in increasing powers of
+    /// `x`, from the constant term up. When the `xs` are far
+    /// from zero relative to their spread these lose
+    /// precision; [`predict`](PolyFit::predict) does not.
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    /// Degree of the polynomial.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /// Value of the polynomial at `x`.
+    pub fn predict(&self, x: f64) -> f64 {
+        let t = (x - self.center) / self.scale;
+        self.scaled.iter().rev().fold(0.0, |acc, c| acc * t + c)
+    }
+
+    /// Number of pairs fitted.
+    pub fn count(&self) -> usize {
+        self.residuals.len()
+    }
+
+    /// Coefficient of determination: the fraction of the
+    /// variance of the `ys` explained by the polynomial. It
+    /// is undefined when the `ys` are all equal.
+    pub fn r_squared(&self) -> Option<f64> {
+        self.r_squared
+    }
+
+    /// Residuals `ys[i] - predict(xs[i])`, in input order.
+    pub fn residuals(&self) -> &[f64] {
+        &self.residuals
+    }
+
+    /// Residual standard error, with `n - degree - 1`
+    /// degrees of freedom. It is undefined when there are no
+    /// more pairs than coefficients.
+    pub fn residual_std_error(&self) -> Option<f64> {
+        self.residual_std_error
+    }
+}
+
+/// Theil–Sen estimate of the line through paired values:
+/// the median of the slopes between pairs of points, and the
+/// median of `ys[i] - slope * xs[i]` as the intercept. Up to
+/// about 29% of the points may be arbitrarily bad without
+/// carrying the line away.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TheilSenFit {
+    slope: f64,
+    intercept: f64,
+    slopes: Vec<f64>,
+    pairs: usize,
+    variance: f64,
+}
+
+/// Theil–Sen line through all `n(n-1)/2` pairs of the given
+/// points, taking `O(n²)` time and memory. Pairs with equal
+/// `xs` are skipped. The fit is undefined for values that
+/// are NaN or infinite and when the `xs` are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::regression::*;
+/// assert_eq!(None, theil_sen(&[2.0, 2.0], &[1.0, 3.0]));
+/// assert_eq!(None, theil_sen(&[1.0, 2.0, 3.0], &[f64::INFINITY, f64::INFINITY, 1.0]));
+/// ```
+/// ```
+/// # use stats::regression::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+/// let ys = [3.0, 5.0, 7.0, 9.0, 11.0, -80.0];
+/// let fit = theil_sen(&xs, &ys).unwrap();
+/// assert_eq!((2.0, 1.0), (fit.slope(), fit.intercept()));
+/// assert!(linreg(&xs, &ys).unwrap().slope() < 0.0);
+/// ```
+pub fn theil_sen(xs: &[f64], ys: &[f64]) -> Option<TheilSenFit> {
+    let n = xs.len();
+    if n != ys.len() || !all_finite(xs) || !all_finite(ys) {
+        return None;
+    }
+    let mut slopes = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            if xs[i] != xs[j] {
+                slopes.push((ys[j] - ys[i]) / (xs[j] - xs[i]));
+            }
+        }
+    }
+    theil_sen_fit(xs, ys, slopes)
+}
+
+/// Theil–Sen line estimated from `samples` pairs of points
+/// chosen at random, for inputs too large for
+/// [`theil_sen`]: it takes `O(n + samples)` time and
+/// `O(samples)` memory. The choice of pairs is determined by
+/// `seed`. With as many samples as pairs, this is
+/// `theil_sen`, and it is undefined likewise.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::regression::*;
+/// let xs: Vec<f64> = (0..100_000).map(f64::from).collect();
+/// let ys: Vec<f64> = xs.iter().map(|x| if x % 10.0 == 0.0 { 0.0 } else { 3.0 * x }).collect();
+/// let fit = theil_sen_sampled(&xs, &ys, 10_000, 1).unwrap();
+/// assert!((fit.slope() - 3.0).abs() < 1e-9);
+/// assert_eq!(fit, theil_sen_sampled(&xs, &ys, 10_000, 1).unwrap());
+/// ```
+pub fn theil_sen_sampled(xs: &[f64], ys: &[f64], samples: usize, seed: u64) -> Option<TheilSenFit> {
+    let n = xs.len();
+    if n != ys.len() || !all_finite(xs) || !all_finite(ys) {
+        return None;
+    }
+    if samples >= distinct_pairs(xs) {
+        return theil_sen(xs, ys);
+    }
+    //rejecting pairs with equal xs takes no more tries in
+    //all than there are pairs
+    let mut rng = Rng::new(seed);
+    let mut slopes = Vec::with_capacity(samples);
+    while slopes.len() < samples {
+        let i = rng.below(n as u64) as usize;
+        let mut j = rng.below(n as u64 - 1) as usize;
+        if j >= i {
+            j += 1;
+        }
+        if xs[i] != xs[j] {
+            slopes.push((ys[j] - ys[i]) / (xs[j] - xs[i]));
+        }
+    }
+    theil_sen_fit(xs, ys, slopes)
+}
+
+/// Number of pairs of `xs` that differ.
+fn distinct_pairs(xs: &[f64]) -> usize {
+    let n = xs.len();
+    let tied: usize = tie_runs(xs).iter().map(|t| t * (t - 1) / 2).sum();
+    n * n.saturating_sub(1) / 2 - tied
+}
+
+fn theil_sen_fit(xs: &[f64], ys: &[f64], mut slopes: Vec<f64>) -> Option<TheilSenFit> {
+    slopes.sort_by(f64::total_cmp);
+    let slope = quantile_sorted(&slopes, 0.5, QuantileMethod::Linear)?;
+    let offsets: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| y - slope * x).collect();
+    let intercept = median(&offsets)?;
+    //variance of Kendall's S between xs and ys - slope·xs, with ties in xs
+    let n = xs.len() as f64;
+    let ties: f64 = tie_runs(xs)
+        .iter()
+        .map(|&t| (t * (t - 1) * (2 * t + 5)) as f64)
+        .sum();
+    let variance = (n * (n - 1.0) * (2.0 * n + 5.0) - ties) / 18.0;
+    Some(TheilSenFit {
+        slope,
+        intercept,
+        slopes,
+        pairs: distinct_pairs(xs),
+        variance,
+    })
+}
+
+impl TheilSenFit {
+    /// Median pairwise slope.
+    pub fn slope(&self) -> f64 {
+        self.slope
+    }
+
+    /// Median intercept given the slope.
+    pub fn intercept(&self) -> f64 {
+        self.intercept
+    }
+
+    /// Value of the line at `x`.
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept + self.slope * x
+    }
+
+    /// Confidence interval for the slope at confidence
+    /// `level` (such as 0.95), by Sen's method: the interval
+    /// between the pairwise slopes whose ranks are set by the
+    /// normal approximation to the distribution of Kendall's
+    /// S. For a sampled fit the ranks are scaled to the
+    /// sample. Undefined for a `level` outside `0.0..1.0`,
+    /// and when there are too few points for the interval to
+    /// lie within the range of the slopes.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::regression::*;
+    /// let xs: Vec<f64> = (0..20).map(f64::from).collect();
+    /// let ys: Vec<f64> = xs.iter().map(|x| 2.0 * x + (x * 7.0 % 5.0)).collect();
+    /// let fit = theil_sen(&xs, &ys).unwrap();
+    /// let (low, high) = fit.slope_confidence_interval(0.95).unwrap();
+    /// assert!(low < fit.slope() && fit.slope() < high);
+    /// assert_eq!(None, theil_sen(&xs[..3], &ys[..3]).unwrap().slope_confidence_interval(0.95));
+    /// ```
+    pub fn slope_confidence_interval(&self, level: f64) -> Option<(f64, f64)> {
+        //Sen (1968): https://doi.org/10.1080/01621459.1968.10480934
+        if level.is_nan() || level <= 0.0 || level >= 1.0 {
+            return None;
+        }
+        let spread = normal_quantile(0.5 + 0.5 * level) * self.variance.sqrt();
+        let (pairs, m) = (self.pairs as f64, self.slopes.len() as f64);
+        //1-based ranks among all pairs, scaled to the slopes kept
+        let low = ((pairs - spread) / 2.0 * m / pairs).round();
+        let high = ((pairs + spread) / 2.0 * m / pairs).round() + 1.0;
+        if low < 1.0 || high > m {
+            return None;
+        }
+        Some((
+            self.slopes[low as usize - 1],
+            self.slopes[high as usize - 1],
+        ))
+    }
+}
@@ -0,0 +1,712 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Resampling methods, which estimate the uncertainty of
+//! any statistic from the sample itself, without assuming
+//! the form of the distribution it was drawn from.
+//!
+//! The statistics are [`StatFn`]s, such as [`mean`] or
+//! [`median`](crate::median). The module also has the
+//! random sampling and shuffling the methods are built on.
+//! The random choices are determined by a `seed`, so that
+//! results can be reproduced.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::intervals::half_alpha;
+use crate::random::Rng;
+use crate::special::{normal_cdf, normal_quantile};
+use crate::tests::TestResult;
+use crate::{has_nan, mean, quantile_sorted, sample_stddev, sorted, QuantileMethod, StatFn};
+
+/// Shuffle `items` into a random order, chosen by `seed`.
+/// Every order is equally likely.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let mut xs = [1, 2, 3, 4, 5, 6, 7, 8];
+/// shuffle(&mut xs, 3);
+/// assert_ne!([1, 2, 3, 4, 5, 6, 7, 8], xs);
+/// xs.sort();
+/// assert_eq!([1, 2, 3, 4, 5, 6, 7, 8], xs);
+/// ```
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    Rng::new(seed).shuffle(items);
+}
+
+/// `k` of the items drawn at random without replacement, in
+/// the order they appear in `items`, with the choice
+/// determined by `seed`. Every subset of `k` items is
+/// equally likely. The time and memory taken grow with `k`,
+/// not with the number of items, so this suits taking a
+/// small subsample of a huge list. `None` if `k` is more
+/// than the number of items.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let xs: Vec<f64> = (0..1_000_000).map(f64::from).collect();
+/// let sample = sample_without_replacement(&xs, 1000, 5).unwrap();
+/// assert_eq!(1000, sample.len());
+/// assert!(sample.windows(2).all(|w| w[0] < w[1]));
+/// assert!((stats::mean(&sample).unwrap() - 500_000.0).abs() < 30_000.0);
+/// assert_eq!(None, sample_without_replacement(&xs[..3], 4, 5));
+/// ```
+pub fn sample_without_replacement<T: Clone>(items: &[T], k: usize, seed: u64) -> Option<Vec<T>> {
+    if k > items.len() {
+        return None;
+    }
+    let chosen = choose(&mut Rng::new(seed), items.len(), k);
+    Some(chosen.into_iter().map(|i| items[i].clone()).collect())
+}
+
+/// `k <= n` of the indices `0..n` chosen at random without
+/// replacement.
+fn choose(rng: &mut Rng, n: usize, k: usize) -> BTreeSet<usize> {
+    //Floyd's algorithm: each j adds a new index, uniformly
+    //completing a random subset of 0..=j
+    let mut chosen = BTreeSet::new();
+    for j in n - k..n {
+        let i = rng.below(j as u64 + 1) as usize;
+        if !chosen.insert(i) {
+            chosen.insert(j);
+        }
+    }
+    chosen
+}
+
+/// How a [`stratified_sample`] divides the sample among the
+/// strata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Allocation {
+    /// In proportion to the sizes of the strata, so that the
+    /// sample has the population's mix of strata.
+    #[default]
+    Proportional,
+    /// Equally, so that small strata are as well represented
+    /// as large ones, except that a stratum smaller than its
+    /// share is taken whole.
+    Equal,
+}
+
+/// Indices of `k` of the values drawn at random without
+/// replacement within strata: `labels[i]` is the stratum of
+/// `values[i]`, and the sample is divided among the strata
+/// by `allocation`. Proportional allocations are rounded by
+/// the largest remainders, so they sum to `k`. The indices
+/// are sorted, and the choice is determined by `seed`.
+/// `None` for lists of different lengths or `k` more than
+/// the number of values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let values = [5.0, 3.0, 8.0, 1.0, 9.0, 2.0, 7.0, 4.0, 6.0, 0.0];
+/// let labels = ["a", "a", "a", "a", "a", "a", "b", "b", "b", "c"];
+/// let sample = stratified_sample(&values, &labels, 5, Allocation::Proportional, 1).unwrap();
+/// let count = |l| sample.iter().filter(|&&i| labels[i] == l).count();
+/// assert_eq!((3, 2, 0), (count("a"), count("b"), count("c")));
+/// let sample = stratified_sample(&values, &labels, 5, Allocation::Equal, 1).unwrap();
+/// let count = |l| sample.iter().filter(|&&i| labels[i] == l).count();
+/// assert_eq!((2, 2, 1), (count("a"), count("b"), count("c")));
+/// ```
+pub fn stratified_sample<T, L: Ord>(
+    values: &[T],
+    labels: &[L],
+    k: usize,
+    allocation: Allocation,
+    seed: u64,
+) -> Option<Vec<usize>> {
+    let n = values.len();
+    if n != labels.len() || k > n {
+        return None;
+    }
+    let mut strata: BTreeMap<&L, Vec<usize>> = BTreeMap::new();
+    for (i, label) in labels.iter().enumerate() {
+        strata.entry(label).or_default().push(i);
+    }
+    let sizes: Vec<usize> = strata.values().map(|s| s.len()).collect();
+    let shares = match allocation {
+        Allocation::Proportional => {
+            let mut shares: Vec<usize> = sizes.iter().map(|&size| k * size / n).collect();
+            //hand out the rest by the largest remainders
+            let mut order: Vec<usize> = (0..sizes.len()).collect();
+            order.sort_by_key(|&h| core::cmp::Reverse(k * sizes[h] % n));
+            let rest = k - shares.iter().sum::<usize>();
+            for &h in &order[..rest] {
+                shares[h] += 1;
+            }
+            shares
+        }
+        Allocation::Equal => {
+            //deal one at a time to the strata with room left
+            let mut shares = vec![0; sizes.len()];
+            let mut rest = k;
+            while rest > 0 {
+                for (share, &size) in shares.iter_mut().zip(&sizes) {
+                    if rest > 0 && *share < size {
+                        *share += 1;
+                        rest -= 1;
+                    }
+                }
+            }
+            shares
+        }
+    };
+    let mut rng = Rng::new(seed);
+    let mut sample: Vec<usize> = strata
+        .values()
+        .zip(shares)
+        .flat_map(|(stratum, share)| {
+            let chosen = choose(&mut rng, stratum.len(), share);
+            chosen
+                .into_iter()
+                .map(|i| stratum[i])
+                .collect::<Vec<usize>>()
+        })
+        .collect();
+    sample.sort_unstable();
+    Some(sample)
+}
+
+/// `k` of the items drawn at random with replacement, with
+/// the choice determined by `seed`: each of the `k` is any
+/// of the items with equal probability. `None` for no items
+/// when `k` is positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let sample = sample_with_replacement(&["a", "b", "c"], 10, 2).unwrap();
+/// assert_eq!(10, sample.len());
+/// assert!(sample.iter().all(|s| ["a", "b", "c"].contains(s)));
+/// assert_eq!(Some(vec![]), sample_with_replacement::<f64>(&[], 0, 2));
+/// ```
+pub fn sample_with_replacement<T: Clone>(items: &[T], k: usize, seed: u64) -> Option<Vec<T>> {
+    if items.is_empty() && k > 0 {
+        return None;
+    }
+    let mut rng = Rng::new(seed);
+    let n = items.len() as u64;
+    Some(
+        (0..k)
+            .map(|_| items[rng.below(n) as usize].clone())
+            .collect(),
+    )
+}
+
+/// Indices `0..n` split at random into a training set and a
+/// test set of `test_fraction` of them (rounded to the
+/// nearest count), as the pair `(train, test)`, each sorted.
+/// The split is determined by `seed`. `None` unless
+/// `test_fraction` is in `0.0..=1.0`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let (train, test) = train_test_split(10, 0.3, 4).unwrap();
+/// assert_eq!((7, 3), (train.len(), test.len()));
+/// let mut all = [train, test].concat();
+/// all.sort();
+/// assert_eq!((0..10).collect::<Vec<_>>(), all);
+/// ```
+pub fn train_test_split(
+    n: usize,
+    test_fraction: f64,
+    seed: u64,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    if !(0.0..=1.0).contains(&test_fraction) {
+        return None;
+    }
+    let mut indices: Vec<usize> = (0..n).collect();
+    shuffle(&mut indices, seed);
+    let split = n - (test_fraction * n as f64).round() as usize;
+    let mut test = indices.split_off(split);
+    indices.sort_unstable();
+    test.sort_unstable();
+    Some((indices, test))
+}
+
+/// Indices `0..n` split at random into `k` folds for k-fold
+/// cross-validation, as one pair `(train, test)` per fold:
+/// each fold's indices are its test set, and those of the
+/// other folds its training set. Every index is in exactly
+/// one test set, and the fold sizes differ by at most one.
+/// Both sets are sorted. The split is determined by `seed`.
+/// `None` unless `k` is at least 2 and at most `n`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let folds = kfold(10, 3, 6).unwrap();
+/// assert_eq!(3, folds.len());
+/// let sizes: Vec<usize> = folds.iter().map(|(_, test)| test.len()).collect();
+/// assert_eq!(vec![4, 3, 3], sizes);
+/// for (train, test) in &folds {
+///     assert_eq!(10, train.len() + test.len());
+///     assert!(test.iter().all(|i| !train.contains(i)));
+/// }
+/// assert_eq!(None, kfold(10, 1, 6));
+/// ```
+pub fn kfold(n: usize, k: usize, seed: u64) -> Option<Vec<(Vec<usize>, Vec<usize>)>> {
+    if k < 2 || k > n {
+        return None;
+    }
+    let mut indices: Vec<usize> = (0..n).collect();
+    shuffle(&mut indices, seed);
+    //the first n % k folds take one extra index
+    let bounds: Vec<usize> = (0..=k).map(|f| f * (n / k) + f.min(n % k)).collect();
+    let folds = bounds
+        .windows(2)
+        .map(|w| {
+            let mut test = indices[w[0]..w[1]].to_vec();
+            let mut train = [&indices[..w[0]], &indices[w[1]..]].concat();
+            test.sort_unstable();
+            train.sort_unstable();
+            (train, test)
+        })
+        .collect();
+    Some(folds)
+}
+
+/// The statistic of the input values with each value left
+/// out in turn, or `None` for fewer than two values or if
+/// the statistic is undefined for any of them.
+fn leave_one_out(nums: &[f64], statistic: StatFn) -> Option<Vec<f64>> {
+    if nums.len() < 2 {
+        return None;
+    }
+    let mut rest = nums[1..].to_vec();
+    let mut values = Vec::with_capacity(nums.len());
+    for i in 0..nums.len() {
+        //rest holds nums without nums[i]
+        if i > 0 {
+            rest[i - 1] = nums[i - 1];
+        }
+        values.push(statistic(&rest)?);
+    }
+    Some(values)
+}
+
+/// Jackknife estimates of the bias and standard error of a
+/// statistic, as computed by [`jackknife`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Jackknife {
+    /// The statistic of the sample itself.
+    pub statistic: f64,
+    /// The statistic corrected for its estimated bias:
+    /// `statistic - bias`.
+    pub estimate: f64,
+    /// Estimated bias of the statistic.
+    pub bias: f64,
+    /// Estimated standard error of the statistic.
+    pub std_error: f64,
+}
+
+/// Jackknife of a statistic of input values: estimates of
+/// its bias and standard error from its values on the `n`
+/// samples leaving out one value each. With the mean of
+/// those values `m`, the bias is `(n - 1) (m - statistic)`
+/// and the squared standard error `(n - 1) / n` times the
+/// sum of their squared deviations from `m`.
+///
+/// The jackknife is deterministic and takes only `n`
+/// evaluations of the statistic, but it suits only smooth
+/// statistics: its standard error for the median, for one,
+/// is unreliable. The jackknife is undefined for fewer than
+/// two values, for values containing NaN, and when the
+/// statistic is undefined for the values or for any of the
+/// samples.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let mean = jackknife(&xs, stats::mean).unwrap();
+/// assert!(mean.bias.abs() < 1e-12);
+/// assert!((mean.std_error - 0.5f64.sqrt()).abs() < 1e-12);
+/// // The population variance is biased low; the correction
+/// // gives the sample variance.
+/// let variance = jackknife(&xs, stats::variance).unwrap();
+/// assert!((variance.bias + 0.5).abs() < 1e-12);
+/// assert!((variance.estimate - 2.5).abs() < 1e-12);
+/// ```
+pub fn jackknife(nums: &[f64], statistic: StatFn) -> Option<Jackknife> {
+    if has_nan(nums) {
+        return None;
+    }
+    let value = statistic(nums)?;
+    let values = leave_one_out(nums, statistic)?;
+    let n = nums.len() as f64;
+    let m = mean(&values)?;
+    let squares: f64 = values.iter().map(|v| (v - m) * (v - m)).sum();
+    let bias = (n - 1.0) * (m - value);
+    Some(Jackknife {
+        statistic: value,
+        estimate: value - bias,
+        bias,
+        std_error: ((n - 1.0) / n * squares).sqrt(),
+    })
+}
+
+/// Bootstrap estimate of the sampling distribution of a
+/// statistic, as computed by [`bootstrap`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bootstrap {
+    estimate: f64,
+    replicates: Vec<f64>,
+    percentile: (f64, f64),
+    bca: Option<(f64, f64)>,
+}
+
+impl Bootstrap {
+    /// The statistic of the sample itself.
+    pub fn estimate(&self) -> f64 {
+        self.estimate
+    }
+
+    /// The statistic of each resample, sorted.
+    pub fn replicates(&self) -> &[f64] {
+        &self.replicates
+    }
+
+    /// Estimated bias of the statistic: the mean of the
+    /// [`replicates`](Bootstrap::replicates) less the
+    /// [`estimate`](Bootstrap::estimate).
+    pub fn bias(&self) -> f64 {
+        mean(&self.replicates).unwrap() - self.estimate
+    }
+
+    /// Estimated standard error of the statistic: the
+    /// standard deviation of the
+    /// [`replicates`](Bootstrap::replicates), or `None` for
+    /// fewer than two.
+    pub fn std_error(&self) -> Option<f64> {
+        sample_stddev(&self.replicates)
+    }
+
+    /// Percentile confidence interval: the quantiles of the
+    /// replicates cutting off `(1 - level) / 2` in each tail.
+    pub fn percentile_interval(&self) -> (f64, f64) {
+        self.percentile
+    }
+
+    /// Bias-corrected and accelerated (BCa) confidence
+    /// interval: percentiles of the replicates adjusted for
+    /// the bias of the statistic and for the change of its
+    /// standard error with the parameter, which is more
+    /// accurate than the
+    /// [`percentile_interval`](Bootstrap::percentile_interval)
+    /// for skewed statistics. `None` for a
+    /// [`block_bootstrap`], when every replicate falls on
+    /// the same side of the estimate, or when the statistic
+    /// is undefined for the sample with a value left out,
+    /// which the acceleration needs.
+    pub fn bca_interval(&self) -> Option<(f64, f64)> {
+        self.bca
+    }
+}
+
+/// Bootstrap of a statistic of input values: the statistic
+/// of each of `resamples` resamples drawn with replacement
+/// from the values, with confidence intervals at `level`
+/// (such as 0.95) from them. Resamples for which the
+/// statistic is undefined are left out. The bootstrap is
+/// undefined for an empty list, for values containing NaN,
+/// for a `level` outside `0.0..1.0`, and when the statistic
+/// is undefined for the values or for every resample.
+///
+/// A thousand or more resamples give stable intervals; the
+/// BCa interval needs the most.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let xs = [2.1, 3.4, 1.9, 5.6, 2.8, 3.0, 4.2, 2.5, 3.9, 2.2];
+/// let boot = bootstrap(&xs, stats::mean, 2000, 0.95, 1).unwrap();
+/// assert!((boot.estimate() - 3.16).abs() < 1e-12);
+/// assert_eq!(2000, boot.replicates().len());
+/// let (low, high) = boot.percentile_interval();
+/// assert!(2.4 < low && low < 3.16 && 3.16 < high && high < 4.0);
+/// // The BCa interval leans toward the long right tail.
+/// let (low, high) = boot.bca_interval().unwrap();
+/// assert!(2.4 < low && low < 3.16 && 3.16 < high && high < 4.0);
+/// assert!(high > boot.percentile_interval().1);
+/// assert!((boot.std_error().unwrap() - 0.35).abs() < 0.05);
+/// assert_eq!(boot, bootstrap(&xs, stats::mean, 2000, 0.95, 1).unwrap());
+/// ```
+pub fn bootstrap(
+    nums: &[f64],
+    statistic: StatFn,
+    resamples: usize,
+    level: f64,
+    seed: u64,
+) -> Option<Bootstrap> {
+    let n = nums.len();
+    bootstrap_with(
+        nums,
+        statistic,
+        resamples,
+        level,
+        seed,
+        true,
+        |rng, resample| {
+            resample.extend((0..n).map(|_| nums[rng.below(n as u64) as usize]));
+        },
+    )
+}
+
+/// How a [`block_bootstrap`] chooses its blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockScheme {
+    /// Blocks of consecutive values starting anywhere they
+    /// fit, so that values near the ends of the series turn
+    /// up in fewer blocks.
+    #[default]
+    Moving,
+    /// Blocks of consecutive values starting anywhere,
+    /// wrapping around from the end of the series to its
+    /// start, so that every value is equally likely to turn
+    /// up.
+    Circular,
+}
+
+/// Bootstrap of a statistic of a series of values, such as
+/// a time series, whose neighbouring values are correlated:
+/// each resample joins blocks of `block_len` consecutive
+/// values chosen at random with replacement by `scheme`,
+/// cut to the length of the series, so that the correlation
+/// within each block survives. The plain [`bootstrap`]
+/// resamples values independently and so understates the
+/// uncertainty of correlated values.
+///
+/// The blocks should be long compared with the lags at
+/// which the values are correlated, and short compared with
+/// the series; a common choice is about `n^(1/3)` values.
+/// With `block_len` of 1 this is the plain bootstrap. The
+/// result has no [`bca_interval`](Bootstrap::bca_interval),
+/// whose correction assumes independent values. The block
+/// bootstrap is undefined as for the plain bootstrap and
+/// for a `block_len` of zero or longer than the series.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// // A slowly wandering series, whose mean is much less
+/// // certain than that of as many independent values.
+/// let xs: Vec<f64> = (0..200).map(|i| (i as f64 / 15.0).sin() + 0.1 * (i % 3) as f64).collect();
+/// let blocks = block_bootstrap(&xs, 20, stats::mean, 1000, 0.95, BlockScheme::Circular, 1).unwrap();
+/// let plain = bootstrap(&xs, stats::mean, 1000, 0.95, 1).unwrap();
+/// assert!(blocks.std_error().unwrap() > 2.0 * plain.std_error().unwrap());
+/// assert_eq!(None, blocks.bca_interval());
+/// assert_eq!(None, block_bootstrap(&xs, 0, stats::mean, 1000, 0.95, BlockScheme::Moving, 1));
+/// ```
+pub fn block_bootstrap(
+    nums: &[f64],
+    block_len: usize,
+    statistic: StatFn,
+    resamples: usize,
+    level: f64,
+    scheme: BlockScheme,
+    seed: u64,
+) -> Option<Bootstrap> {
+    let n = nums.len();
+    if block_len == 0 || block_len > n {
+        return None;
+    }
+    let starts = match scheme {
+        BlockScheme::Moving => n - block_len + 1,
+        BlockScheme::Circular => n,
+    };
+    bootstrap_with(
+        nums,
+        statistic,
+        resamples,
+        level,
+        seed,
+        false,
+        |rng, resample| {
+            while resample.len() < n {
+                let start = rng.below(starts as u64) as usize;
+                let len = block_len.min(n - resample.len());
+                resample.extend((start..start + len).map(|i| nums[i % n]));
+            }
+        },
+    )
+}
+
+/// Bootstrap of a statistic of input values with each
+/// resample made by `fill`, and a BCa interval if `bca`.
+fn bootstrap_with<F: FnMut(&mut Rng, &mut Vec<f64>)>(
+    nums: &[f64],
+    statistic: StatFn,
+    resamples: usize,
+    level: f64,
+    seed: u64,
+    bca: bool,
+    mut fill: F,
+) -> Option<Bootstrap> {
+    let tail = half_alpha(level)?;
+    if nums.is_empty() || has_nan(nums) {
+        return None;
+    }
+    let estimate = statistic(nums)?;
+    let mut rng = Rng::new(seed);
+    let mut resample = Vec::with_capacity(nums.len());
+    let mut replicates = Vec::with_capacity(resamples);
+    for _ in 0..resamples {
+        resample.clear();
+        fill(&mut rng, &mut resample);
+        if let Some(value) = statistic(&resample) {
+            replicates.push(value);
+        }
+    }
+    if replicates.is_empty() || has_nan(&replicates) {
+        return None;
+    }
+    let replicates = sorted(&replicates);
+    let at = |p: f64| quantile_sorted(&replicates, p, QuantileMethod::Linear).unwrap();
+    let percentile = (at(tail), at(1.0 - tail));
+    let bca = if bca {
+        bca_probabilities(nums, statistic, estimate, &replicates, tail)
+            .map(|(low, high)| (at(low), at(high)))
+    } else {
+        None
+    };
+    Some(Bootstrap {
+        estimate,
+        replicates,
+        percentile,
+        bca,
+    })
+}
+
+/// Probabilities at which to take the quantiles of the
+/// sorted `replicates` for the BCa interval with `tail` in
+/// each tail.
+fn bca_probabilities(
+    nums: &[f64],
+    statistic: StatFn,
+    estimate: f64,
+    replicates: &[f64],
+    tail: f64,
+) -> Option<(f64, f64)> {
+    //Efron & Tibshirani, An Introduction to the Bootstrap, §14.3
+    let below = replicates.partition_point(|&r| r < estimate);
+    if below == 0 || below == replicates.len() {
+        return None;
+    }
+    let z0 = normal_quantile(below as f64 / replicates.len() as f64);
+    let jackknife = leave_one_out(nums, statistic)?;
+    let center = mean(&jackknife)?;
+    let (mut squares, mut cubes) = (0.0, 0.0);
+    for d in jackknife.iter().map(|j| center - j) {
+        squares += d * d;
+        cubes += d * d * d;
+    }
+    let a = if squares > 0.0 {
+        cubes / (6.0 * squares.powf(1.5))
+    } else {
+        0.0
+    };
+    let adjust = |z: f64| normal_cdf(z0 + (z0 + z) / (1.0 - a * (z0 + z)));
+    let z = normal_quantile(tail);
+    let (low, high) = (adjust(z), adjust(-z));
+    if low.is_nan() || high.is_nan() {
+        return None;
+    }
+    Some((low, high))
+}
+
+/// Permutation test of whether two groups of values come
+/// from the same distribution, judged by the difference of
+/// a statistic of the groups. The test statistic is
+/// `statistic(xs) - statistic(ys)`; its distribution if the
+/// groups are alike is estimated by computing it for
+/// `permutations` random reassignments of the values to the
+/// groups, chosen by `seed`. The p-value is the fraction of
+/// the reassignments, counting the observed one, whose
+/// difference is at least as large in magnitude as the
+/// observed one, so it is never below `1 / (permutations +
+/// 1)`.
+///
+/// Any statistic may be compared, such as the difference of
+/// 95th percentiles, for which there is no closed-form test.
+/// Reassignments for which the statistic of either group is
+/// undefined are left out. The test is undefined for an
+/// empty group, for values containing NaN, and when the
+/// statistic is undefined for either group or for every
+/// reassignment.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::resampling::*;
+/// let xs = [12.1, 14.3, 13.8, 15.2, 14.9, 13.5, 16.0, 14.4];
+/// let ys = [11.2, 12.0, 11.8, 12.9, 11.5, 12.4, 13.1, 12.2];
+/// let result = permutation_test(&xs, &ys, stats::median, 5000, 1).unwrap();
+/// assert!((result.statistic - 2.25).abs() < 1e-12);
+/// assert!(result.p_value < 0.01);
+/// let result = permutation_test(&xs[..4], &xs[4..], stats::median, 5000, 1).unwrap();
+/// assert!(result.p_value > 0.2);
+/// ```
+pub fn permutation_test(
+    xs: &[f64],
+    ys: &[f64],
+    statistic: StatFn,
+    permutations: usize,
+    seed: u64,
+) -> Option<TestResult> {
+    if xs.is_empty() || ys.is_empty() || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let observed = statistic(xs)? - statistic(ys)?;
+    if observed.is_nan() {
+        return None;
+    }
+    //differences this close to the observed one differ only
+    //by the rounding of the statistic, so count as ties
+    let threshold = observed.abs() * (1.0 - 1e-12);
+    let mut pooled = [xs, ys].concat();
+    let mut rng = Rng::new(seed);
+    let (mut count, mut extreme) = (0, 0);
+    for _ in 0..permutations {
+        rng.shuffle(&mut pooled);
+        let (a, b) = pooled.split_at(xs.len());
+        if let (Some(a), Some(b)) = (statistic(a), statistic(b)) {
+            count += 1;
+            if (a - b).abs() >= threshold {
+                extreme += 1;
+            }
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    Some(TestResult {
+        statistic: observed,
+        df: None,
+        p_value: (extreme + 1) as f64 / (count + 1) as f64,
+    })
+}
@@ -0,0 +1,298 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Robust estimators of location and scale, which are
+//! little affected by a minority of outlying values.
+//!
+//! The "normalized" estimators are scaled so that for
+//! normally-distributed data they estimate the standard
+//! deviation.
+
+use alloc::vec::Vec;
+
+use crate::{has_nan, median, sorted};
+
+/// Consistency constant making the MAD estimate the
+/// standard deviation of a normal distribution: `1 /
+/// Φ⁻¹(3/4)`.
+pub const MAD_NORMAL_CONSTANT: f64 = 1.4826;
+
+/// Median absolute deviation from the median of input
+/// values. The MAD of an empty list, or of a list containing
+/// NaN, is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// assert_eq!(None, mad(&[]));
+/// ```
+/// ```
+/// # use stats::robust::*;
+/// assert_eq!(Some(1.0), mad(&[1.0, 2.0, 3.0, 4.0, 1000.0]));
+/// ```
+pub fn mad(nums: &[f64]) -> Option<f64> {
+    let center = median(nums)?;
+    let deviations: Vec<f64> = nums.iter().map(|x| (x - center).abs()).collect();
+    median(&deviations)
+}
+
+/// Median absolute deviation of input values scaled by
+/// [`MAD_NORMAL_CONSTANT`].
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// assert_eq!(Some(1.4826), normalized_mad(&[1.0, 2.0, 3.0, 4.0, 1000.0]));
+/// ```
+pub fn normalized_mad(nums: &[f64]) -> Option<f64> {
+    mad(nums).map(|m| MAD_NORMAL_CONSTANT * m)
+}
+
+/// Low median (the `⌊(n + 1) / 2⌋`-th smallest) of a
+/// list.
+fn low_median(mut nums: Vec<f64>) -> f64 {
+    let k = nums.len().div_ceil(2) - 1;
+    *nums.select_nth_unstable_by(k, f64::total_cmp).1
+}
+
+/// High median (the `(⌊n / 2⌋ + 1)`-th smallest) of a
+/// list.
+fn high_median(mut nums: Vec<f64>) -> f64 {
+    let k = nums.len() / 2;
+    *nums.select_nth_unstable_by(k, f64::total_cmp).1
+}
+
+/// Rousseeuw and Croux's Sn scale estimator of input
+/// values, `c · lomed_i himed_j |x_i - x_j|`, normalized
+/// with their small-sample correction factors. Sn is
+/// undefined for fewer than two values or for a list
+/// containing NaN. This takes O(n²) time.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// assert_eq!(None, sn(&[1.0]));
+/// ```
+/// ```
+/// # use stats::robust::*;
+/// let s = sn(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 1000.0]).unwrap();
+/// assert!((s - 3.8966).abs() < 1e-3);
+/// ```
+pub fn sn(nums: &[f64]) -> Option<f64> {
+    //estimator referenced on: Rousseeuw & Croux, "Alternatives to the Median Absolute Deviation" (1993)
+    let n = nums.len();
+    if n < 2 || has_nan(nums) {
+        return None;
+    }
+    let inner: Vec<f64> = nums
+        .iter()
+        .map(|xi| high_median(nums.iter().map(|xj| (xi - xj).abs()).collect()))
+        .collect();
+    let correction = match n {
+        2..=9 => [0.743, 1.851, 0.954, 1.351, 0.993, 1.198, 1.005, 1.131][n - 2],
+        _ if n % 2 == 1 => n as f64 / (n as f64 - 0.9),
+        _ => 1.0,
+    };
+    Some(correction * 1.1926 * low_median(inner))
+}
+
+/// Rousseeuw and Croux's Qn scale estimator of input
+/// values: `d` times the `k`-th smallest of the pairwise
+/// distances `|x_i - x_j|, i < j`, with `k = h(h - 1) / 2`
+/// and `h = ⌊n / 2⌋ + 1`, normalized with their
+/// small-sample correction factors. Qn is undefined for
+/// fewer than two values or for a list containing NaN. This
+/// takes O(n²) time and space.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// assert_eq!(None, qn(&[]));
+/// ```
+/// ```
+/// # use stats::robust::*;
+/// let q = qn(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 1000.0]).unwrap();
+/// assert!((q - 3.9421).abs() < 1e-3);
+/// ```
+pub fn qn(nums: &[f64]) -> Option<f64> {
+    //estimator referenced on: Rousseeuw & Croux, "Alternatives to the Median Absolute Deviation" (1993)
+    let n = nums.len();
+    if n < 2 || has_nan(nums) {
+        return None;
+    }
+    let nums = sorted(nums);
+    let mut distances = Vec::with_capacity(n * (n - 1) / 2);
+    for i in 0..n {
+        for j in i + 1..n {
+            distances.push(nums[j] - nums[i]);
+        }
+    }
+    let h = n / 2 + 1;
+    let k = h * (h - 1) / 2;
+    let kth = *distances.select_nth_unstable_by(k - 1, f64::total_cmp).1;
+    let correction = match n {
+        2..=9 => [0.399, 0.994, 0.512, 0.844, 0.611, 0.857, 0.669, 0.872][n - 2],
+        _ if n % 2 == 1 => n as f64 / (n as f64 + 1.4),
+        _ => n as f64 / (n as f64 + 3.8),
+    };
+    Some(correction * 2.2219 * kth)
+}
+
+/// Most iterations of the reweighting estimators before they
+/// give up on converging.
+const MAX_ITERATIONS: usize = 1000;
+
+/// True if `x` is a valid tuning constant or tolerance.
+fn is_positive(x: f64) -> bool {
+    x.is_finite() && x > 0.0
+}
+
+/// Huber's M-estimate of the location of input values: the
+/// center about which values within `k` scale units count
+/// fully and values farther out count as if only `k` units
+/// out. The scale is the [`normalized_mad`], held fixed, as
+/// in R's `MASS::huber`. Starting from the median, the
+/// estimate is reweighted until it moves by at most
+/// `tolerance` times the scale. Smaller `k` is more robust
+/// and larger `k` closer to the mean; `k = 1.345` is 95% as
+/// efficient as the mean for normal values, and `1.5` is
+/// also common.
+///
+/// The estimate is the median if more than half the values
+/// are equal. It is undefined for an empty list, for a list
+/// containing NaN or infinite values, for `k` or `tolerance`
+/// that is not positive, if the sum of the values overflows,
+/// and if it fails to converge.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// assert_eq!(None, huber_location(&[-1e308, -1e308, 0.0, 1e308, 1e308], 1.5, 1e-9));
+/// assert_eq!(None, huber_location(&[1.0, f64::INFINITY, 2.0], 1.5, 1e-9));
+/// ```
+/// ```
+/// # use stats::robust::*;
+/// let xs = [2.0, 2.1, 2.3, 2.6, 2.8, 3.0, 3.5, 4.1, 9.7, 15.0, 1.2];
+/// let location = huber_location(&xs, 1.5, 1e-12).unwrap();
+/// assert!((location - 2.99459125).abs() < 1e-8);
+/// assert!(location < stats::mean(&xs).unwrap());
+/// ```
+pub fn huber_location(nums: &[f64], k: f64, tolerance: f64) -> Option<f64> {
+    if !is_positive(k) || !is_positive(tolerance) || nums.iter().any(|x| !x.is_finite()) {
+        return None;
+    }
+    let scale = normalized_mad(nums)?;
+    let mut location = median(nums)?;
+    for _ in 0..MAX_ITERATIONS {
+        let (low, high) = (location - k * scale, location + k * scale);
+        let next = nums.iter().map(|x| x.clamp(low, high)).sum::<f64>() / nums.len() as f64;
+        if !next.is_finite() {
+            return None;
+        }
+        let step = (next - location).abs();
+        location = next;
+        if step <= tolerance * scale {
+            return Some(location);
+        }
+    }
+    None
+}
+
+/// Tukey's biweight location of input values: the mean
+/// weighted by `(1 - u²)²`, for `u` each value's distance
+/// from the location in units of `c` times the [`mad`],
+/// which gives values `c` MADs out no weight at all.
+/// Starting from the median, with the MAD about the median
+/// held fixed, the estimate is reweighted until it moves by
+/// at most `tolerance` times the MAD. `c = 6` is usual.
+///
+/// The estimate is the median if more than half the values
+/// are equal. It is undefined for an empty list, for a list
+/// containing NaN, for `c` or `tolerance` that is not
+/// positive, and if it fails to converge.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// let xs = [2.0, 2.1, 2.3, 2.6, 2.8, 3.0, 3.5, 4.1, 9.7, 15.0, 1.2];
+/// let location = biweight_location(&xs, 6.0, 1e-12).unwrap();
+/// assert!((location - 2.61232629).abs() < 1e-8);
+/// ```
+pub fn biweight_location(nums: &[f64], c: f64, tolerance: f64) -> Option<f64> {
+    if !is_positive(c) || !is_positive(tolerance) {
+        return None;
+    }
+    let mad = mad(nums)?;
+    let mut location = median(nums)?;
+    if mad == 0.0 {
+        return Some(location);
+    }
+    for _ in 0..MAX_ITERATIONS {
+        let (mut weighted, mut total) = (0.0, 0.0);
+        for x in nums {
+            let u = (x - location) / (c * mad);
+            if u.abs() < 1.0 {
+                let w = (1.0 - u * u) * (1.0 - u * u);
+                weighted += w * x;
+                total += w;
+            }
+        }
+        let next = weighted / total;
+        let step = (next - location).abs();
+        location = next;
+        if step <= tolerance * mad {
+            return Some(location);
+        }
+    }
+    None
+}
+
+/// Biweight midvariance of input values, a robust estimate
+/// of their variance: with `u` each value's distance from
+/// the median in units of `c` times the [`mad`],
+///
+/// `n Σ (x - M)² (1 - u²)⁴ / (Σ (1 - u²)(1 - 5u²))²`
+///
+/// over the values with `|u| < 1`. `c = 9` is usual, with
+/// which it comes within a few percent of the variance of
+/// normal values. It is zero if more than half the values
+/// are equal, and undefined for an empty list, for a list
+/// containing NaN, and for `c` that is not positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::robust::*;
+/// let xs = [2.0, 2.1, 2.3, 2.6, 2.8, 3.0, 3.5, 4.1, 9.7, 15.0, 1.2];
+/// let variance = biweight_midvariance(&xs, 9.0).unwrap();
+/// assert!((variance - 0.878865648).abs() < 1e-9);
+/// assert!(variance < stats::sample_variance(&xs).unwrap());
+/// ```
+pub fn biweight_midvariance(nums: &[f64], c: f64) -> Option<f64> {
+    if !is_positive(c) {
+        return None;
+    }
+    let mad = mad(nums)?;
+    let center = median(nums)?;
+    if mad == 0.0 {
+        return Some(0.0);
+    }
+    let (mut spread, mut slope) = (0.0, 0.0);
+    for x in nums {
+        let u = (x - center) / (c * mad);
+        if u.abs() < 1.0 {
+            let v = 1.0 - u * u;
+            spread += (x - center) * (x - center) * v * v * v * v;
+            slope += v * (1.0 - 5.0 * u * u);
+        }
+    }
+    Some(nums.len() as f64 * spread / (slope * slope))
+}
@@ -0,0 +1,692 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Mergeable sketches: compact summaries of a stream of
+//! values that answer approximate queries in bounded memory
+//! and can be combined across threads or machines.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+use core::hash::{Hash, Hasher};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::streaming::Accumulator;
+
+/// Cluster of nearby values in a [`TDigest`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Dunning's t-digest of a stream of values, for estimating
+/// quantiles, especially extreme ones, in bounded memory.
+///
+/// Values are clustered into centroids whose size is limited
+/// by their position: clusters near the median hold many
+/// values, while those in the tails hold few, so that tail
+/// quantiles such as p99 and p999 stay accurate. The
+/// `compression` parameter bounds the number of centroids
+/// (to about `compression / 2` once compressed): larger
+/// values use more memory and give more accurate estimates.
+/// Digests with the same compression can be merged.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::sketches::*;
+/// let mut digest = TDigest::default();
+/// for i in 0..100_000 {
+///     digest.push(f64::from((i * 7919) % 100_000));
+/// }
+/// let p99 = digest.quantile(0.99).unwrap();
+/// let p999 = digest.quantile(0.999).unwrap();
+/// assert!((p99 - 98_999.5).abs() < 50.0);
+/// assert!((p999 - 99_899.5).abs() < 20.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<Centroid>,
+    count: usize,
+    min: f64,
+    max: f64,
+    nan: bool,
+}
+
+impl Default for TDigest {
+    /// Digest with a compression of 100.
+    fn default() -> Self {
+        TDigest::new(100.0).unwrap()
+    }
+}
+
+impl TDigest {
+    /// Empty digest with the given `compression`, or `None`
+    /// if the compression is less than 1.
+    pub fn new(compression: f64) -> Option<TDigest> {
+        if compression.is_nan() || compression < 1.0 {
+            return None;
+        }
+        Some(TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            nan: false,
+        })
+    }
+
+    /// Number of values pushed, including those of merged
+    /// digests.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Add one value.
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        if x.is_nan() {
+            self.nan = true;
+            return;
+        }
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        self.buffer.push(Centroid {
+            mean: x,
+            weight: 1.0,
+        });
+        if self.buffer.len() >= 5 * self.compression as usize {
+            self.compress();
+        }
+    }
+
+    /// Combine with the digest of another set of values.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::sketches::*;
+    /// let mut hosts: Vec<TDigest> = (0..4).map(|_| TDigest::default()).collect();
+    /// for i in 0..40_000 {
+    ///     hosts[i % 4].push(i as f64);
+    /// }
+    /// let mut total = TDigest::default();
+    /// for digest in &hosts {
+    ///     total.merge(digest);
+    /// }
+    /// assert_eq!(40_000, total.count());
+    /// assert!((total.quantile(0.99).unwrap() - 39_599.5).abs() < 40.0);
+    /// ```
+    pub fn merge(&mut self, other: &TDigest) {
+        self.count += other.count;
+        self.nan |= other.nan;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.buffer.extend_from_slice(&other.centroids);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.compress();
+    }
+
+    /// Scale function `k₁` mapping a quantile to the index of
+    /// the centroid covering it; each centroid may span at
+    /// most one unit of `k`.
+    fn k(&self, q: f64) -> f64 {
+        self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+
+    /// Inverse of [`k`](TDigest::k).
+    fn k_inverse(&self, k: f64) -> f64 {
+        let angle = (2.0 * PI * k / self.compression).min(PI / 2.0);
+        (angle.sin() + 1.0) / 2.0
+    }
+
+    /// Merge the buffered values into the centroids.
+    fn compress(&mut self) {
+        //merging t-digest referenced on: Dunning & Ertl, "Computing Extremely Accurate Quantiles Using t-Digests" (2019)
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut all = core::mem::take(&mut self.buffer);
+        all.append(&mut self.centroids);
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = all.iter().map(|c| c.weight).sum();
+
+        let mut merged = Vec::with_capacity(self.compression as usize);
+        let mut current = all[0];
+        let mut before = 0.0;
+        let mut limit = total * self.k_inverse(self.k(0.0) + 1.0);
+        for &c in &all[1..] {
+            if before + current.weight + c.weight <= limit {
+                let weight = current.weight + c.weight;
+                current.mean += (c.mean - current.mean) * c.weight / weight;
+                current.weight = weight;
+            } else {
+                before += current.weight;
+                merged.push(current);
+                limit = total * self.k_inverse(self.k(before / total) + 1.0);
+                current = c;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Estimated quantile `q` of the values. Between centroid
+    /// means, and between the outermost centroids and the
+    /// exact extremes, the estimate is interpolated linearly
+    /// in rank. The quantile is undefined for no values, if
+    /// any value was NaN, or for `q` outside `[0, 1]`.
+    ///
+    /// Values pushed since the digest was last compressed are
+    /// included by compressing a copy.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::sketches::*;
+    /// let mut digest = TDigest::default();
+    /// assert_eq!(None, digest.quantile(0.5));
+    /// digest.push(1.0);
+    /// digest.push(3.0);
+    /// assert_eq!(Some(2.0), digest.quantile(0.5));
+    /// assert_eq!(Some(3.0), digest.quantile(1.0));
+    /// ```
+    pub fn quantile(&self, q: f64) -> Option<f64> {
+        if self.nan || self.count == 0 || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        if !self.buffer.is_empty() {
+            let mut compressed = self.clone();
+            compressed.compress();
+            return compressed.quantile(q);
+        }
+
+        // Each centroid's mean is taken to sit at the middle of
+        // its weight; the extremes sit at ranks 0 and n.
+        let n = self.count as f64;
+        let rank = q * n;
+        let mut previous = (0.0, self.min);
+        let mut before = 0.0;
+        for c in &self.centroids {
+            let center = before + c.weight / 2.0;
+            if rank < center {
+                return Some(interpolate(previous, (center, c.mean), rank));
+            }
+            previous = (center, c.mean);
+            before += c.weight;
+        }
+        Some(interpolate(previous, (n, self.max), rank))
+    }
+}
+
+/// Value at `rank` on the line through two (rank, value)
+/// points.
+fn interpolate((r0, x0): (f64, f64), (r1, x1): (f64, f64), rank: f64) -> f64 {
+    if r1 <= r0 {
+        return x1;
+    }
+    x0 + (x1 - x0) * (rank - r0) / (r1 - r0)
+}
+
+impl Accumulator for TDigest {
+    fn push(&mut self, x: f64) {
+        TDigest::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Extend<f64> for TDigest {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// HDR ("high dynamic range") histogram of non-negative
+/// integer values such as latencies, recording each value to
+/// a fixed number of significant decimal digits.
+///
+/// Buckets are exact for small values and then grow in
+/// proportion to the value, so that every recorded value is
+/// known to within a relative error of `10^-digits` across
+/// the whole range of `u64`. Recording takes constant time;
+/// memory grows with the logarithm of the largest value.
+/// Histograms can be merged, which is exact when their
+/// precisions are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::sketches::*;
+/// let mut latencies = HdrHistogram::new(3).unwrap();
+/// for micros in 1..=10_000 {
+///     latencies.record(micros);
+/// }
+/// latencies.record_n(1_000_000, 10);
+/// assert_eq!(Some(5_007), latencies.value_at_percentile(50.0));
+/// assert_eq!(Some(9_911), latencies.value_at_percentile(99.0));
+/// assert_eq!(Some(1_000_000), latencies.value_at_percentile(100.0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HdrHistogram {
+    digits: u8,
+    /// Number of bits in the exact part of a value.
+    bits: u32,
+    counts: Vec<u64>,
+    total: u64,
+    min: u64,
+    max: u64,
+}
+
+impl HdrHistogram {
+    /// Empty histogram keeping `digits` significant decimal
+    /// digits, or `None` unless `digits` is between 1 and 5.
+    pub fn new(digits: u8) -> Option<HdrHistogram> {
+        if !(1..=5).contains(&digits) {
+            return None;
+        }
+        // Enough bits to count to 2·10^digits.
+        let largest = 2 * 10u64.pow(u32::from(digits));
+        let bits = 64 - (largest - 1).leading_zeros();
+        Some(HdrHistogram {
+            digits,
+            bits,
+            counts: Vec::new(),
+            total: 0,
+            min: u64::MAX,
+            max: 0,
+        })
+    }
+
+    /// Number of significant decimal digits kept.
+    pub fn significant_digits(&self) -> u8 {
+        self.digits
+    }
+
+    /// Number of values below which buckets are exact.
+    fn exact(&self) -> u64 {
+        1 << self.bits
+    }
+
+    /// Index of the bucket holding `value`.
+    fn index(&self, value: u64) -> usize {
+        let exact = self.exact();
+        if value < exact {
+            return value as usize;
+        }
+        let half = exact / 2;
+        let shift = 63 - value.leading_zeros() - (self.bits - 1);
+        (exact + u64::from(shift - 1) * half + ((value >> shift) - half)) as usize
+    }
+
+    /// Smallest and largest values held by bucket `index`.
+    fn bounds(&self, index: usize) -> (u64, u64) {
+        let (exact, index) = (self.exact(), index as u64);
+        if index < exact {
+            return (index, index);
+        }
+        let half = exact / 2;
+        let shift = (index - exact) / half + 1;
+        let low = ((index - exact) % half + half) << shift;
+        (low, low + ((1 << shift) - 1))
+    }
+
+    /// Record one occurrence of `value`.
+    pub fn record(&mut self, value: u64) {
+        self.record_n(value, 1);
+    }
+
+    /// Record `count` occurrences of `value`.
+    pub fn record_n(&mut self, value: u64, count: u64) {
+        if count == 0 {
+            return;
+        }
+        let index = self.index(value);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += count;
+        self.total += count;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    /// Combine with the values recorded in another
+    /// histogram. If `other` has a different precision, each
+    /// of its buckets is recorded as its smallest value.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::sketches::*;
+    /// let (mut a, mut b) = (HdrHistogram::new(2).unwrap(), HdrHistogram::new(2).unwrap());
+    /// a.record(10);
+    /// b.record_n(20, 3);
+    /// a.merge(&b);
+    /// assert_eq!(4, a.count());
+    /// assert_eq!(Some(20), a.value_at_percentile(50.0));
+    /// ```
+    pub fn merge(&mut self, other: &HdrHistogram) {
+        if other.total == 0 {
+            return;
+        }
+        if other.bits != self.bits {
+            for (index, &count) in other.counts.iter().enumerate() {
+                self.record_n(other.bounds(index).0, count);
+            }
+            return;
+        }
+        if other.counts.len() > self.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (count, &more) in self.counts.iter_mut().zip(&other.counts) {
+            *count += more;
+        }
+        self.total += other.total;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    /// Number of values recorded.
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    /// Smallest value recorded.
+    pub fn min(&self) -> Option<u64> {
+        (self.total > 0).then_some(self.min)
+    }
+
+    /// Largest value recorded.
+    pub fn max(&self) -> Option<u64> {
+        (self.total > 0).then_some(self.max)
+    }
+
+    /// Mean of the recorded values, each taken as the middle
+    /// of its bucket.
+    pub fn mean(&self) -> Option<f64> {
+        if self.total == 0 {
+            return None;
+        }
+        let mut sum = crate::Neumaier::new();
+        for (index, &count) in self.counts.iter().enumerate().filter(|p| *p.1 > 0) {
+            let (low, high) = self.bounds(index);
+            sum.add(count as f64 * (low as f64 + high as f64) / 2.0);
+        }
+        Some(sum.total() / self.total as f64)
+    }
+
+    /// Value at percentile `p` (between 0 and 100): the
+    /// largest value equivalent, at this precision, to the
+    /// smallest recorded value with at least `p` percent of
+    /// the values at or below it, and never more than the
+    /// largest value recorded. The percentile is undefined for
+    /// an empty histogram or for `p` outside `[0, 100]`.
+    pub fn value_at_percentile(&self, p: f64) -> Option<u64> {
+        if self.total == 0 || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let target = ((p / 100.0 * self.total as f64).ceil() as u64).max(1);
+        let mut cumulative = 0;
+        for (index, &count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return Some(self.bounds(index).1.min(self.max));
+            }
+        }
+        Some(self.max)
+    }
+}
+
+impl Accumulator for HdrHistogram {
+    /// Record `x` rounded to the nearest integer; negative
+    /// values and NaN are recorded as 0.
+    fn push(&mut self, x: f64) {
+        self.record(x.round() as u64);
+    }
+
+    fn count(&self) -> usize {
+        self.total as usize
+    }
+}
+
+/// 64-bit FNV-1a hash with a SplitMix64 finalizer, so that
+/// every input bit affects every output bit.
+struct MixHasher(u64);
+
+impl Hasher for MixHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        //http://www.isthe.com/chongo/tech/comp/fnv/
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut x = self.0;
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^ (x >> 31)
+    }
+}
+
+/// Flajolet et al.'s HyperLogLog estimator of the number of
+/// distinct items in a stream, using `2^precision` one-byte
+/// registers. The relative standard error of the estimate is
+/// about `1.04 / √(2^precision)`: 0.8% at the default
+/// precision of 14, using 16 KiB.
+///
+/// Any hashable item can be inserted; pushed `f64` values
+/// are counted by value, with `0.0` and `-0.0` the same.
+/// Sketches can be merged, which gives the sketch of the
+/// union of their streams.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::sketches::*;
+/// let mut users = HyperLogLog::default();
+/// for i in 0..1_000_000u32 {
+///     users.insert(&(i % 50_000));
+/// }
+/// let estimate = users.estimate();
+/// assert!((estimate - 50_000.0).abs() < 0.03 * 50_000.0);
+/// ```
+/// ```
+/// # use stats::sketches::*;
+/// let mut words = HyperLogLog::new(10).unwrap();
+/// for w in "the cat saw the other cat".split(' ') {
+///     words.insert(w);
+/// }
+/// assert_eq!(4.0, words.estimate().round());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HyperLogLog {
+    precision: u32,
+    registers: Vec<u8>,
+    inserted: usize,
+}
+
+impl Default for HyperLogLog {
+    /// Sketch with a precision of 14.
+    fn default() -> Self {
+        HyperLogLog::new(14).unwrap()
+    }
+}
+
+impl HyperLogLog {
+    /// Empty sketch with `2^precision` registers, or `None`
+    /// unless `precision` is between 4 and 18.
+    pub fn new(precision: u32) -> Option<HyperLogLog> {
+        if !(4..=18).contains(&precision) {
+            return None;
+        }
+        Some(HyperLogLog {
+            precision,
+            registers: vec![0; 1 << precision],
+            inserted: 0,
+        })
+    }
+
+    /// Precision of the sketch.
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    /// Add an item to the stream.
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        //https://en.wikipedia.org/wiki/HyperLogLog
+        let mut hasher = MixHasher(0xcbf2_9ce4_8422_2325);
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+        self.inserted += 1;
+        let index = (hash >> (64 - self.precision)) as usize;
+        // Position of the first one bit after the index, with
+        // a sentinel bit bounding it.
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// Combine with the sketch of another stream. If `other`
+    /// has a different precision, the result has the lower
+    /// of the two.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::sketches::*;
+    /// let (mut a, mut b) = (HyperLogLog::new(12).unwrap(), HyperLogLog::new(14).unwrap());
+    /// a.extend((0..3000).map(f64::from));
+    /// b.extend((2000..5000).map(f64::from));
+    /// a.merge(&b);
+    /// assert_eq!(12, a.precision());
+    /// assert!((a.estimate() - 5000.0).abs() < 250.0);
+    /// ```
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        if other.precision < self.precision {
+            *self = self.folded(other.precision);
+        }
+        self.inserted += other.inserted;
+        let other = other.folded(self.precision);
+        for (r, &o) in self.registers.iter_mut().zip(&other.registers) {
+            *r = (*r).max(o);
+        }
+    }
+
+    /// This sketch reduced to a lower `precision`: each
+    /// register moves to the index given by the leading bits
+    /// of its own, and the bits dropped from the index count
+    /// towards its rank.
+    fn folded(&self, precision: u32) -> HyperLogLog {
+        let mut folded = HyperLogLog::new(precision).unwrap();
+        folded.inserted = self.inserted;
+        let dropped = self.precision - precision;
+        for (i, &r) in self.registers.iter().enumerate() {
+            if r == 0 {
+                continue;
+            }
+            let extra = i & ((1 << dropped) - 1);
+            let rank = if extra == 0 {
+                dropped as u8 + r
+            } else {
+                (extra.leading_zeros() - (usize::BITS - dropped)) as u8 + 1
+            };
+            let j = i >> dropped;
+            folded.registers[j] = folded.registers[j].max(rank);
+        }
+        folded
+    }
+
+    /// Estimated number of distinct items inserted, by
+    /// Ertl's improved estimator, which needs no empirical
+    /// bias correction and is accurate for small and large
+    /// counts alike.
+    pub fn estimate(&self) -> f64 {
+        //estimator referenced on: Ertl, "New cardinality estimation algorithms for HyperLogLog sketches" (2017)
+        let q = 64 - self.precision as usize;
+        let mut histogram = vec![0usize; q + 2];
+        for &r in &self.registers {
+            histogram[usize::from(r)] += 1;
+        }
+        let m = self.registers.len() as f64;
+        let mut z = m * tau(1.0 - histogram[q + 1] as f64 / m);
+        for &c in histogram[1..=q].iter().rev() {
+            z = 0.5 * (z + c as f64);
+        }
+        z += m * sigma(histogram[0] as f64 / m);
+        m * m / (2.0 * core::f64::consts::LN_2) / z
+    }
+}
+
+/// Ertl's `σ(x) = x + Σ_k x^(2^k) 2^(k-1)`, for the registers
+/// still zero.
+fn sigma(mut x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+    let (mut y, mut z) = (1.0, x);
+    loop {
+        x *= x;
+        let previous = z;
+        z += x * y;
+        y += y;
+        if z == previous {
+            return z;
+        }
+    }
+}
+
+/// Ertl's `τ(x) = (1 - x - Σ_k (1 - x^(2^-k))² 2^-k) / 3`,
+/// for the registers at the largest possible rank.
+fn tau(mut x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+    let (mut y, mut z) = (1.0, 1.0 - x);
+    loop {
+        x = x.sqrt();
+        let previous = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z == previous {
+            return z / 3.0;
+        }
+    }
+}
+
+impl Accumulator for HyperLogLog {
+    fn push(&mut self, x: f64) {
+        // Adding 0.0 turns -0.0 into 0.0.
+        self.insert(&(x + 0.0).to_bits());
+    }
+
+    fn count(&self) -> usize {
+        self.inserted
+    }
+}
+
+impl Extend<f64> for HyperLogLog {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            Accumulator::push(self, x);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Moving averages, which smooth a series of values into a
+//! series of the same length.
+//!
+//! Each output value depends only on the input values up to
+//! and including the same position, so the smoothed series
+//! lags the input. Until a moving average has seen a full
+//! window, its warm-up values are the same average taken over
+//! the values so far, as for the
+//! [`streaming`](crate::streaming) accumulators; the first
+//! output value is always the first input value.
+
+use alloc::vec::Vec;
+
+use crate::dot;
+use crate::streaming::{Ewma, RollingStats};
+
+/// Simple moving average of input values: the mean of the
+/// last `window` values at each position. `None` for a
+/// `window` of zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::smoothing::*;
+/// let smoothed = sma(&[1.0, 2.0, 6.0, 4.0, 8.0], 3).unwrap();
+/// assert_eq!(vec![1.0, 1.5, 3.0, 4.0, 6.0], smoothed);
+/// assert_eq!(None, sma(&[1.0], 0));
+/// ```
+pub fn sma(nums: &[f64], window: usize) -> Option<Vec<f64>> {
+    let mut rolling = RollingStats::with_window(window)?;
+    let smoothed = nums
+        .iter()
+        .map(|&x| {
+            rolling.push(x);
+            rolling.mean().unwrap()
+        })
+        .collect();
+    Some(smoothed)
+}
+
+/// Weighted moving average of input values: the mean of the
+/// last `weights.len()` values weighted by `weights`, the
+/// last weight applying to the newest value. The weights are
+/// normalized, so only their proportions matter; during the
+/// warm-up the trailing weights are normalized over the
+/// values so far. `None` for no weights, for weights that
+/// are negative or NaN, and unless the last weight is
+/// positive.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::smoothing::*;
+/// // The usual linearly increasing weights.
+/// let smoothed = wma(&[1.0, 2.0, 6.0, 4.0], &[1.0, 2.0, 3.0]).unwrap();
+/// assert_eq!(1.0, smoothed[0]);
+/// assert!((smoothed[1] - (2.0 * 1.0 + 3.0 * 2.0) / 5.0).abs() < 1e-15);
+/// assert!((smoothed[3] - (2.0 + 2.0 * 6.0 + 3.0 * 4.0) / 6.0).abs() < 1e-15);
+/// ```
+pub fn wma(nums: &[f64], weights: &[f64]) -> Option<Vec<f64>> {
+    if weights.is_empty() || !weights.iter().all(|&w| w >= 0.0) {
+        return None;
+    }
+    let w = weights.len();
+    //which also keeps the warm-up weights from summing to zero
+    if weights[w - 1] <= 0.0 {
+        return None;
+    }
+    let smoothed = (0..nums.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(w);
+            let values = &nums[start..=i];
+            let weights = &weights[w - values.len()..];
+            dot(weights, values).unwrap() / weights.iter().sum::<f64>()
+        })
+        .collect();
+    Some(smoothed)
+}
+
+/// Exponential moving average of input values: each value
+/// has weight `alpha` and scales the weight of those before
+/// it by `1 - alpha`, as for [`Ewma`]. `None` unless `0 <
+/// alpha <= 1`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::smoothing::*;
+/// assert_eq!(Some(vec![10.0, 15.0, 17.5]), ema(&[10.0, 20.0, 20.0], 0.5));
+/// ```
+pub fn ema(nums: &[f64], alpha: f64) -> Option<Vec<f64>> {
+    let mut ewma = Ewma::new(alpha)?;
+    let smoothed = nums
+        .iter()
+        .map(|&x| {
+            ewma.update(x);
+            ewma.value().unwrap()
+        })
+        .collect();
+    Some(smoothed)
+}
@@ -0,0 +1,365 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistical process control: control charts, which
+//! signal when a process drifts from its usual variation.
+//!
+//! Shewhart's X-bar and R chart sets limits from subgroups
+//! of measurements taken while the process is in control,
+//! against which later subgroups are judged. The CUSUM and
+//! EWMA charts accumulate evidence over successive
+//! measurements, against a target and standard deviation
+//! known in advance, and so detect small sustained shifts
+//! sooner.
+
+use alloc::vec::Vec;
+use core::f64::consts::FRAC_2_SQRT_PI;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::has_nan;
+
+/// Center line and control limits of a control chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlLimits {
+    /// The lower control limit.
+    pub lower: f64,
+    /// The center line.
+    pub center: f64,
+    /// The upper control limit.
+    pub upper: f64,
+}
+
+impl ControlLimits {
+    /// True if `x` is within the control limits.
+    pub fn contains(&self, x: f64) -> bool {
+        (self.lower..=self.upper).contains(&x)
+    }
+}
+
+/// Expected range `d2` and standard deviation of the range
+/// `d3` of `n` standard normal values, for `n` from 2 to 25,
+/// computed by numerical integration.
+const RANGE_CONSTANTS: [(f64, f64); 24] = [
+    (FRAC_2_SQRT_PI, 0.852_502_466_4),
+    (1.692_568_750_6, 0.888_368_004_0),
+    (2.058_750_746_0, 0.879_808_202_8),
+    (2.325_928_947_3, 0.864_081_941_1),
+    (2.534_412_721_2, 0.848_039_686_1),
+    (2.704_356_751_2, 0.833_205_335_6),
+    (2.847_200_612_1, 0.819_831_489_8),
+    (2.970_026_324_4, 0.807_834_274_6),
+    (3.077_505_461_7, 0.797_050_673_5),
+    (3.172_872_703_8, 0.787_314_620_6),
+    (3.258_455_279_7, 0.778_478_341_2),
+    (3.335_980_354_1, 0.770_416_202_1),
+    (3.406_763_108_2, 0.763_023_095_6),
+    (3.471_826_889_9, 0.756_211_429_7),
+    (3.531_982_786_1, 0.749_908_089_4),
+    (3.587_883_961_8, 0.744_051_784_0),
+    (3.640_063_757_9, 0.738_590_853_4),
+    (3.688_963_023_2, 0.733_481_495_5),
+    (3.734_950_119_6, 0.728_686_345_7),
+    (3.778_335_829_8, 0.724_173_340_7),
+    (3.819_384_643_4, 0.719_914_808_4),
+    (3.858_323_423_3, 0.715_886_735_5),
+    (3.895_348_148_5, 0.712_068_175_1),
+    (3.930_629_219_5, 0.708_440_765_9),
+];
+
+/// Shewhart's X-bar and R control charts of subgrouped
+/// measurements, as computed by [`xbar_r_chart`]: one
+/// charts the subgroup means, the other the subgroup
+/// ranges.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XbarRChart {
+    xbar: ControlLimits,
+    range: ControlLimits,
+    means: Vec<f64>,
+    ranges: Vec<f64>,
+}
+
+impl XbarRChart {
+    /// Limits of the X-bar chart: the grand mean, plus and
+    /// minus `A2` times the mean range.
+    pub fn xbar(&self) -> ControlLimits {
+        self.xbar
+    }
+
+    /// Limits of the R chart: the mean range, times `D3`
+    /// and `D4`.
+    pub fn range(&self) -> ControlLimits {
+        self.range
+    }
+
+    /// The mean of each subgroup.
+    pub fn means(&self) -> &[f64] {
+        &self.means
+    }
+
+    /// The range of each subgroup.
+    pub fn ranges(&self) -> &[f64] {
+        &self.ranges
+    }
+
+    /// Positions of the subgroups whose mean or range is
+    /// outside the control limits, in increasing order.
+    pub fn out_of_control(&self) -> Vec<usize> {
+        (0..self.means.len())
+            .filter(|&i| !self.xbar.contains(self.means[i]) || !self.range.contains(self.ranges[i]))
+            .collect()
+    }
+}
+
+/// X-bar and R charts of `subgroups` of measurements,
+/// each of the same size from 2 to 25. The limits are three
+/// standard errors from the center lines, with the standard
+/// deviation of the process estimated as the mean range over
+/// `d2`, the expected range of that many normal values.
+/// Undefined for no subgroups, for subgroups of different or
+/// unsupported sizes, and for values containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::spc::*;
+/// let subgroups = [
+///     [10.2, 9.9, 10.1, 10.0],
+///     [10.1, 10.3, 9.8, 10.0],
+///     [9.9, 10.0, 10.2, 10.1],
+///     [10.0, 10.1, 9.9, 10.2],
+///     [10.9, 11.2, 11.0, 10.8],
+/// ];
+/// let chart = xbar_r_chart(&subgroups).unwrap();
+/// let limits = chart.xbar();
+/// assert!((limits.center - 10.235).abs() < 1e-12);
+/// assert!((limits.upper - (10.235 + 0.729 * 0.36)).abs() < 1e-3);
+/// assert!((chart.range().upper - 2.282 * 0.36).abs() < 1e-3);
+/// assert_eq!(vec![4], chart.out_of_control());
+/// ```
+pub fn xbar_r_chart<G: AsRef<[f64]>>(subgroups: &[G]) -> Option<XbarRChart> {
+    let size = subgroups.first()?.as_ref().len();
+    if !(2..=RANGE_CONSTANTS.len() + 1).contains(&size) {
+        return None;
+    }
+    let mut means = Vec::with_capacity(subgroups.len());
+    let mut ranges = Vec::with_capacity(subgroups.len());
+    for g in subgroups {
+        let g = g.as_ref();
+        if g.len() != size || has_nan(g) {
+            return None;
+        }
+        let (low, high) = g
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(l, h), &x| {
+                (l.min(x), h.max(x))
+            });
+        means.push(g.iter().sum::<f64>() / size as f64);
+        ranges.push(high - low);
+    }
+    let k = subgroups.len() as f64;
+    let grand_mean = means.iter().sum::<f64>() / k;
+    let mean_range = ranges.iter().sum::<f64>() / k;
+    let (d2, d3) = RANGE_CONSTANTS[size - 2];
+    let margin = 3.0 * mean_range / (d2 * (size as f64).sqrt());
+    Some(XbarRChart {
+        xbar: ControlLimits {
+            lower: grand_mean - margin,
+            center: grand_mean,
+            upper: grand_mean + margin,
+        },
+        range: ControlLimits {
+            lower: (mean_range * (1.0 - 3.0 * d3 / d2)).max(0.0),
+            center: mean_range,
+            upper: mean_range * (1.0 + 3.0 * d3 / d2),
+        },
+        means,
+        ranges,
+    })
+}
+
+/// Tabular CUSUM chart: the cumulative sums of the
+/// standardized deviations of measurements above and below
+/// a `target`, less an allowance of `k` standard deviations
+/// per measurement, each floored at zero. It signals when
+/// either sum exceeds `h` standard deviations. The usual
+/// choice, `k = 0.5` and `h = 4` or `5`, quickly detects
+/// shifts of one standard deviation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::spc::*;
+/// let mut chart = Cusum::new(10.0, 1.0, 0.5, 4.0).unwrap();
+/// let signals: Vec<bool> = [10.5, 9.2, 10.1, 11.5, 11.8, 11.2, 11.6]
+///     .iter()
+///     .map(|&x| chart.push(x))
+///     .collect();
+/// assert_eq!(vec![false, false, false, false, false, false, true], signals);
+/// assert!((chart.upper() - 4.1).abs() < 1e-12);
+/// assert_eq!(0.0, chart.lower());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cusum {
+    target: f64,
+    sigma: f64,
+    k: f64,
+    h: f64,
+    upper: f64,
+    lower: f64,
+}
+
+impl Cusum {
+    /// CUSUM chart for measurements with the given `target`
+    /// mean and standard deviation `sigma` while in control.
+    /// `None` unless `target` is finite, `sigma` and `h`
+    /// finite and positive, and `k` finite and not negative.
+    pub fn new(target: f64, sigma: f64, k: f64, h: f64) -> Option<Cusum> {
+        let positive = |x: f64| x.is_finite() && x > 0.0;
+        if !target.is_finite() || !positive(sigma) || !positive(h) {
+            return None;
+        }
+        if !k.is_finite() || k < 0.0 {
+            return None;
+        }
+        Some(Cusum {
+            target,
+            sigma,
+            k,
+            h,
+            upper: 0.0,
+            lower: 0.0,
+        })
+    }
+
+    /// Add a measurement, returning true if the chart
+    /// signals that the process is out of control.
+    pub fn push(&mut self, x: f64) -> bool {
+        let z = (x - self.target) / self.sigma;
+        self.upper = (self.upper + z - self.k).max(0.0);
+        self.lower = (self.lower - z - self.k).max(0.0);
+        self.is_signaling()
+    }
+
+    /// True if either sum exceeds the decision interval `h`.
+    pub fn is_signaling(&self) -> bool {
+        self.upper > self.h || self.lower > self.h
+    }
+
+    /// The sum of upward deviations, in standard deviations.
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// The sum of downward deviations, in standard
+    /// deviations.
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Restart both sums from zero, as once the cause of a
+    /// signal has been dealt with.
+    pub fn reset(&mut self) {
+        self.upper = 0.0;
+        self.lower = 0.0;
+    }
+}
+
+/// EWMA control chart: the exponentially weighted moving
+/// average of measurements, starting from a `target`, with
+/// weight `lambda` on each new measurement. It signals when
+/// the average leaves the limits `target ± L σ √(λ / (2 -
+/// λ) (1 - (1 - λ)^(2t)))` after `t` measurements, which
+/// widen to their steady state as the average comes to
+/// depend on more measurements. The usual choice, `lambda`
+/// from 0.05 to 0.25 with `L` about 3, detects small shifts.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::spc::*;
+/// let mut chart = EwmaChart::new(10.0, 1.0, 0.2, 3.0).unwrap();
+/// assert!(!chart.push(10.5));
+/// assert!((chart.value() - 10.1).abs() < 1e-12);
+/// let limits = chart.limits();
+/// assert!((limits.upper - 10.6).abs() < 1e-12);
+/// let signals: Vec<bool> = [11.0, 11.5, 11.2, 11.8, 11.4].iter().map(|&x| chart.push(x)).collect();
+/// assert_eq!(vec![false, false, false, false, true], signals);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EwmaChart {
+    target: f64,
+    sigma: f64,
+    lambda: f64,
+    width: f64,
+    value: f64,
+    //(1 - λ)^(2t)
+    decay: f64,
+}
+
+impl EwmaChart {
+    /// EWMA chart for measurements with the given `target`
+    /// mean and standard deviation `sigma` while in control,
+    /// with limits `width` (`L`) standard errors wide. `None`
+    /// unless `target` is finite, `sigma` and `width` finite
+    /// and positive, and `0 < lambda <= 1`.
+    pub fn new(target: f64, sigma: f64, lambda: f64, width: f64) -> Option<EwmaChart> {
+        let positive = |x: f64| x.is_finite() && x > 0.0;
+        if !target.is_finite() || !positive(sigma) || !positive(width) {
+            return None;
+        }
+        if !(lambda > 0.0 && lambda <= 1.0) {
+            return None;
+        }
+        Some(EwmaChart {
+            target,
+            sigma,
+            lambda,
+            width,
+            value: target,
+            decay: 1.0,
+        })
+    }
+
+    /// Add a measurement, returning true if the chart
+    /// signals that the process is out of control.
+    pub fn push(&mut self, x: f64) -> bool {
+        self.value += self.lambda * (x - self.value);
+        self.decay *= (1.0 - self.lambda) * (1.0 - self.lambda);
+        self.is_signaling()
+    }
+
+    /// True if the average is outside the current limits.
+    pub fn is_signaling(&self) -> bool {
+        !self.limits().contains(self.value)
+    }
+
+    /// The current moving average.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The current control limits, about the target.
+    pub fn limits(&self) -> ControlLimits {
+        let spread = self.lambda / (2.0 - self.lambda) * (1.0 - self.decay);
+        let margin = self.width * self.sigma * spread.sqrt();
+        ControlLimits {
+            lower: self.target - margin,
+            center: self.target,
+            upper: self.target + margin,
+        }
+    }
+
+    /// Restart the average from the target.
+    pub fn reset(&mut self) {
+        self.value = self.target;
+        self.decay = 1.0;
+    }
+}
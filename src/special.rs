@@ -0,0 +1,574 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Special functions behind the probability distributions
+//! and the confidence intervals and tests built on them.
+//! Each is accurate to around twelve significant digits for
+//! parameters up to the hundreds of thousands, degrading
+//! slowly beyond.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Natural logarithm of the gamma function of `x > 0`.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    //Lanczos approximation, g = 7: https://en.wikipedia.org/wiki/Lanczos_approximation
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        //reflection: Γ(x)Γ(1-x) = π / sin(πx)
+        let pi = core::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Remainder `ln Γ(x) - ((x - 1/2) ln x - x + ln √(2π))`
+/// of Stirling's series, for `x >= 10`.
+fn stirling_remainder(x: f64) -> f64 {
+    //Bernoulli-number terms B₂ₙ / (2n (2n-1) x^(2n-1))
+    const TERMS: [f64; 5] = [
+        1.0 / 12.0,
+        -1.0 / 360.0,
+        1.0 / 1260.0,
+        -1.0 / 1680.0,
+        1.0 / 1188.0,
+    ];
+    let x2 = 1.0 / (x * x);
+    let mut sum = 0.0;
+    for &term in TERMS.iter().rev() {
+        sum = sum * x2 + term;
+    }
+    sum / x
+}
+
+/// Natural logarithm of the beta function of `a, b > 0`.
+/// Computing this directly from [`ln_gamma`] would cancel
+/// most of the digits when either argument is large.
+pub(crate) fn ln_beta(a: f64, b: f64) -> f64 {
+    let (a, b) = if a < b { (a, b) } else { (b, a) };
+    if b < 10.0 {
+        return ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+    }
+    let correction = stirling_remainder(b) - stirling_remainder(a + b);
+    if a < 10.0 {
+        //ln Γ(a+b) - ln Γ(b) by Stirling's series in b
+        ln_gamma(a) + correction - (b - 0.5) * (a / b).ln_1p() - a * (a + b).ln() + a
+    } else {
+        let c = a + b;
+        0.5 * (2.0 * core::f64::consts::PI).ln() - 0.5 * b.ln() + (a - 0.5) * (a / c).ln()
+            - b * (a / b).ln_1p()
+            + stirling_remainder(a)
+            + correction
+    }
+}
+
+/// Continued fraction for the regularized incomplete beta
+/// function, evaluated by the modified Lentz method.
+fn beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    //Numerical Recipes, 3rd ed., §6.4
+    const TINY: f64 = 1e-300;
+    let clamp = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let (qab, qap, qam) = (a + b, a + 1.0, a - 1.0);
+    let mut c = 1.0;
+    let mut d = 1.0 / clamp(1.0 - qab * x / qap);
+    let mut h = d;
+    for m in 1..10_000 {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1.0 / clamp(1.0 + aa * d);
+        c = clamp(1.0 + aa / c);
+        h *= d * c;
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1.0 / clamp(1.0 + aa * d);
+        c = clamp(1.0 + aa / c);
+        let step = d * c;
+        h *= step;
+        if (step - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h
+}
+
+/// Regularized incomplete beta function `I_x(a, b)` for
+/// `a, b > 0` and `0 <= x <= 1`.
+pub(crate) fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    beta_inc_split(a, b, x, 1.0 - x)
+}
+
+/// [`beta_inc`] of `x`, where `y = 1 - x` is given
+/// separately so that no digits of it are lost when `x` is
+/// near 1.
+fn beta_inc_split(a: f64, b: f64, x: f64, y: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if y <= 0.0 {
+        return 1.0;
+    }
+    let (ln_x, ln_y) = if x < 0.5 {
+        (x.ln(), (-x).ln_1p())
+    } else {
+        ((-y).ln_1p(), y.ln())
+    };
+    let front = (a * ln_x + b * ln_y - ln_beta(a, b)).exp();
+    //the fraction converges quickly on this side of the mean
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_fraction(b, a, y) / b
+    }
+}
+
+/// Inverse of the regularized incomplete beta function: the
+/// `x` with `I_x(a, b) = p`, for `a, b > 0`.
+pub(crate) fn beta_inc_inv(a: f64, b: f64, p: f64) -> f64 {
+    //Numerical Recipes, 3rd ed., §6.14: an initial guess refined by Halley's method
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return 1.0;
+    }
+    let (a1, b1) = (a - 1.0, b - 1.0);
+    let mut x = if a >= 1.0 && b >= 1.0 {
+        let pp = if p < 0.5 { p } else { 1.0 - p };
+        let t = (-2.0 * pp.ln()).sqrt();
+        let mut x = (2.30753 + t * 0.27061) / (1.0 + t * (0.99229 + t * 0.04481)) - t;
+        if p < 0.5 {
+            x = -x;
+        }
+        let al = (x * x - 3.0) / 6.0;
+        let h = 2.0 / (1.0 / (2.0 * a - 1.0) + 1.0 / (2.0 * b - 1.0));
+        let w = x * (al + h).sqrt() / h
+            - (1.0 / (2.0 * b - 1.0) - 1.0 / (2.0 * a - 1.0)) * (al + 5.0 / 6.0 - 2.0 / (3.0 * h));
+        a / (a + b * (2.0 * w).exp())
+    } else {
+        let (lna, lnb) = ((a / (a + b)).ln(), (b / (a + b)).ln());
+        let t = (a * lna).exp() / a;
+        let u = (b * lnb).exp() / b;
+        let w = t + u;
+        if p < t / w {
+            (a * w * p).powf(1.0 / a)
+        } else {
+            1.0 - (b * w * (1.0 - p)).powf(1.0 / b)
+        }
+    };
+    let afac = -ln_beta(a, b);
+    for i in 0..20 {
+        if x == 0.0 || x == 1.0 {
+            return x;
+        }
+        let err = beta_inc(a, b, x) - p;
+        let density = (a1 * x.ln() + b1 * (-x).ln_1p() + afac).exp();
+        let u = err / density;
+        let step = u / (1.0 - 0.5 * (u * (a1 / x - b1 / (1.0 - x))).min(1.0));
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if x >= 1.0 {
+            x = 0.5 * (x + step + 1.0);
+        }
+        if step.abs() < 1e-13 * x && i > 0 {
+            break;
+        }
+    }
+    x
+}
+
+/// Cumulative distribution function of Student's t
+/// distribution with `df > 0` degrees of freedom.
+pub(crate) fn student_t_cdf(t: f64, df: f64) -> f64 {
+    //the tail is I_x(df/2, 1/2) / 2 for x = df / (df + t²)
+    let (x, y) = (df / (df + t * t), t * t / (df + t * t));
+    let tail = 0.5 * beta_inc_split(0.5 * df, 0.5, x, y);
+    if t > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Density of Student's t distribution with `df > 0`
+/// degrees of freedom.
+pub(crate) fn student_t_pdf(t: f64, df: f64) -> f64 {
+    //Γ((df+1)/2) / (Γ(df/2) √(df π)) = 1 / (B(df/2, 1/2) √df)
+    let ln_norm = -ln_beta(0.5 * df, 0.5) - 0.5 * df.ln();
+    (ln_norm - 0.5 * (df + 1.0) * (t * t / df).ln_1p()).exp()
+}
+
+/// Quantile function of Student's t distribution with
+/// `df > 0` degrees of freedom, for `0 < p < 1`.
+pub(crate) fn student_t_quantile(p: f64, df: f64) -> f64 {
+    let tail = if p < 0.5 { p } else { 1.0 - p };
+    let x = beta_inc_inv(0.5 * df, 0.5, 2.0 * tail);
+    let mut t = -(df * (1.0 - x) / x).sqrt();
+    //x near 1 has lost the low digits of 1 - x; recover them
+    //by Newton's method on the lower tail
+    if x > 0.5 {
+        for _ in 0..4 {
+            let step = (student_t_cdf(t, df) - tail) / student_t_pdf(t, df);
+            t -= step;
+            if step.abs() <= 1e-15 * t.abs() {
+                break;
+            }
+        }
+    }
+    if p < 0.5 {
+        t
+    } else {
+        -t
+    }
+}
+
+/// Cumulative distribution function of the F distribution
+/// with `d1` and `d2 > 0` degrees of freedom, at `f >= 0`.
+pub(crate) fn f_cdf(f: f64, d1: f64, d2: f64) -> f64 {
+    //I_x(d1/2, d2/2) for x = d1 f / (d1 f + d2)
+    let (x, y) = (d1 * f / (d1 * f + d2), d2 / (d1 * f + d2));
+    beta_inc_split(0.5 * d1, 0.5 * d2, x, y)
+}
+
+/// Upper tail probability of the F distribution with `d1`
+/// and `d2 > 0` degrees of freedom, at `f >= 0`.
+pub(crate) fn f_upper_tail(f: f64, d1: f64, d2: f64) -> f64 {
+    //I_x(d2/2, d1/2) for x = d2 / (d2 + d1 f)
+    let (x, y) = (d2 / (d2 + d1 * f), d1 * f / (d2 + d1 * f));
+    beta_inc_split(0.5 * d2, 0.5 * d1, x, y)
+}
+
+/// Evaluate the polynomial with coefficients `c`, constant
+/// term first, at `x`.
+fn polynomial(c: &[f64], x: f64) -> f64 {
+    c.iter().rev().fold(0.0, |acc, &c| acc * x + c)
+}
+
+/// Quantile function of the standard normal distribution,
+/// for `0 < p < 1`.
+//the coefficients are given as published
+#[allow(clippy::excessive_precision)]
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    //Wichura's AS 241 (PPND16): https://doi.org/10.2307/2347330
+    const A: [f64; 8] = [
+        3.387_132_872_796_366_608,
+        133.141_667_891_784_377_45,
+        1_971.590_950_306_551_442_7,
+        13_731.693_765_509_461_125,
+        45_921.953_931_549_871_457,
+        67_265.770_927_008_700_853,
+        33_430.575_583_588_128_105,
+        2_509.080_928_730_122_672_7,
+    ];
+    const B: [f64; 8] = [
+        1.0,
+        42.313_330_701_600_911_252,
+        687.187_007_492_057_908_3,
+        5_394.196_021_424_751_107_7,
+        21_213.794_301_586_595_867,
+        39_307.895_800_092_710_61,
+        28_729.085_735_721_942_674,
+        5_226.495_278_852_854_561,
+    ];
+    const C: [f64; 8] = [
+        1.423_437_110_749_683_577_34,
+        4.630_337_846_156_545_295_9,
+        5.769_497_221_460_691_405_5,
+        3.647_848_324_763_204_605_04,
+        1.270_458_252_452_368_382_58,
+        0.241_780_725_177_450_611_77,
+        0.022_723_844_989_269_184_583_3,
+        7.745_450_142_783_414_076_4e-4,
+    ];
+    const D: [f64; 8] = [
+        1.0,
+        2.053_191_626_637_758_821_87,
+        1.676_384_830_183_803_849_4,
+        0.689_767_334_985_100_004_55,
+        0.148_103_976_427_480_074_59,
+        0.015_198_666_563_616_457_196_6,
+        5.475_938_084_995_344_946e-4,
+        1.050_750_071_644_416_843_24e-9,
+    ];
+    const E: [f64; 8] = [
+        6.657_904_643_501_103_777_2,
+        5.463_784_911_164_114_369_9,
+        1.784_826_539_917_291_335_8,
+        0.296_560_571_828_504_891_23,
+        0.026_532_189_526_576_123_093,
+        0.001_242_660_947_388_078_438_6,
+        2.711_555_568_743_487_578_15e-5,
+        2.010_334_399_292_288_132_65e-7,
+    ];
+    const F: [f64; 8] = [
+        1.0,
+        0.599_832_206_555_887_937_69,
+        0.136_929_880_922_735_805_31,
+        0.014_875_361_290_850_614_852_5,
+        7.868_691_311_456_132_591e-4,
+        1.846_318_317_510_054_681_8e-5,
+        1.421_511_758_316_445_888_7e-7,
+        2.044_263_103_389_939_785_64e-15,
+    ];
+    let q = p - 0.5;
+    if q.abs() <= 0.425 {
+        let r = 0.180_625 - q * q;
+        return q * polynomial(&A, r) / polynomial(&B, r);
+    }
+    let r = (-(if q < 0.0 { p } else { 1.0 - p }).ln()).sqrt();
+    let x = if r <= 5.0 {
+        let r = r - 1.6;
+        polynomial(&C, r) / polynomial(&D, r)
+    } else {
+        let r = r - 5.0;
+        polynomial(&E, r) / polynomial(&F, r)
+    };
+    if q < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Factor `xᵃ e⁻ˣ / Γ(a)` common to both incomplete gamma
+/// functions.
+fn gamma_front(a: f64, x: f64) -> f64 {
+    if a < 10.0 {
+        return (a * x.ln() - x - ln_gamma(a)).exp();
+    }
+    //with Stirling's series for ln Γ(a), the large terms of
+    //a ln x - x - ln Γ(a) cancel to -a (t - ln(1 + t)) for
+    //t = (x - a) / a
+    let t = (x - a) / a;
+    let ln_front = -a * (t - t.ln_1p()) + 0.5 * (a / (2.0 * core::f64::consts::PI)).ln()
+        - stirling_remainder(a);
+    ln_front.exp()
+}
+
+/// Series for the regularized lower incomplete gamma
+/// function, converging quickly for `x < a + 1`.
+fn gamma_series(a: f64, x: f64) -> f64 {
+    //Numerical Recipes, 3rd ed., §6.2
+    let mut term = 1.0 / a;
+    let mut sum = term;
+    for n in 1..10_000 {
+        term *= x / (a + n as f64);
+        sum += term;
+        if term.abs() < 1e-16 * sum.abs() {
+            break;
+        }
+    }
+    sum * gamma_front(a, x)
+}
+
+/// Continued fraction for the regularized upper incomplete
+/// gamma function, converging quickly for `x >= a + 1`, by
+/// the modified Lentz method.
+fn gamma_fraction(a: f64, x: f64) -> f64 {
+    //Numerical Recipes, 3rd ed., §6.2
+    const TINY: f64 = 1e-300;
+    let clamp = |v: f64| if v.abs() < TINY { TINY } else { v };
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / clamp(b);
+    let mut h = d;
+    for i in 1..10_000 {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.0;
+        d = 1.0 / clamp(an * d + b);
+        c = clamp(b + an / c);
+        let step = d * c;
+        h *= step;
+        if (step - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h * gamma_front(a, x)
+}
+
+/// Regularized lower incomplete gamma function `P(a, x)` for
+/// `a > 0` and `x >= 0`.
+pub(crate) fn gamma_inc(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_fraction(a, x)
+    }
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x)`
+/// for `a > 0` and `x >= 0`, computed directly so that
+/// small upper tails keep their digits.
+pub(crate) fn gamma_inc_upper(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_fraction(a, x)
+    }
+}
+
+/// Inverse of the regularized lower incomplete gamma
+/// function: the `x` with `P(a, x) = p`, for `a > 0`.
+pub(crate) fn gamma_inc_inv(a: f64, p: f64) -> f64 {
+    //Numerical Recipes, 3rd ed., §6.2.1: an initial guess refined by Halley's method
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+    let a1 = a - 1.0;
+    let mut x = if a > 1.0 {
+        let pp = if p < 0.5 { p } else { 1.0 - p };
+        let t = (-2.0 * pp.ln()).sqrt();
+        let mut z = (2.30753 + t * 0.27061) / (1.0 + t * (0.99229 + t * 0.04481)) - t;
+        if p < 0.5 {
+            z = -z;
+        }
+        //Wilson and Hilferty
+        (a * (1.0 - 1.0 / (9.0 * a) - z / (3.0 * a.sqrt())).powi(3)).max(1e-3)
+    } else {
+        let t = 1.0 - a * (0.253 + a * 0.12);
+        if p < t {
+            (p / t).powf(1.0 / a)
+        } else {
+            1.0 - (-(p - t) / (1.0 - t)).ln_1p()
+        }
+    };
+    for i in 0..20 {
+        if x <= 0.0 {
+            return 0.0;
+        }
+        //work in whichever tail keeps the digits of p
+        let err = if p < 0.5 {
+            gamma_inc(a, x) - p
+        } else {
+            (1.0 - p) - gamma_inc_upper(a, x)
+        };
+        let density = gamma_front(a, x) / x;
+        let u = err / density;
+        let step = u / (1.0 - 0.5 * (u * (a1 / x - 1.0)).min(1.0));
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * (x + step);
+        }
+        if step.abs() < 1e-14 * x && i > 0 {
+            break;
+        }
+    }
+    x
+}
+
+/// Cumulative distribution function of the standard normal
+/// distribution.
+pub(crate) fn normal_cdf(z: f64) -> f64 {
+    //Φ(z) = Q(1/2, z²/2) / 2 for z <= 0
+    let tail = 0.5 * gamma_inc_upper(0.5, 0.5 * z * z);
+    if z > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// Cumulative distribution function of the noncentral t
+/// distribution with `df > 0` degrees of freedom and
+/// noncentrality `delta`: the distribution of `(Z + delta) /
+/// √(V / df)` for standard normal `Z` and chi-square `V` on
+/// `df` degrees of freedom.
+pub(crate) fn noncentral_t_cdf(t: f64, df: f64, delta: f64) -> f64 {
+    if t < 0.0 {
+        return 1.0 - noncentral_t_cdf(-t, df, -delta);
+    }
+    if delta.abs() > 37.0 {
+        //the Poisson weights below underflow; Abramowitz and
+        //Stegun 26.7.10 is accurate this far out
+        let z = (t * (1.0 - 0.25 / df) - delta) / (1.0 + 0.5 * t * t / df).sqrt();
+        return normal_cdf(z);
+    }
+    //Lenth, Algorithm AS 243, Applied Statistics 38 (1989)
+    let x = t * t / (t * t + df);
+    let mut total = normal_cdf(-delta);
+    if x <= 0.0 {
+        return total;
+    }
+    let lambda = delta * delta;
+    let mut p = 0.5 * (-0.5 * lambda).exp();
+    let mut q = (2.0 / core::f64::consts::PI).sqrt() * p * delta;
+    let mut s = 0.5 - p;
+    let (mut a, b) = (0.5, 0.5 * df);
+    let rxb = (1.0 - x).powf(b);
+    let mut x_odd = beta_inc(a, b, x);
+    let mut g_odd = 2.0 * rxb * (a * x.ln() - ln_beta(a, b)).exp();
+    let mut x_even = 1.0 - rxb;
+    let mut g_even = b * x * rxb;
+    let mut sum = p * x_odd + q * x_even;
+    for n in 1..=1000 {
+        let n = n as f64;
+        a += 1.0;
+        x_odd -= g_odd;
+        x_even -= g_even;
+        g_odd *= x * (a + b - 1.0) / a;
+        g_even *= x * (a + b - 0.5) / (a + 0.5);
+        p *= lambda / (2.0 * n);
+        q *= lambda / (2.0 * n + 1.0);
+        s -= p;
+        sum += p * x_odd + q * x_even;
+        if (2.0 * s * (x_odd - g_odd)).abs() < 1e-14 {
+            break;
+        }
+    }
+    total += sum;
+    total.clamp(0.0, 1.0)
+}
+
+/// Upper tail probability `P(K > λ)` of the Kolmogorov
+/// distribution, the limiting distribution of `√n` times the
+/// Kolmogorov–Smirnov statistic.
+pub(crate) fn kolmogorov_upper_tail(lambda: f64) -> f64 {
+    use core::f64::consts::PI;
+    if lambda <= 0.0 {
+        return 1.0;
+    }
+    if lambda < 1.0 {
+        //P(K <= λ) = √(2π) / λ Σ exp(-(2j - 1)² π² / (8 λ²)),
+        //which converges quickly for small λ
+        let q = (-PI * PI / (8.0 * lambda * lambda)).exp();
+        let sum: f64 = (1..=6).map(|j| q.powi((2 * j - 1) * (2 * j - 1))).sum();
+        return 1.0 - (2.0 * PI).sqrt() / lambda * sum;
+    }
+    //P(K > λ) = 2 Σ (-1)^(j - 1) exp(-2 j² λ²)
+    let mut sum = 0.0;
+    for j in 1..=100 {
+        let j = j as f64;
+        let term = (-2.0 * j * j * lambda * lambda).exp();
+        sum += if j % 2.0 == 1.0 { term } else { -term };
+        if term < 1e-17 * sum {
+            break;
+        }
+    }
+    2.0 * sum
+}
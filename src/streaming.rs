@@ -0,0 +1,1116 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Accumulators that maintain statistics of an unbounded
+//! stream of values incrementally, without buffering it.
+//!
+//! Every accumulator implements [`Accumulator`], so code
+//! feeding a stream need not know which statistics are
+//! being kept.
+//!
+//! With the `serde` feature, every accumulator can be
+//! serialized, so a long-running stream can be checkpointed
+//! and resumed, or summarized in several places and merged.
+//!
+//! # Examples:
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! # use stats::streaming::*;
+//! let mut here = RunningStats::new();
+//! here.extend([1.0, 2.0, 3.0]);
+//! let saved = serde_json::to_string(&here).unwrap();
+//!
+//! let mut there = RunningStats::new();
+//! there.extend([4.0, 5.0]);
+//! there.merge(&serde_json::from_str(&saved).unwrap());
+//! assert_eq!(Some(3.0), there.mean());
+//! # }
+//! ```
+
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::random::Rng;
+use crate::{interpolate, quantile_position, quantile_with, welford, PartialStats, QuantileMethod};
+
+/// A statistic of a stream, updated one value at a time.
+pub trait Accumulator {
+    /// Add the next value of the stream.
+    fn push(&mut self, x: f64);
+
+    /// Number of values pushed.
+    fn count(&self) -> usize;
+}
+
+/// Running count, mean, variance and extremes of a stream of
+/// values, updated in constant time and memory by Welford's
+/// algorithm as each value is pushed. Queries follow the
+/// conventions of the slice function of the same name and
+/// may be made at any point in the stream.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut stats = RunningStats::new();
+/// assert_eq!(None, stats.variance());
+/// for x in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+///     stats.push(x);
+/// }
+/// assert_eq!(8, stats.count());
+/// assert_eq!(Some(5.0), stats.mean());
+/// assert_eq!(Some(2.0), stats.stddev());
+/// assert_eq!((Some(2.0), Some(9.0)), (stats.min(), stats.max()));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunningStats {
+    stats: PartialStats,
+}
+
+impl RunningStats {
+    /// Accumulator for an empty stream.
+    pub fn new() -> RunningStats {
+        RunningStats::default()
+    }
+
+    /// Add the next value of the stream.
+    pub fn push(&mut self, x: f64) {
+        self.stats.push(x);
+    }
+
+    /// Combine with the statistics of another stream.
+    pub fn merge(&mut self, other: &RunningStats) {
+        self.stats.merge(&other.stats);
+    }
+
+    /// Number of values pushed.
+    pub fn count(&self) -> usize {
+        self.stats.count()
+    }
+
+    /// Arithmetic mean; 0.0 for an empty stream.
+    pub fn mean(&self) -> Option<f64> {
+        self.stats.mean()
+    }
+
+    /// Population variance.
+    pub fn variance(&self) -> Option<f64> {
+        self.stats.variance()
+    }
+
+    /// Sample variance.
+    pub fn sample_variance(&self) -> Option<f64> {
+        self.stats.sample_variance()
+    }
+
+    /// Population standard deviation.
+    pub fn stddev(&self) -> Option<f64> {
+        self.stats.stddev()
+    }
+
+    /// Sample standard deviation.
+    pub fn sample_stddev(&self) -> Option<f64> {
+        self.stats.sample_stddev()
+    }
+
+    /// Smallest value; undefined if any value was NaN.
+    pub fn min(&self) -> Option<f64> {
+        self.stats.min()
+    }
+
+    /// Largest value; undefined if any value was NaN.
+    pub fn max(&self) -> Option<f64> {
+        self.stats.max()
+    }
+}
+
+impl Accumulator for RunningStats {
+    fn push(&mut self, x: f64) {
+        RunningStats::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        RunningStats::count(self)
+    }
+}
+
+impl From<PartialStats> for RunningStats {
+    fn from(stats: PartialStats) -> Self {
+        RunningStats { stats }
+    }
+}
+
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        self.stats.extend(iter);
+    }
+}
+
+impl core::iter::FromIterator<f64> for RunningStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        RunningStats {
+            stats: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Jain and Chlamtac's P² estimator of quantile `p` of a
+/// stream. Five markers track the minimum, the maximum, the
+/// estimate itself and two points either side of it; each
+/// push moves them towards their ideal positions, adjusting
+/// their heights by piecewise-parabolic interpolation. This
+/// takes constant time and memory per value. The estimate
+/// is exact for up to five values and typically close for
+/// long streams whose distribution does not drift.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// assert!(P2Quantile::new(1.5).is_none());
+/// let mut p95 = P2Quantile::new(0.95).unwrap();
+/// assert_eq!(None, p95.quantile());
+/// for i in 0..10_007 {
+///     p95.push(f64::from((i * 7919) % 10_007));
+/// }
+/// let estimate = p95.quantile().unwrap();
+/// assert!((estimate - 9506.0).abs() < 50.0);
+/// ```
+/// ```
+/// # use stats::streaming::*;
+/// let mut median = P2Quantile::new(0.5).unwrap();
+/// median.extend([5.0, 1.0, 4.0, 2.0]);
+/// assert_eq!(Some(3.0), median.quantile());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct P2Quantile {
+    p: f64,
+    count: usize,
+    nan: bool,
+    /// The first five values, until the markers start.
+    initial: Vec<f64>,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    /// Estimator of quantile `p` of an empty stream, or
+    /// `None` if `p` is outside `[0, 1]`.
+    pub fn new(p: f64) -> Option<P2Quantile> {
+        if !(0.0..=1.0).contains(&p) {
+            return None;
+        }
+        Some(P2Quantile {
+            p,
+            count: 0,
+            nan: false,
+            initial: Vec::with_capacity(5),
+            heights: [0.0; 5],
+            positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+            desired: [0.0, 4.0 * p / 2.0, 4.0 * p, 2.0 + 2.0 * p, 4.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        })
+    }
+
+    /// The quantile being estimated.
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Add the next value of the stream.
+    pub fn push(&mut self, x: f64) {
+        //algorithm referenced on: Jain & Chlamtac, "The P² Algorithm for Dynamic Calculation of Quantiles and Histograms Without Storing Observations" (1985)
+        self.count += 1;
+        if x.is_nan() {
+            self.nan = true;
+            return;
+        }
+        if self.initial.len() < 5 {
+            self.initial.push(x);
+            if self.initial.len() == 5 {
+                self.initial.sort_by(f64::total_cmp);
+                self.heights.copy_from_slice(&self.initial);
+            }
+            return;
+        }
+
+        // Find the cell containing x, stretching the extremes
+        // if needed, and shift the markers above it.
+        let h = &mut self.heights;
+        let cell = if x < h[0] {
+            h[0] = x;
+            0
+        } else if x >= h[4] {
+            h[4] = x;
+            3
+        } else {
+            (1..4).find(|&i| x < h[i]).unwrap_or(4) - 1
+        };
+        for n in &mut self.positions[cell + 1..] {
+            *n += 1.0;
+        }
+        for (d, dn) in self.desired.iter_mut().zip(&self.increments) {
+            *d += dn;
+        }
+
+        for i in 1..4 {
+            let (n, q) = (self.positions, self.heights);
+            let d = self.desired[i] - n[i];
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+                self.heights[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                self.positions[i] += d;
+            }
+        }
+    }
+
+    /// Current estimate of the quantile. The estimate is
+    /// undefined for an empty stream or if any value was
+    /// NaN.
+    pub fn quantile(&self) -> Option<f64> {
+        if self.nan {
+            return None;
+        }
+        // The last marker's position is one less than the
+        // number of values.
+        if self.positions[4] < 5.0 {
+            return quantile_with(&self.initial, self.p, QuantileMethod::Linear);
+        }
+        if self.p == 0.0 {
+            Some(self.heights[0])
+        } else if self.p == 1.0 {
+            Some(self.heights[4])
+        } else {
+            Some(self.heights[2])
+        }
+    }
+}
+
+impl Accumulator for P2Quantile {
+    fn push(&mut self, x: f64) {
+        P2Quantile::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Extend<f64> for P2Quantile {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Uniform random sample of fixed size from a stream, kept
+/// by Vitter's reservoir sampling "Algorithm R": after `n`
+/// values have been pushed, each is in the sample with equal
+/// probability `k / n`. Exact statistics of the sample can
+/// then be taken with the slice functions.
+///
+/// The choice of sample is pseudo-random and determined by
+/// the seed, so runs can be reproduced.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut reservoir = Reservoir::with_seed(1000, 42);
+/// for i in 0..1_000_000 {
+///     reservoir.push(f64::from(i % 100));
+/// }
+/// assert_eq!(1000, reservoir.sample().len());
+/// let median = stats::median(reservoir.sample()).unwrap();
+/// assert!((median - 49.5).abs() < 5.0);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reservoir {
+    capacity: usize,
+    count: usize,
+    sample: Vec<f64>,
+    rng: Rng,
+}
+
+impl Reservoir {
+    /// Reservoir keeping a sample of up to `capacity` values,
+    /// with a fixed default seed.
+    pub fn new(capacity: usize) -> Reservoir {
+        Reservoir::with_seed(capacity, 0)
+    }
+
+    /// Reservoir keeping a sample of up to `capacity` values,
+    /// choosing it pseudo-randomly from `seed`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::streaming::*;
+    /// let mut a = Reservoir::with_seed(10, 7);
+    /// let mut b = Reservoir::with_seed(10, 7);
+    /// a.extend((0..1000).map(f64::from));
+    /// b.extend((0..1000).map(f64::from));
+    /// assert_eq!(a.sample(), b.sample());
+    /// ```
+    pub fn with_seed(capacity: usize, seed: u64) -> Reservoir {
+        Reservoir {
+            capacity,
+            count: 0,
+            sample: Vec::with_capacity(capacity),
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Add the next value of the stream.
+    pub fn push(&mut self, x: f64) {
+        //https://en.wikipedia.org/wiki/Reservoir_sampling#Simple:_Algorithm_R
+        self.count += 1;
+        if self.sample.len() < self.capacity {
+            self.sample.push(x);
+            return;
+        }
+        let j = self.rng.below(self.count as u64) as usize;
+        if j < self.capacity {
+            self.sample[j] = x;
+        }
+    }
+
+    /// Largest size of the sample.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The values sampled so far, in no particular order:
+    /// every value pushed, until there have been more than
+    /// the capacity.
+    pub fn sample(&self) -> &[f64] {
+        &self.sample
+    }
+
+    /// Take the sample, leaving the reservoir.
+    pub fn into_sample(self) -> Vec<f64> {
+        self.sample
+    }
+}
+
+impl Accumulator for Reservoir {
+    fn push(&mut self, x: f64) {
+        Reservoir::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Extend<f64> for Reservoir {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Exponentially weighted moving mean and variance of a
+/// stream: each update gives the newest value weight `alpha`
+/// and scales the weight of everything before by `1 -
+/// alpha`, so that recent values dominate. The first value
+/// starts the mean.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut ewma = Ewma::new(0.5).unwrap();
+/// assert_eq!(None, ewma.value());
+/// for x in [10.0, 20.0, 20.0] {
+///     ewma.update(x);
+/// }
+/// assert_eq!(Some(17.5), ewma.value());
+/// ```
+/// ```
+/// # use stats::streaming::*;
+/// let mut ewma = Ewma::with_half_life(10.0).unwrap();
+/// ewma.update(0.0);
+/// for _ in 0..10 {
+///     ewma.update(100.0);
+/// }
+/// assert!((ewma.value().unwrap() - 50.0).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ewma {
+    alpha: f64,
+    count: usize,
+    mean: f64,
+    variance: f64,
+}
+
+impl Ewma {
+    /// Moving statistics with smoothing factor `alpha`, or
+    /// `None` unless `0 < alpha <= 1`.
+    pub fn new(alpha: f64) -> Option<Ewma> {
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return None;
+        }
+        Some(Ewma {
+            alpha,
+            count: 0,
+            mean: 0.0,
+            variance: 0.0,
+        })
+    }
+
+    /// Moving statistics in which a value's weight halves
+    /// every `half_life` updates, or `None` unless
+    /// `half_life` is positive.
+    pub fn with_half_life(half_life: f64) -> Option<Ewma> {
+        if half_life.is_nan() || half_life <= 0.0 {
+            return None;
+        }
+        Ewma::new(1.0 - (-1.0 / half_life).exp2())
+    }
+
+    /// The smoothing factor.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Add the next value of the stream.
+    pub fn update(&mut self, x: f64) {
+        //incremental form referenced on: Finch, "Incremental calculation of weighted mean and variance" (2009)
+        self.count += 1;
+        if self.count == 1 {
+            self.mean = x;
+            return;
+        }
+        let diff = x - self.mean;
+        let increment = self.alpha * diff;
+        self.mean += increment;
+        self.variance = (1.0 - self.alpha) * (self.variance + diff * increment);
+    }
+
+    /// The moving mean; undefined before the first update.
+    pub fn value(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The moving variance; undefined before the first
+    /// update.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.variance)
+    }
+
+    /// The moving standard deviation; undefined before the
+    /// first update.
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+impl Accumulator for Ewma {
+    fn push(&mut self, x: f64) {
+        self.update(x);
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Push-based anomaly detection on a stream: each value is
+/// scored by its distance from the exponentially weighted
+/// moving mean of the values before it, in moving standard
+/// deviations, as kept by an [`Ewma`] with smoothing factor
+/// `alpha`, and flagged as an anomaly if the score is beyond
+/// `threshold` either way. Values are scored once `warmup`
+/// values have been seen.
+///
+/// A scored value enters the moving statistics clamped to
+/// within `threshold` standard deviations of the mean, so
+/// that an anomaly does not mask those after it by inflating
+/// the variance; a lasting shift in level is still adopted
+/// within a few multiples of `1 / alpha` values. NaN and
+/// infinite values are ignored.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut detector = AnomalyDetector::new(0.1, 3.0, 10).unwrap();
+/// let readings = [100.0, 101.0, 99.0, 100.5, 99.5, 100.0, 101.0, 99.0, 100.0, 100.5];
+/// for x in readings {
+///     assert!(!detector.push(x));
+/// }
+/// assert!(detector.push(130.0));
+/// assert!(detector.score().unwrap() > 3.0);
+/// assert!(!detector.push(100.5));
+/// assert!(detector.push(60.0));
+/// assert!(!detector.push(f64::INFINITY));
+/// assert_eq!(None, detector.score());
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnomalyDetector {
+    ewma: Ewma,
+    threshold: f64,
+    warmup: usize,
+    score: Option<f64>,
+}
+
+impl AnomalyDetector {
+    /// Detector with smoothing factor `alpha`, flagging
+    /// values more than `threshold` standard deviations from
+    /// the mean once `warmup` values have been seen. `None`
+    /// unless `0 < alpha <= 1`, `threshold` is positive, and
+    /// `warmup` is at least 2.
+    pub fn new(alpha: f64, threshold: f64, warmup: usize) -> Option<AnomalyDetector> {
+        if threshold.is_nan() || threshold <= 0.0 || warmup < 2 {
+            return None;
+        }
+        Some(AnomalyDetector {
+            ewma: Ewma::new(alpha)?,
+            threshold,
+            warmup,
+            score: None,
+        })
+    }
+
+    /// The threshold score, in standard deviations.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Add the next value of the stream, returning true if
+    /// it is an anomaly.
+    pub fn push(&mut self, x: f64) -> bool {
+        self.score = None;
+        if !x.is_finite() {
+            return false;
+        }
+        if self.ewma.count < self.warmup {
+            self.ewma.update(x);
+            return false;
+        }
+        let (mean, stddev) = (self.ewma.mean, self.ewma.variance.sqrt());
+        //a constant warm-up has no spread to clamp to
+        let score = if x == mean { 0.0 } else { (x - mean) / stddev };
+        let margin = self.threshold * stddev;
+        //the moving statistics can overflow on huge values
+        let clamped = if mean.is_finite() && stddev.is_finite() && stddev > 0.0 {
+            x.clamp(mean - margin, mean + margin)
+        } else {
+            x
+        };
+        self.ewma.update(clamped);
+        self.score = Some(score);
+        score.abs() > self.threshold
+    }
+
+    /// The score of the last value pushed, its signed
+    /// distance from the mean in standard deviations;
+    /// undefined during the warm-up and after a NaN or
+    /// infinite value.
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+
+    /// The moving mean; undefined before the first value.
+    pub fn mean(&self) -> Option<f64> {
+        self.ewma.value()
+    }
+
+    /// The moving standard deviation; undefined before the
+    /// first value.
+    pub fn stddev(&self) -> Option<f64> {
+        self.ewma.stddev()
+    }
+}
+
+impl Accumulator for AnomalyDetector {
+    fn push(&mut self, x: f64) {
+        AnomalyDetector::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.ewma.count
+    }
+}
+
+/// Statistics of the last `window` values of a stream,
+/// updated in amortized constant time per value. The mean
+/// and variance are updated incrementally as values enter
+/// and leave the window, and recomputed from the window
+/// once per `window` values so that rounding errors cannot
+/// build up, and on every value while the window or the
+/// moments are not finite. The minimum and maximum are kept
+/// with monotonic queues of candidates.
+///
+/// Until the window fills, the statistics are those of all
+/// the values so far. Queries follow the conventions of the
+/// slice function of the same name applied to the window.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut rolling = RollingStats::with_window(3).unwrap();
+/// for x in [1.0, 5.0, 3.0, 2.0, 4.0] {
+///     rolling.push(x);
+/// }
+/// assert_eq!(3, rolling.len());
+/// assert_eq!(Some(3.0), rolling.mean());
+/// assert!((rolling.sample_variance().unwrap() - 1.0).abs() < 1e-12);
+/// assert_eq!((Some(2.0), Some(4.0)), (rolling.min(), rolling.max()));
+/// ```
+/// ```
+/// # use stats::streaming::*;
+/// let mut rolling = RollingStats::with_window(2).unwrap();
+/// for x in [1.0, f64::INFINITY, 1.0] {
+///     rolling.push(x);
+/// }
+/// assert!(rolling.mean().unwrap().is_nan());
+/// rolling.push(2.0);
+/// assert_eq!(Some(1.5), rolling.mean());
+/// assert_eq!(Some(0.25), rolling.variance());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingStats {
+    window: usize,
+    values: VecDeque<f64>,
+    /// Stream positions and values that could become the
+    /// window's minimum, in increasing order of both.
+    minima: VecDeque<(usize, f64)>,
+    /// Likewise for the maximum, in decreasing order of value.
+    maxima: VecDeque<(usize, f64)>,
+    pushed: usize,
+    nans: usize,
+    mean: f64,
+    m2: f64,
+    stale: usize,
+}
+
+impl RollingStats {
+    /// Rolling statistics over a window of the last `window`
+    /// values, or `None` for an empty window.
+    pub fn with_window(window: usize) -> Option<RollingStats> {
+        if window == 0 {
+            return None;
+        }
+        Some(RollingStats {
+            window,
+            values: VecDeque::with_capacity(window),
+            minima: VecDeque::new(),
+            maxima: VecDeque::new(),
+            pushed: 0,
+            nans: 0,
+            mean: 0.0,
+            m2: 0.0,
+            stale: 0,
+        })
+    }
+
+    /// Add the next value of the stream, dropping the oldest
+    /// value in the window if it is full.
+    pub fn push(&mut self, x: f64) {
+        let position = self.pushed;
+        self.pushed += 1;
+        let removed = if self.values.len() == self.window {
+            self.values.pop_front()
+        } else {
+            None
+        };
+        self.values.push_back(x);
+
+        if x.is_nan() {
+            self.nans += 1;
+        } else {
+            while self.minima.back().is_some_and(|&(_, m)| m >= x) {
+                self.minima.pop_back();
+            }
+            self.minima.push_back((position, x));
+            while self.maxima.back().is_some_and(|&(_, m)| m <= x) {
+                self.maxima.pop_back();
+            }
+            self.maxima.push_back((position, x));
+        }
+        let oldest = self.pushed - self.values.len();
+        while self.minima.front().is_some_and(|&(i, _)| i < oldest) {
+            self.minima.pop_front();
+        }
+        while self.maxima.front().is_some_and(|&(i, _)| i < oldest) {
+            self.maxima.pop_front();
+        }
+
+        self.stale += 1;
+        if removed.is_some_and(f64::is_nan) {
+            self.nans -= 1;
+        }
+        //the sliding update cannot take an infinity back out
+        let finite = x.is_finite()
+            && removed.is_none_or(f64::is_finite)
+            && self.mean.is_finite()
+            && self.m2.is_finite();
+        match removed {
+            _ if !finite => self.refresh(),
+            Some(_) if self.stale >= self.window => self.refresh(),
+            Some(y) => {
+                //sliding update: https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance#Welford's_online_algorithm
+                let mean = self.mean + (x - y) / self.window as f64;
+                self.m2 += (x - y) * (x - mean + y - self.mean);
+                self.mean = mean;
+            }
+            None => {
+                let delta = x - self.mean;
+                self.mean += delta / self.values.len() as f64;
+                self.m2 += delta * (x - self.mean);
+            }
+        }
+    }
+
+    /// Recompute the moments from the window.
+    fn refresh(&mut self) {
+        let (_, mean, m2) = welford(self.values.iter().copied());
+        self.mean = mean;
+        self.m2 = m2;
+        self.stale = 0;
+    }
+
+    /// Length of the window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no values have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Arithmetic mean of the window; 0.0 before any values.
+    pub fn mean(&self) -> Option<f64> {
+        Some(self.mean)
+    }
+
+    /// Variance of the window with `ddof` delta degrees of
+    /// freedom.
+    pub fn variance_ddof(&self, ddof: usize) -> Option<f64> {
+        if self.values.len() <= ddof {
+            return None;
+        }
+        Some(self.m2 / (self.values.len() - ddof) as f64)
+    }
+
+    /// Population variance of the window.
+    pub fn variance(&self) -> Option<f64> {
+        self.variance_ddof(0)
+    }
+
+    /// Sample variance of the window.
+    pub fn sample_variance(&self) -> Option<f64> {
+        self.variance_ddof(1)
+    }
+
+    /// Population standard deviation of the window.
+    pub fn stddev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Sample standard deviation of the window.
+    pub fn sample_stddev(&self) -> Option<f64> {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    /// Smallest value in the window; undefined if the window
+    /// is empty or holds a NaN.
+    pub fn min(&self) -> Option<f64> {
+        if self.nans > 0 {
+            return None;
+        }
+        self.minima.front().map(|m| m.1)
+    }
+
+    /// Largest value in the window; undefined if the window
+    /// is empty or holds a NaN.
+    pub fn max(&self) -> Option<f64> {
+        if self.nans > 0 {
+            return None;
+        }
+        self.maxima.front().map(|m| m.1)
+    }
+}
+
+impl Accumulator for RollingStats {
+    fn push(&mut self, x: f64) {
+        RollingStats::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.pushed
+    }
+}
+
+impl Extend<f64> for RollingStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Key ordering floats as `f64::total_cmp` does.
+fn ordered(x: f64) -> u64 {
+    let bits = x.to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | 1 << 63
+    }
+}
+
+/// Inverse of [`ordered`].
+fn unordered(key: u64) -> f64 {
+    if key >> 63 == 1 {
+        f64::from_bits(key & !(1 << 63))
+    } else {
+        f64::from_bits(!key)
+    }
+}
+
+/// Quantile `q` of the last `window` values of a stream,
+/// updated in O(log window) time per value. The window is
+/// split between two ordered sets: the values up to the
+/// quantile's lower order statistic and the values above
+/// it, so that the one or two values the quantile needs are
+/// the ends of the sets.
+///
+/// Until the window fills, the quantile is that of all the
+/// values so far. Queries follow the conventions of
+/// [`quantile_with`](crate::quantile_with) applied to the
+/// window.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut p90 = RollingQuantile::with_window(10, 0.9).unwrap();
+/// for i in 0..100 {
+///     p90.push(f64::from(i));
+/// }
+/// assert_eq!(Some(98.1), p90.quantile());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingQuantile {
+    window: usize,
+    q: f64,
+    method: QuantileMethod,
+    /// Keys of the values in the window, oldest first.
+    keys: VecDeque<u64>,
+    /// Values (as keys) with their stream positions, up to
+    /// and including the lower order statistic.
+    lower: BTreeSet<(u64, usize)>,
+    upper: BTreeSet<(u64, usize)>,
+    pushed: usize,
+    nans: usize,
+}
+
+impl RollingQuantile {
+    /// Rolling quantile `q` over a window of the last
+    /// `window` values, interpolating linearly, or `None` for
+    /// an empty window or `q` outside `[0, 1]`.
+    pub fn with_window(window: usize, q: f64) -> Option<RollingQuantile> {
+        RollingQuantile::with_method(window, q, QuantileMethod::Linear)
+    }
+
+    /// Rolling quantile `q` using the given interpolation
+    /// `method`, as for [`with_window`](RollingQuantile::with_window).
+    pub fn with_method(window: usize, q: f64, method: QuantileMethod) -> Option<RollingQuantile> {
+        if window == 0 || !(0.0..=1.0).contains(&q) {
+            return None;
+        }
+        Some(RollingQuantile {
+            window,
+            q,
+            method,
+            keys: VecDeque::with_capacity(window),
+            lower: BTreeSet::new(),
+            upper: BTreeSet::new(),
+            pushed: 0,
+            nans: 0,
+        })
+    }
+
+    /// Add the next value of the stream, dropping the oldest
+    /// value in the window if it is full.
+    pub fn push(&mut self, x: f64) {
+        if self.keys.len() == self.window {
+            let oldest = (self.keys.pop_front().unwrap(), self.pushed - self.window);
+            if !self.lower.remove(&oldest) {
+                self.upper.remove(&oldest);
+            }
+            if unordered(oldest.0).is_nan() {
+                self.nans -= 1;
+            }
+        }
+        let entry = (ordered(x), self.pushed);
+        self.pushed += 1;
+        self.keys.push_back(entry.0);
+        self.nans += usize::from(x.is_nan());
+        if self.upper.first().is_none_or(|&first| entry < first) {
+            self.lower.insert(entry);
+        } else {
+            self.upper.insert(entry);
+        }
+
+        let (lo, _) = quantile_position(self.keys.len(), self.q, self.method);
+        while self.lower.len() > lo + 1 {
+            let moved = self.lower.pop_last().unwrap();
+            self.upper.insert(moved);
+        }
+        while self.lower.len() < lo + 1 {
+            let moved = self.upper.pop_first().unwrap();
+            self.lower.insert(moved);
+        }
+    }
+
+    /// Length of the window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether no values have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Quantile of the window; undefined if the window is
+    /// empty or holds a NaN.
+    pub fn quantile(&self) -> Option<f64> {
+        if self.nans > 0 {
+            return None;
+        }
+        let (_, t) = quantile_position(self.keys.len(), self.q, self.method);
+        let x = unordered(self.lower.last()?.0);
+        let next = || unordered(self.upper.first().unwrap().0);
+        Some(interpolate(self.method, x, next, t))
+    }
+}
+
+impl Accumulator for RollingQuantile {
+    fn push(&mut self, x: f64) {
+        RollingQuantile::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.pushed
+    }
+}
+
+impl Extend<f64> for RollingQuantile {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Median of the last `window` values of a stream, updated
+/// in O(log window) time per value: a [`RollingQuantile`] at
+/// 0.5.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::streaming::*;
+/// let mut rolling = RollingMedian::with_window(4).unwrap();
+/// rolling.extend([9.0, 1.0, 8.0, 2.0, 7.0, 3.0]);
+/// assert_eq!(Some(5.0), rolling.median());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RollingMedian {
+    quantile: RollingQuantile,
+}
+
+impl RollingMedian {
+    /// Rolling median over a window of the last `window`
+    /// values, or `None` for an empty window.
+    pub fn with_window(window: usize) -> Option<RollingMedian> {
+        let quantile = RollingQuantile::with_window(window, 0.5)?;
+        Some(RollingMedian { quantile })
+    }
+
+    /// Add the next value of the stream.
+    pub fn push(&mut self, x: f64) {
+        self.quantile.push(x);
+    }
+
+    /// Length of the window.
+    pub fn window(&self) -> usize {
+        self.quantile.window()
+    }
+
+    /// Number of values currently in the window.
+    pub fn len(&self) -> usize {
+        self.quantile.len()
+    }
+
+    /// Whether no values have been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.quantile.is_empty()
+    }
+
+    /// Median of the window; undefined if the window is
+    /// empty or holds a NaN.
+    pub fn median(&self) -> Option<f64> {
+        self.quantile.quantile()
+    }
+}
+
+impl Accumulator for RollingMedian {
+    fn push(&mut self, x: f64) {
+        RollingMedian::push(self, x);
+    }
+
+    fn count(&self) -> usize {
+        self.quantile.pushed
+    }
+}
+
+impl Extend<f64> for RollingMedian {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        self.quantile.extend(iter);
+    }
+}
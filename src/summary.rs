@@ -0,0 +1,193 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Descriptive summary of a slice of floating-point numbers,
+//! computed together to avoid repeated passes over the data.
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::compactor::Compactor;
+use crate::{five_number_summary_sorted, sorted, welford, FiveNumSummary, PartialStats};
+
+/// Values kept per level by [`Summary::ingest_chunks`].
+const COMPACTOR_CAPACITY: usize = 2048;
+
+/// Count, moments, extremes and quartiles of a non-empty
+/// list of values. Building a `Summary` makes one sorted copy
+/// of the input and one pass over it; every accessor is then
+/// constant-time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    quartiles: FiveNumSummary,
+}
+
+impl Summary {
+    /// Summarize the input values. The summary of an empty
+    /// list, or of a list containing NaN, is undefined.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// assert_eq!(None, Summary::new(&[]));
+    /// ```
+    /// ```
+    /// # use stats::*;
+    /// let s = Summary::new(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]).unwrap();
+    /// assert_eq!(8, s.count());
+    /// assert_eq!(5.0, s.mean());
+    /// assert_eq!(2.0, s.stddev());
+    /// assert_eq!((2.0, 9.0), (s.min(), s.max()));
+    /// assert_eq!(4.5, s.median());
+    /// ```
+    pub fn new(nums: &[f64]) -> Option<Summary> {
+        let nums = sorted(nums);
+        let quartiles = five_number_summary_sorted(&nums)?;
+
+        let (_, mean, m2) = welford(nums.iter().copied());
+
+        Some(Summary {
+            count: nums.len(),
+            mean,
+            m2,
+            quartiles,
+        })
+    }
+
+    /// Summarize values arriving as a sequence of chunks,
+    /// such as blocks read from a file too large to hold in
+    /// memory. The count, mean, variance and extremes are
+    /// exact up to rounding; the quartiles are approximate:
+    /// each is a value whose rank is typically within a
+    /// fraction of a percent of the true quartile's. At most
+    /// a few tens of thousands of values are kept at once.
+    /// Inputs of up to 2047 values are summarized exactly.
+    /// As for [`new`](Summary::new), the summary of no values
+    /// or of values containing NaN is undefined.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// # use stats::*;
+    /// let chunk: Vec<f64> = (0..10_000).map(f64::from).collect();
+    /// let s = Summary::ingest_chunks((0..100).map(|_| &chunk[..])).unwrap();
+    /// assert_eq!(1_000_000, s.count());
+    /// assert_eq!(4999.5, s.mean());
+    /// assert_eq!((0.0, 9999.0), (s.min(), s.max()));
+    /// assert!((s.median() - 5000.0).abs() < 50.0);
+    /// ```
+    /// ```
+    /// # use stats::*;
+    /// let nums = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    /// let chunked = Summary::ingest_chunks(nums.chunks(3));
+    /// assert_eq!(Summary::new(&nums), chunked);
+    /// ```
+    pub fn ingest_chunks<'a, I>(chunks: I) -> Option<Summary>
+    where
+        I: IntoIterator<Item = &'a [f64]>,
+    {
+        let mut moments = PartialStats::new();
+        let mut compactor = Compactor::new(COMPACTOR_CAPACITY);
+        for chunk in chunks {
+            moments.merge(&PartialStats::from(chunk));
+            for &x in chunk {
+                compactor.push(x);
+            }
+        }
+        let (min, max) = (moments.min()?, moments.max()?);
+
+        let quartiles = match compactor.exact() {
+            Some(nums) => five_number_summary_sorted(&nums)?,
+            None => FiveNumSummary {
+                min,
+                q1: compactor.quantile(0.25)?,
+                median: compactor.quantile(0.5)?,
+                q3: compactor.quantile(0.75)?,
+                max,
+            },
+        };
+        let count = moments.count();
+        Some(Summary {
+            count,
+            mean: moments.mean()?,
+            m2: moments.variance()? * count as f64,
+            quartiles,
+        })
+    }
+
+    /// Number of values.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Arithmetic mean.
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Population variance.
+    pub fn variance(&self) -> f64 {
+        self.m2 / self.count as f64
+    }
+
+    /// Sample variance, or `None` for a single value.
+    pub fn sample_variance(&self) -> Option<f64> {
+        if self.count < 2 {
+            return None;
+        }
+        Some(self.m2 / (self.count - 1) as f64)
+    }
+
+    /// Population standard deviation.
+    pub fn stddev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Sample standard deviation, or `None` for a single
+    /// value.
+    pub fn sample_stddev(&self) -> Option<f64> {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    /// Smallest value.
+    pub fn min(&self) -> f64 {
+        self.quartiles.min
+    }
+
+    /// Largest value.
+    pub fn max(&self) -> f64 {
+        self.quartiles.max
+    }
+
+    /// First quartile.
+    pub fn q1(&self) -> f64 {
+        self.quartiles.q1
+    }
+
+    /// Median.
+    pub fn median(&self) -> f64 {
+        self.quartiles.median
+    }
+
+    /// Third quartile.
+    pub fn q3(&self) -> f64 {
+        self.quartiles.q3
+    }
+
+    /// Interquartile range.
+    pub fn iqr(&self) -> f64 {
+        self.quartiles.iqr()
+    }
+
+    /// Five-number summary.
+    pub fn five_number_summary(&self) -> FiveNumSummary {
+        self.quartiles
+    }
+}
@@ -0,0 +1,1220 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Hypothesis tests.
+//!
+//! Each test returns `None` when its statistic is undefined
+//! for the input, including for input containing NaN. The
+//! p-values are two-sided unless stated otherwise.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::correlation::{ranks, tie_runs};
+use crate::regression::theil_sen;
+use crate::special::{
+    f_upper_tail, gamma_inc_upper, kolmogorov_upper_tail, normal_cdf, normal_quantile,
+    student_t_cdf, student_t_quantile,
+};
+use crate::{has_nan, mean, median, sample_variance, sorted, sum};
+
+/// Outcome of a hypothesis test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    /// The test statistic.
+    pub statistic: f64,
+    /// Degrees of freedom of the statistic's reference
+    /// distribution, for tests that have one.
+    pub df: Option<f64>,
+    /// Probability, if the null hypothesis holds, of a
+    /// statistic at least as extreme as the one observed.
+    pub p_value: f64,
+}
+
+/// Result of a t test with statistic `t` on `df` degrees of
+/// freedom.
+fn t_result(t: f64, df: f64) -> TestResult {
+    TestResult {
+        statistic: t,
+        df: Some(df),
+        p_value: (2.0 * student_t_cdf(-t.abs(), df)).min(1.0),
+    }
+}
+
+/// Mean and sample variance of values, or `None` for fewer
+/// than two values or values containing NaN.
+fn mean_and_variance(nums: &[f64]) -> Option<(f64, f64)> {
+    if has_nan(nums) {
+        return None;
+    }
+    Some((mean(nums)?, sample_variance(nums)?))
+}
+
+/// Student's one-sample t test of whether input values have
+/// mean `mu0`. The test is undefined for fewer than two
+/// values and for values that are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// assert_eq!(None, t_test_one_sample(&[3.0, 3.0, 3.0], 0.0));
+/// ```
+/// ```
+/// # use stats::tests::*;
+/// let result = t_test_one_sample(&[5.1, 4.9, 5.6, 5.8, 6.0, 5.3], 5.0).unwrap();
+/// assert!((result.statistic - 2.6053).abs() < 1e-4);
+/// assert_eq!(Some(5.0), result.df);
+/// assert!((result.p_value - 0.04794).abs() < 1e-5);
+/// ```
+pub fn t_test_one_sample(xs: &[f64], mu0: f64) -> Option<TestResult> {
+    let (xmean, variance) = mean_and_variance(xs)?;
+    let n = xs.len() as f64;
+    let se = (variance / n).sqrt();
+    if se <= 0.0 {
+        return None;
+    }
+    Some(t_result((xmean - mu0) / se, n - 1.0))
+}
+
+/// Two-sample t test of whether two groups of values have
+/// equal means. With `equal_var`, this is Student's test,
+/// pooling the groups' variances; otherwise it is Welch's
+/// test, which does not assume the groups have equal
+/// variance and takes its degrees of freedom from the
+/// Welch–Satterthwaite equation. The test is undefined
+/// unless each group has at least two values, and when the
+/// values within both groups are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let (xs, ys) = ([19.0, 22.0, 16.0, 29.0, 24.0], [20.0, 11.0, 17.0, 12.0]);
+/// let student = t_test_two_sample(&xs, &ys, true).unwrap();
+/// assert!((student.statistic - 2.2393).abs() < 1e-4);
+/// assert_eq!(Some(7.0), student.df);
+/// assert!((student.p_value - 0.06014).abs() < 1e-5);
+/// let welch = t_test_two_sample(&xs, &ys, false).unwrap();
+/// assert!((welch.df.unwrap() - 6.9288).abs() < 1e-4);
+/// assert!((welch.p_value - 0.05675).abs() < 1e-5);
+/// ```
+pub fn t_test_two_sample(xs: &[f64], ys: &[f64], equal_var: bool) -> Option<TestResult> {
+    let (xmean, xvar) = mean_and_variance(xs)?;
+    let (ymean, yvar) = mean_and_variance(ys)?;
+    let (nx, ny) = (xs.len() as f64, ys.len() as f64);
+    let (se, df) = if equal_var {
+        let df = nx + ny - 2.0;
+        let pooled = ((nx - 1.0) * xvar + (ny - 1.0) * yvar) / df;
+        ((pooled * (1.0 / nx + 1.0 / ny)).sqrt(), df)
+    } else {
+        //Welch–Satterthwaite
+        let (vx, vy) = (xvar / nx, yvar / ny);
+        let df = (vx + vy).powi(2) / (vx * vx / (nx - 1.0) + vy * vy / (ny - 1.0));
+        ((vx + vy).sqrt(), df)
+    };
+    if se <= 0.0 {
+        return None;
+    }
+    Some(t_result((xmean - ymean) / se, df))
+}
+
+/// Outcome of a paired t test, with the mean of the
+/// differences between the pairs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairedTTest {
+    result: TestResult,
+    mean_difference: f64,
+    std_error: f64,
+}
+
+impl PairedTTest {
+    /// The t statistic, its degrees of freedom and the
+    /// p-value.
+    pub fn result(&self) -> TestResult {
+        self.result
+    }
+
+    /// Mean of the differences `after - before`.
+    pub fn mean_difference(&self) -> f64 {
+        self.mean_difference
+    }
+
+    /// Standard error of the mean difference.
+    pub fn std_error(&self) -> f64 {
+        self.std_error
+    }
+
+    /// Confidence interval for the mean difference at the
+    /// given confidence `level`, from the t distribution.
+    /// `None` unless `level` is strictly between 0.0 and 1.0.
+    pub fn confidence_interval(&self, level: f64) -> Option<(f64, f64)> {
+        if level.is_nan() || level <= 0.0 || level >= 1.0 {
+            return None;
+        }
+        let df = self.result.df?;
+        let margin = student_t_quantile(0.5 + 0.5 * level, df) * self.std_error;
+        Some((self.mean_difference - margin, self.mean_difference + margin))
+    }
+}
+
+/// Student's paired t test of whether matched measurements
+/// differ on average: the one-sample t test of the
+/// differences `after - before` against zero. The test is
+/// undefined for inputs of different lengths, for fewer than
+/// two pairs, and when every pair differs by the same amount.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let before = [200.0, 185.0, 210.0, 190.0, 178.0, 205.0, 195.0];
+/// let after = [192.0, 180.0, 205.0, 184.0, 176.0, 199.0, 193.0];
+/// let paired = t_test_paired(&before, &after).unwrap();
+/// assert!((paired.mean_difference() - -34.0 / 7.0).abs() < 1e-12);
+/// let result = paired.result();
+/// assert!((result.statistic - -5.8597).abs() < 1e-4);
+/// assert_eq!(Some(6.0), result.df);
+/// assert!((result.p_value - 0.0010916).abs() < 1e-7);
+/// let (low, high) = paired.confidence_interval(0.95).unwrap();
+/// assert!((low - -6.8854).abs() < 1e-4 && (high - -2.8289).abs() < 1e-4);
+/// ```
+pub fn t_test_paired(before: &[f64], after: &[f64]) -> Option<PairedTTest> {
+    if before.len() != after.len() {
+        return None;
+    }
+    let differences: Vec<f64> = before.iter().zip(after).map(|(b, a)| a - b).collect();
+    let result = t_test_one_sample(&differences, 0.0)?;
+    let (mean_difference, variance) = mean_and_variance(&differences)?;
+    Some(PairedTTest {
+        result,
+        mean_difference,
+        std_error: (variance / differences.len() as f64).sqrt(),
+    })
+}
+
+/// Sum of `t³ - t` over the runs of `t` tied values, by
+/// which ties reduce the variance of rank statistics.
+fn tie_term(nums: &[f64]) -> f64 {
+    tie_runs(nums).iter().map(|&t| (t * t * t - t) as f64).sum()
+}
+
+/// Number of ways that each value of the Mann–Whitney
+/// statistic `U` arises among the orderings of `nx` and `ny`
+/// distinct values, indexed by `U`.
+fn u_frequencies(nx: usize, ny: usize) -> Vec<f64> {
+    //f(m, n, u) = f(m - 1, n, u - n) + f(m, n - 1, u), as the
+    //largest value either is one of the m and exceeds all n,
+    //or is one of the n
+    let mut previous: Vec<Vec<f64>> = (0..=ny).map(|_| vec![1.0]).collect();
+    for m in 1..=nx {
+        let mut current = vec![vec![1.0]];
+        for n in 1..=ny {
+            let mut f = vec![0.0; m * n + 1];
+            for (u, count) in previous[n].iter().enumerate() {
+                f[u + n] += count;
+            }
+            for (u, count) in current[n - 1].iter().enumerate() {
+                f[u] += count;
+            }
+            current.push(f);
+        }
+        previous = current;
+    }
+    previous.swap_remove(ny)
+}
+
+/// Mann–Whitney U test (Wilcoxon rank-sum test) of whether
+/// values from one group tend to be larger or smaller than
+/// values from another, without assuming either is normally
+/// distributed. The statistic is `U` for `xs`: the number of
+/// pairs of a value from `xs` and a value from `ys` in which
+/// the `xs` value is larger, counting ties as one half.
+///
+/// When both groups have fewer than 50 values and there are
+/// no ties, the p-value is exact. Otherwise it comes from the
+/// normal approximation with continuity correction, with the
+/// variance corrected for ties. The test is undefined for an
+/// empty group, for values containing NaN, and when all the
+/// values are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let result = mann_whitney_u(&[1.1, 3.4, 2.2, 5.0], &[4.1, 6.3, 7.2, 5.5, 8.8]).unwrap();
+/// assert_eq!(1.0, result.statistic);
+/// assert_eq!(None, result.df);
+/// // 4 of the 126 orderings are at least as extreme.
+/// assert!((result.p_value - 4.0 / 126.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::tests::*;
+/// let xs = [3.0, 4.0, 2.0, 6.0, 2.0, 5.0, 4.0, 7.0, 3.0, 3.0, 8.0, 5.0];
+/// let ys = [9.0, 7.0, 5.0, 10.0, 6.0, 8.0, 7.0, 9.0, 6.0, 11.0, 8.0, 12.0, 7.0];
+/// let result = mann_whitney_u(&xs, &ys).unwrap();
+/// assert_eq!(14.5, result.statistic);
+/// assert!((result.p_value - 5.6891e-4).abs() < 1e-8);
+/// ```
+pub fn mann_whitney_u(xs: &[f64], ys: &[f64]) -> Option<TestResult> {
+    let (nx, ny) = (xs.len(), ys.len());
+    if nx == 0 || ny == 0 {
+        return None;
+    }
+    let pooled: Vec<f64> = xs.iter().chain(ys).cloned().collect();
+    let ranks = ranks(&pooled)?;
+    let u = ranks[..nx].iter().sum::<f64>() - (nx * (nx + 1)) as f64 / 2.0;
+    let ties = tie_term(&pooled);
+    let p_value = if ties == 0.0 && nx < 50 && ny < 50 {
+        let frequencies = u_frequencies(nx, ny);
+        let total: f64 = frequencies.iter().sum();
+        let u = u as usize;
+        let lower: f64 = frequencies[..=u].iter().sum();
+        let upper: f64 = frequencies[u..].iter().sum();
+        2.0 * lower.min(upper) / total
+    } else {
+        let (nx, ny) = (nx as f64, ny as f64);
+        let n = nx + ny;
+        let variance = nx * ny / 12.0 * (n + 1.0 - ties / (n * (n - 1.0)));
+        if variance <= 0.0 {
+            return None;
+        }
+        let z = ((u - nx * ny / 2.0).abs() - 0.5).max(0.0) / variance.sqrt();
+        2.0 * normal_cdf(-z)
+    };
+    Some(TestResult {
+        statistic: u,
+        df: None,
+        p_value: p_value.min(1.0),
+    })
+}
+
+/// Number of ways that each value of the signed-rank
+/// statistic arises among the `2ⁿ` assignments of signs to
+/// the ranks `1..=n`, indexed by the statistic.
+fn w_frequencies(n: usize) -> Vec<f64> {
+    let mut f = vec![0.0; n * (n + 1) / 2 + 1];
+    f[0] = 1.0;
+    for rank in 1..=n {
+        //counts for the subsets of 1..=rank, in place
+        for w in (rank..=rank * (rank + 1) / 2).rev() {
+            f[w] += f[w - rank];
+        }
+    }
+    f
+}
+
+/// Wilcoxon signed-rank test of whether `differences` are
+/// centered on zero.
+fn signed_rank(differences: &[f64]) -> Option<TestResult> {
+    if has_nan(differences) {
+        return None;
+    }
+    let nonzero: Vec<f64> = differences.iter().cloned().filter(|&d| d != 0.0).collect();
+    let n = nonzero.len();
+    if n == 0 {
+        return None;
+    }
+    let magnitudes: Vec<f64> = nonzero.iter().map(|d| d.abs()).collect();
+    let ranks = ranks(&magnitudes)?;
+    let w: f64 = ranks
+        .iter()
+        .zip(&nonzero)
+        .filter(|&(_, &d)| d > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+    let ties = tie_term(&magnitudes);
+    let p_value = if ties == 0.0 && n == differences.len() && n < 50 {
+        let frequencies = w_frequencies(n);
+        let total: f64 = frequencies.iter().sum();
+        let w = w as usize;
+        let lower: f64 = frequencies[..=w].iter().sum();
+        let upper: f64 = frequencies[w..].iter().sum();
+        2.0 * lower.min(upper) / total
+    } else {
+        let n = n as f64;
+        let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties / 48.0;
+        if variance <= 0.0 {
+            return None;
+        }
+        let z = ((w - n * (n + 1.0) / 4.0).abs() - 0.5).max(0.0) / variance.sqrt();
+        2.0 * normal_cdf(-z)
+    };
+    Some(TestResult {
+        statistic: w,
+        df: None,
+        p_value: p_value.min(1.0),
+    })
+}
+
+/// Wilcoxon signed-rank test of whether matched measurements
+/// differ, without assuming their differences are normally
+/// distributed: a test of whether the differences
+/// `ys - xs` are symmetric about zero. The statistic is the
+/// sum of the ranks of the positive differences among the
+/// absolute differences, with tied absolute differences
+/// given their mean rank. Pairs with no difference are
+/// dropped.
+///
+/// When fewer than 50 pairs remain, there are no ties and no
+/// pairs were dropped, the p-value is exact. Otherwise it
+/// comes from the normal approximation with continuity
+/// correction, with the variance corrected for ties. The
+/// test is undefined for inputs of different lengths, for
+/// values containing NaN, and when no pair differs.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let xs = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+/// let ys = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+/// let result = wilcoxon_signed_rank(&xs, &ys).unwrap();
+/// assert_eq!(5.0, result.statistic);
+/// assert!((result.p_value - 20.0 / 512.0).abs() < 1e-12);
+/// ```
+/// ```
+/// # use stats::tests::*;
+/// let before = [125.0, 115.0, 130.0, 140.0, 140.0, 115.0, 140.0, 125.0, 140.0, 135.0];
+/// let after = [110.0, 122.0, 125.0, 120.0, 140.0, 124.0, 123.0, 137.0, 135.0, 145.0];
+/// let result = wilcoxon_signed_rank(&before, &after).unwrap();
+/// assert_eq!(18.0, result.statistic);
+/// assert!((result.p_value - 0.63529).abs() < 1e-5);
+/// ```
+pub fn wilcoxon_signed_rank(xs: &[f64], ys: &[f64]) -> Option<TestResult> {
+    if xs.len() != ys.len() {
+        return None;
+    }
+    let differences: Vec<f64> = xs.iter().zip(ys).map(|(x, y)| y - x).collect();
+    signed_rank(&differences)
+}
+
+/// Wilcoxon signed-rank test of whether input values are
+/// symmetric about `median0`: the
+/// [`wilcoxon_signed_rank`] test of the differences
+/// `xs - median0`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let xs = [0.5, 1.2, 2.4, 3.1, 3.3, 4.5, 5.6, 6.7];
+/// let result = wilcoxon_signed_rank_one_sample(&xs, 3.0).unwrap();
+/// // Three of the differences are negative, ranked 3, 5 and 6.
+/// assert_eq!(36.0 - 14.0, result.statistic);
+/// assert!(result.p_value > 0.5);
+/// ```
+pub fn wilcoxon_signed_rank_one_sample(xs: &[f64], median0: f64) -> Option<TestResult> {
+    let differences: Vec<f64> = xs.iter().map(|x| x - median0).collect();
+    signed_rank(&differences)
+}
+
+/// Result of a chi-square test with statistic `x` on `df`
+/// degrees of freedom.
+fn chi_square_result(x: f64, df: f64) -> TestResult {
+    //the upper tail of chi-square with df degrees of freedom
+    //is Q(df/2, x/2)
+    TestResult {
+        statistic: x,
+        df: Some(df),
+        p_value: gamma_inc_upper(0.5 * df, 0.5 * x),
+    }
+}
+
+/// Pearson's chi-square goodness-of-fit test of whether
+/// observed counts in categories follow the expected
+/// distribution. The `expected` values may be counts or
+/// relative frequencies: they are rescaled to the total of
+/// the `observed` counts.
+///
+/// The chi-square approximation is poor when the expected
+/// count of any category is small, below about 5. The test is
+/// undefined for inputs of different lengths, for fewer than
+/// two categories, for negative or NaN counts, for an
+/// expected value that is not positive, and when nothing was
+/// observed.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// // 300 rolls of a die
+/// let rolls = [43.0, 52.0, 54.0, 40.0, 58.0, 53.0];
+/// let result = chi_square_gof(&rolls, &[1.0; 6]).unwrap();
+/// assert!((result.statistic - 4.84).abs() < 1e-12);
+/// assert_eq!(Some(5.0), result.df);
+/// assert!((result.p_value - 0.435717).abs() < 1e-6);
+/// ```
+/// ```
+/// # use stats::tests::*;
+/// let observed = [89.0, 37.0, 30.0, 28.0, 2.0];
+/// let result = chi_square_gof(&observed, &[0.40, 0.20, 0.20, 0.15, 0.05]).unwrap();
+/// assert!((result.p_value - 0.040594).abs() < 1e-6);
+/// ```
+pub fn chi_square_gof(observed: &[f64], expected: &[f64]) -> Option<TestResult> {
+    let k = observed.len();
+    if k != expected.len() || k < 2 {
+        return None;
+    }
+    if observed.iter().any(|&o| o.is_nan() || o < 0.0)
+        || expected.iter().any(|&e| e.is_nan() || e <= 0.0)
+    {
+        return None;
+    }
+    let scale = sum(observed) / sum(expected);
+    if scale <= 0.0 {
+        return None;
+    }
+    let x: f64 = observed
+        .iter()
+        .zip(expected)
+        .map(|(o, e)| {
+            let e = e * scale;
+            (o - e).powi(2) / e
+        })
+        .sum();
+    Some(chi_square_result(x, (k - 1) as f64))
+}
+
+/// Pearson's chi-square test of independence of the row and
+/// column variables of a contingency table of counts, given
+/// as its rows: `table[i][j]` is the number of observations
+/// in row category `i` and column category `j`. No
+/// continuity correction is applied, even to 2×2 tables.
+///
+/// As with [`chi_square_gof`], the approximation is poor
+/// when any expected count is below about 5. The test is
+/// undefined for a table with fewer than two rows or
+/// columns, with rows of different lengths, with negative or
+/// NaN counts, or with a row or column that is all zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let table = [[762.0, 327.0, 468.0], [484.0, 239.0, 477.0]];
+/// let result = chi_square_independence(&table).unwrap();
+/// assert!((result.statistic - 30.0701).abs() < 1e-4);
+/// assert_eq!(Some(2.0), result.df);
+/// assert!((result.p_value - 2.95359e-7).abs() < 1e-11);
+/// ```
+pub fn chi_square_independence<R: AsRef<[f64]>>(table: &[R]) -> Option<TestResult> {
+    let rows = table.len();
+    let cols = table.first()?.as_ref().len();
+    if rows < 2 || cols < 2 || table.iter().any(|r| r.as_ref().len() != cols) {
+        return None;
+    }
+    if table
+        .iter()
+        .any(|r| r.as_ref().iter().any(|&c| c.is_nan() || c < 0.0))
+    {
+        return None;
+    }
+    let row_totals: Vec<f64> = table.iter().map(|r| sum(r.as_ref())).collect();
+    let col_totals: Vec<f64> = (0..cols)
+        .map(|j| table.iter().map(|r| r.as_ref()[j]).sum())
+        .collect();
+    if row_totals.iter().chain(&col_totals).any(|&t| t <= 0.0) {
+        return None;
+    }
+    let total: f64 = row_totals.iter().sum();
+    let mut x = 0.0;
+    for (row, row_total) in table.iter().zip(&row_totals) {
+        for (count, col_total) in row.as_ref().iter().zip(&col_totals) {
+            let expected = row_total * col_total / total;
+            x += (count - expected).powi(2) / expected;
+        }
+    }
+    Some(chi_square_result(x, ((rows - 1) * (cols - 1)) as f64))
+}
+
+/// Outcome of an F test comparing groups of values, such as
+/// an analysis of variance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnovaResult {
+    /// The F statistic: the ratio of the mean square between
+    /// groups to the mean square within them.
+    pub statistic: f64,
+    /// Degrees of freedom between groups, one less than the
+    /// number of groups.
+    pub df_between: f64,
+    /// Degrees of freedom within groups, the number of
+    /// values less the number of groups.
+    pub df_within: f64,
+    /// Probability, if the null hypothesis holds, of an F
+    /// statistic at least as large as the one observed.
+    pub p_value: f64,
+}
+
+/// One-way analysis of variance: the F test of whether
+/// several groups of values have equal means, assuming the
+/// values are normally distributed with equal variance in
+/// every group. With two groups, it is equivalent to
+/// Student's [`t_test_two_sample`]. The analysis is
+/// undefined for fewer than two groups, for an empty group,
+/// for no more values than groups, for values containing
+/// NaN, and when the values within every group are all
+/// equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let a = [6.0, 8.0, 4.0, 5.0, 3.0, 4.0];
+/// let b = [8.0, 12.0, 9.0, 11.0, 6.0, 8.0];
+/// let c = [13.0, 9.0, 11.0, 8.0, 7.0, 12.0];
+/// let result = anova_one_way(&[&a[..], &b, &c]).unwrap();
+/// assert!((result.statistic - 9.264706).abs() < 1e-6);
+/// assert_eq!((2.0, 15.0), (result.df_between, result.df_within));
+/// assert!((result.p_value - 0.00239878).abs() < 1e-8);
+/// ```
+pub fn anova_one_way<G: AsRef<[f64]>>(groups: &[G]) -> Option<AnovaResult> {
+    let k = groups.len();
+    if k < 2
+        || groups
+            .iter()
+            .any(|g| g.as_ref().is_empty() || has_nan(g.as_ref()))
+    {
+        return None;
+    }
+    let n: usize = groups.iter().map(|g| g.as_ref().len()).sum();
+    if n <= k {
+        return None;
+    }
+    let means = groups
+        .iter()
+        .map(|g| mean(g.as_ref()))
+        .collect::<Option<Vec<f64>>>()?;
+    let grand_mean = groups
+        .iter()
+        .zip(&means)
+        .map(|(g, m)| g.as_ref().len() as f64 * m)
+        .sum::<f64>()
+        / n as f64;
+    let mut between = 0.0;
+    let mut within = 0.0;
+    for (g, m) in groups.iter().zip(&means) {
+        let g = g.as_ref();
+        between += g.len() as f64 * (m - grand_mean).powi(2);
+        within += g.iter().map(|x| (x - m).powi(2)).sum::<f64>();
+    }
+    if within <= 0.0 {
+        return None;
+    }
+    let (df_between, df_within) = ((k - 1) as f64, (n - k) as f64);
+    let f = (between / df_between) / (within / df_within);
+    Some(AnovaResult {
+        statistic: f,
+        df_between,
+        df_within,
+        p_value: f_upper_tail(f, df_between, df_within),
+    })
+}
+
+/// Kruskal–Wallis H test of whether several groups of values
+/// come from the same distribution, against the alternative
+/// that some group tends to larger or smaller values: the
+/// rank-based counterpart of [`anova_one_way`], which does
+/// not assume normality. The statistic is corrected for
+/// ties, and its p-value comes from its chi-square
+/// approximation with one less degree of freedom than there
+/// are groups, which needs about 5 values in every group to
+/// be accurate. The test is undefined for fewer than two
+/// groups, for an empty group, for values containing NaN,
+/// and when all the values are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let a = [1.0, 2.0, 2.0, 3.0, 4.0];
+/// let b = [3.0, 4.0, 4.0, 5.0, 6.0, 6.0];
+/// let c = [5.0, 6.0, 7.0, 7.0, 8.0];
+/// let result = kruskal_wallis(&[&a[..], &b, &c]).unwrap();
+/// assert!((result.statistic - 10.79491).abs() < 1e-5);
+/// assert_eq!(Some(2.0), result.df);
+/// assert!((result.p_value - 0.00452809).abs() < 1e-8);
+/// ```
+pub fn kruskal_wallis<G: AsRef<[f64]>>(groups: &[G]) -> Option<TestResult> {
+    let k = groups.len();
+    if k < 2 || groups.iter().any(|g| g.as_ref().is_empty()) {
+        return None;
+    }
+    let pooled: Vec<f64> = groups.iter().flat_map(|g| g.as_ref()).cloned().collect();
+    let ranks = ranks(&pooled)?;
+    let n = pooled.len() as f64;
+    let mut weighted = 0.0;
+    let mut start = 0;
+    for g in groups {
+        let len = g.as_ref().len();
+        let rank_sum: f64 = ranks[start..start + len].iter().sum();
+        weighted += rank_sum * rank_sum / len as f64;
+        start += len;
+    }
+    let correction = 1.0 - tie_term(&pooled) / (n * n * n - n);
+    if correction <= 0.0 {
+        return None;
+    }
+    let h = (12.0 / (n * (n + 1.0)) * weighted - 3.0 * (n + 1.0)) / correction;
+    Some(chi_square_result(h.max(0.0), (k - 1) as f64))
+}
+
+/// Product of two square matrices of order `m`, in row-major
+/// order.
+fn square_product(a: &[f64], b: &[f64], m: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * m];
+    for i in 0..m {
+        for k in 0..m {
+            let aik = a[i * m + k];
+            for j in 0..m {
+                c[i * m + j] += aik * b[k * m + j];
+            }
+        }
+    }
+    c
+}
+
+/// Exact probability that the Kolmogorov–Smirnov statistic
+/// of `n` values is less than `d`.
+fn kolmogorov_cdf(n: usize, d: f64) -> f64 {
+    //Marsaglia, Tsang and Wang (2003), "Evaluating
+    //Kolmogorov's distribution": https://doi.org/10.18637/jss.v008.i18
+    let nd = n as f64 * d;
+    let k = nd as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nd;
+    let mut a = vec![0.0; m * m];
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 >= j {
+                a[i * m + j] = 1.0;
+            }
+        }
+    }
+    for i in 0..m {
+        a[i * m] -= h.powi(i as i32 + 1);
+        a[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h > 1.0 {
+        a[(m - 1) * m] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..=(i + 1).min(m - 1) {
+            for g in 1..=i + 1 - j {
+                a[i * m + j] /= g as f64;
+            }
+        }
+    }
+    //a^n by squaring, keeping the scale as a power of two
+    //apart so that nothing underflows
+    let mut power = a;
+    let mut power_scale = 0;
+    let mut result: Option<(Vec<f64>, i32)> = None;
+    let mut e = n;
+    while e > 0 {
+        if e % 2 == 1 {
+            result = Some(match result {
+                None => (power.clone(), power_scale),
+                Some((r, scale)) => {
+                    rescale(square_product(&r, &power, m), scale + power_scale, m, k)
+                }
+            });
+        }
+        e /= 2;
+        if e > 0 {
+            let (p, scale) = rescale(square_product(&power, &power, m), 2 * power_scale, m, k);
+            power = p;
+            power_scale = scale;
+        }
+    }
+    let (q, scale) = match result {
+        Some(r) => r,
+        None => return 1.0,
+    };
+    //multiply by n! / nⁿ
+    let mut p = q[(k - 1) * m + k - 1];
+    let mut scale = scale;
+    for i in 1..=n {
+        p *= i as f64 / n as f64;
+        if p < 1e-140 {
+            p *= 2f64.powi(465);
+            scale -= 465;
+        }
+    }
+    p * 2f64.powi(scale)
+}
+
+/// Matrix `a` with its central element brought below `1e140`
+/// by a power of two, added to `scale`.
+fn rescale(mut a: Vec<f64>, mut scale: i32, m: usize, k: usize) -> (Vec<f64>, i32) {
+    if a[(k - 1) * m + k - 1] > 1e140 {
+        for x in a.iter_mut() {
+            *x *= 2f64.powi(-465);
+        }
+        scale += 465;
+    }
+    (a, scale)
+}
+
+/// Kolmogorov–Smirnov test of whether input values come
+/// from the continuous distribution with cumulative
+/// distribution function `cdf`. The statistic `D` is the
+/// largest distance between `cdf` and the empirical
+/// distribution function of the values.
+///
+/// For up to 100 values the p-value is exact; for more, it
+/// comes from the limiting Kolmogorov distribution with
+/// Stephens' correction for the number of values. The test
+/// is undefined for no values, for values containing NaN,
+/// and when `cdf` returns NaN or a value outside
+/// `0.0..=1.0`. The p-value is only valid if the
+/// distribution was chosen without reference to the values;
+/// parameters estimated from them make it conservative.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let uniform = |x: f64| x.clamp(0.0, 1.0);
+/// let result = ks_test(&[0.1, 0.2, 0.3, 0.35, 0.4], uniform).unwrap();
+/// assert!((result.statistic - 0.6).abs() < 1e-12);
+/// assert!((result.p_value - 0.03008).abs() < 1e-12);
+/// ```
+pub fn ks_test<F: Fn(f64) -> f64>(xs: &[f64], cdf: F) -> Option<TestResult> {
+    if xs.is_empty() || has_nan(xs) {
+        return None;
+    }
+    let xs = sorted(xs);
+    let n = xs.len() as f64;
+    let mut d: f64 = 0.0;
+    for (i, &x) in xs.iter().enumerate() {
+        let f = cdf(x);
+        if f.is_nan() || !(0.0..=1.0).contains(&f) {
+            return None;
+        }
+        d = d.max((i + 1) as f64 / n - f).max(f - i as f64 / n);
+    }
+    let p_value = if xs.len() <= 100 {
+        1.0 - kolmogorov_cdf(xs.len(), d)
+    } else {
+        kolmogorov_upper_tail((n.sqrt() + 0.12 + 0.11 / n.sqrt()) * d)
+    };
+    Some(TestResult {
+        statistic: d,
+        df: None,
+        p_value: p_value.clamp(0.0, 1.0),
+    })
+}
+
+/// Two-sample Kolmogorov–Smirnov test of whether two groups
+/// of values come from the same continuous distribution. The
+/// statistic `D` is the largest distance between the
+/// empirical distribution functions of the groups.
+///
+/// When the product of the group sizes is at most 10,000 the
+/// p-value is exact, by counting the orderings of the
+/// values, assuming there are no ties; otherwise it comes
+/// from the limiting Kolmogorov distribution with Stephens'
+/// correction for the group sizes. The test is undefined for
+/// an empty group and for values containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let xs = [
+///     0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64, 0.05, -0.06, 0.64,
+///     -0.82, 0.37, 1.77, 1.09, -1.28, 2.36, 1.31, 1.05, -0.32, -0.4, 1.06, -2.47,
+/// ];
+/// let ys = [
+///     2.20, 1.66, 1.38, 0.20, 0.36, 0.00, 0.96, 1.56, 0.44, 1.50, -0.30, 0.66, 2.31,
+///     3.29, -0.27, -0.37, 0.38, 0.70, 0.52, -0.71,
+/// ];
+/// let result = ks_2samp(&xs, &ys).unwrap();
+/// assert!((result.statistic - 0.23).abs() < 1e-12);
+/// assert!((result.p_value - 0.528636).abs() < 1e-6);
+/// assert_eq!(Some(0.0), ks_2samp(&xs, &xs).map(|r| r.statistic));
+/// ```
+pub fn ks_2samp(xs: &[f64], ys: &[f64]) -> Option<TestResult> {
+    let (nx, ny) = (xs.len(), ys.len());
+    if nx == 0 || ny == 0 || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let (xs, ys) = (sorted(xs), sorted(ys));
+    //D·nx·ny, the largest |i·ny - j·nx| after the first i of
+    //xs and j of ys, as an integer to compare exactly
+    let (mut i, mut j, mut largest) = (0, 0, 0);
+    while i < nx || j < ny {
+        let value = match (xs.get(i), ys.get(j)) {
+            (Some(&x), Some(&y)) => x.min(y),
+            (Some(&x), None) => x,
+            (None, Some(&y)) => y,
+            (None, None) => unreachable!(),
+        };
+        while i < nx && xs[i] == value {
+            i += 1;
+        }
+        while j < ny && ys[j] == value {
+            j += 1;
+        }
+        largest = largest.max((i * ny).abs_diff(j * nx));
+    }
+    let d = largest as f64 / (nx * ny) as f64;
+    let p_value = if nx * ny <= 10_000 {
+        //count the lattice paths from (0, 0) to (nx, ny) that
+        //stay within the observed distance
+        let mut paths = vec![0.0; ny + 1];
+        for i in 0..=nx {
+            for j in 0..=ny {
+                paths[j] = if (i * ny).abs_diff(j * nx) >= largest {
+                    0.0
+                } else if i == 0 && j == 0 {
+                    1.0
+                } else if j == 0 {
+                    paths[j]
+                } else {
+                    paths[j] + paths[j - 1]
+                };
+            }
+        }
+        let total = (1..=nx).fold(1.0, |c, i| c * (ny + i) as f64 / i as f64);
+        1.0 - paths[ny] / total
+    } else {
+        let n = (nx * ny) as f64 / (nx + ny) as f64;
+        kolmogorov_upper_tail((n.sqrt() + 0.12 + 0.11 / n.sqrt()) * d)
+    };
+    Some(TestResult {
+        statistic: d,
+        df: None,
+        p_value: p_value.clamp(0.0, 1.0),
+    })
+}
+
+/// Shapiro–Wilk test of whether input values come from a
+/// normal distribution, of any mean and variance. The
+/// statistic `W` is the squared correlation between the
+/// sorted values and their expected positions under
+/// normality, near 1.0 for normal data; small values of `W`
+/// are evidence against normality. `W` and its p-value are
+/// computed by Royston's approximations, valid for 3 to 5000
+/// values. The test is undefined outside that range, for
+/// values containing NaN, and when all the values are equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let heights = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let result = shapiro_wilk(&heights).unwrap();
+/// assert!((result.statistic - 0.78881).abs() < 1e-5);
+/// assert!((result.p_value - 0.006704).abs() < 1e-6);
+/// ```
+/// ```
+/// # use stats::tests::*;
+/// let xs = [-1.2, -0.8, -0.5, -0.3, -0.1, 0.0, 0.2, 0.4, 0.6, 0.9, 1.3];
+/// assert!(shapiro_wilk(&xs).unwrap().p_value > 0.9);
+/// ```
+pub fn shapiro_wilk(xs: &[f64]) -> Option<TestResult> {
+    //Royston (1995), "Remark AS R94": https://doi.org/10.2307/2986146
+    const C1: [f64; 6] = [0.0, 0.221_157, -0.147_981, -2.071_19, 4.434_685, -2.706_056];
+    const C2: [f64; 6] = [
+        0.0, 0.042_981, -0.293_762, -1.752_461, 5.682_633, -3.582_633,
+    ];
+    const C3: [f64; 4] = [0.544, -0.399_78, 0.025_054, -6.714e-4];
+    const C4: [f64; 4] = [1.3822, -0.778_57, 0.062_767, -0.002_032_2];
+    const C5: [f64; 4] = [-1.5861, -0.310_82, -0.083_751, 0.003_891_5];
+    const C6: [f64; 3] = [-0.4803, -0.082_676, 0.003_030_2];
+    const G: [f64; 2] = [-2.273, 0.459];
+    let polynomial = |c: &[f64], x: f64| c.iter().rev().fold(0.0, |acc, &c| acc * x + c);
+    let n = xs.len();
+    if !(3..=5000).contains(&n) || has_nan(xs) {
+        return None;
+    }
+    let xs = sorted(xs);
+    let xmean = mean(&xs)?;
+    let ss: f64 = xs.iter().map(|x| (x - xmean).powi(2)).sum();
+    if ss <= 0.0 || xs[n - 1] == xs[0] {
+        return None;
+    }
+    //coefficients a_i of the i-th smallest and largest values
+    let half = n / 2;
+    let nf = n as f64;
+    let a: Vec<f64> = if n == 3 {
+        vec![0.5f64.sqrt()]
+    } else {
+        let m: Vec<f64> = (1..=half)
+            .map(|i| -normal_quantile((i as f64 - 0.375) / (nf + 0.25)))
+            .collect();
+        let summ2 = 2.0 * m.iter().map(|m| m * m).sum::<f64>();
+        let (ssumm2, rsn) = (summ2.sqrt(), 1.0 / nf.sqrt());
+        let a1 = m[0] / ssumm2 + polynomial(&C1, rsn);
+        let mut a = vec![a1];
+        let fac = if n > 5 {
+            let a2 = m[1] / ssumm2 + polynomial(&C2, rsn);
+            a.push(a2);
+            ((summ2 - 2.0 * (m[0] * m[0] + m[1] * m[1])) / (1.0 - 2.0 * (a1 * a1 + a2 * a2))).sqrt()
+        } else {
+            ((summ2 - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a1 * a1)).sqrt()
+        };
+        a.extend(m[a.len()..].iter().map(|m| m / fac));
+        a
+    };
+    let numerator: f64 = a
+        .iter()
+        .enumerate()
+        .map(|(i, a)| a * (xs[n - 1 - i] - xs[i]))
+        .sum();
+    let w = (numerator * numerator / ss).min(1.0);
+    let p_value = if n == 3 {
+        //the exact distribution
+        use core::f64::consts::PI;
+        (6.0 / PI * (w.sqrt().asin() - PI / 3.0)).max(0.0)
+    } else {
+        //a normalizing transformation of W
+        let mut y = (1.0 - w).ln();
+        let (m, s) = if n <= 11 {
+            let gamma = polynomial(&G, nf);
+            if y >= gamma {
+                return Some(TestResult {
+                    statistic: w,
+                    df: None,
+                    p_value: 0.0,
+                });
+            }
+            y = -(gamma - y).ln();
+            (polynomial(&C3, nf), polynomial(&C4, nf).exp())
+        } else {
+            let ln_n = nf.ln();
+            (polynomial(&C5, ln_n), polynomial(&C6, ln_n).exp())
+        };
+        normal_cdf((m - y) / s)
+    };
+    Some(TestResult {
+        statistic: w,
+        df: None,
+        p_value: p_value.min(1.0),
+    })
+}
+
+/// Analysis of variance of the absolute deviations of each
+/// group's values from the group's `center`.
+fn deviation_anova<G, F>(groups: &[G], center: F) -> Option<AnovaResult>
+where
+    G: AsRef<[f64]>,
+    F: Fn(&[f64]) -> Option<f64>,
+{
+    let deviations = groups
+        .iter()
+        .map(|g| {
+            let g = g.as_ref();
+            let c = center(g)?;
+            Some(g.iter().map(|x| (x - c).abs()).collect())
+        })
+        .collect::<Option<Vec<Vec<f64>>>>()?;
+    anova_one_way(&deviations)
+}
+
+/// Levene's test of whether several groups of values have
+/// equal variances: the [`anova_one_way`] of the absolute
+/// deviations of the values from their group means. It is
+/// less sensitive to non-normality than [`bartlett`], but see
+/// [`brown_forsythe`] for skewed data. The test is undefined
+/// as [`anova_one_way`] is, including when the values within
+/// every group are all equal.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let a = [8.88, 9.12, 9.04, 8.98, 9.00, 9.08, 9.01, 8.85, 9.06, 8.99];
+/// let b = [8.88, 8.95, 9.29, 9.44, 9.15, 9.58, 8.36, 9.18, 8.67, 9.05];
+/// let c = [8.95, 9.12, 8.95, 8.85, 9.03, 8.84, 9.07, 8.98, 8.86, 8.98];
+/// let result = levene(&[&a[..], &b, &c]).unwrap();
+/// assert!((result.statistic - 7.905194).abs() < 1e-6);
+/// assert!((result.p_value - 0.00198380).abs() < 1e-8);
+/// ```
+pub fn levene<G: AsRef<[f64]>>(groups: &[G]) -> Option<AnovaResult> {
+    deviation_anova(groups, mean)
+}
+
+/// Brown–Forsythe test of whether several groups of values
+/// have equal variances: [`levene`]'s test with deviations
+/// from the group medians rather than means, which keeps its
+/// size for skewed distributions.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let a = [8.88, 9.12, 9.04, 8.98, 9.00, 9.08, 9.01, 8.85, 9.06, 8.99];
+/// let b = [8.88, 8.95, 9.29, 9.44, 9.15, 9.58, 8.36, 9.18, 8.67, 9.05];
+/// let c = [8.95, 9.12, 8.95, 8.85, 9.03, 8.84, 9.07, 8.98, 8.86, 8.98];
+/// let result = brown_forsythe(&[&a[..], &b, &c]).unwrap();
+/// assert!((result.statistic - 7.584953).abs() < 1e-6);
+/// assert!((result.p_value - 0.00243151).abs() < 1e-8);
+/// ```
+pub fn brown_forsythe<G: AsRef<[f64]>>(groups: &[G]) -> Option<AnovaResult> {
+    deviation_anova(groups, median)
+}
+
+/// Bartlett's test of whether several groups of values have
+/// equal variances, assuming the values are normally
+/// distributed, with a chi-square statistic on one less
+/// degree of freedom than there are groups. It is sensitive
+/// to departures from normality; [`levene`] and
+/// [`brown_forsythe`] are more robust. The test is
+/// undefined for fewer than two groups, for a group of fewer
+/// than two values or of values that are all equal, and for
+/// values containing NaN.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let a = [8.88, 9.12, 9.04, 8.98, 9.00, 9.08, 9.01, 8.85, 9.06, 8.99];
+/// let b = [8.88, 8.95, 9.29, 9.44, 9.15, 9.58, 8.36, 9.18, 8.67, 9.05];
+/// let c = [8.95, 9.12, 8.95, 8.85, 9.03, 8.84, 9.07, 8.98, 8.86, 8.98];
+/// let result = bartlett(&[&a[..], &b, &c]).unwrap();
+/// assert!((result.statistic - 22.789435).abs() < 1e-6);
+/// assert_eq!(Some(2.0), result.df);
+/// assert!((result.p_value - 1.125478e-5).abs() < 1e-11);
+/// ```
+pub fn bartlett<G: AsRef<[f64]>>(groups: &[G]) -> Option<TestResult> {
+    let k = groups.len();
+    if k < 2 {
+        return None;
+    }
+    let mut df_within = 0.0;
+    let mut pooled = 0.0;
+    let mut ln_variances = 0.0;
+    let mut reciprocals = 0.0;
+    for g in groups {
+        let (_, variance) = mean_and_variance(g.as_ref())?;
+        if variance <= 0.0 {
+            return None;
+        }
+        let df = (g.as_ref().len() - 1) as f64;
+        df_within += df;
+        pooled += df * variance;
+        ln_variances += df * variance.ln();
+        reciprocals += 1.0 / df;
+    }
+    pooled /= df_within;
+    let k = k as f64;
+    let correction = 1.0 + (reciprocals - 1.0 / df_within) / (3.0 * (k - 1.0));
+    let t = (df_within * pooled.ln() - ln_variances) / correction;
+    Some(chi_square_result(t.max(0.0), k - 1.0))
+}
+
+/// Outcome of a Mann–Kendall trend test, with Sen's
+/// estimate of the slope of the trend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MannKendall {
+    result: TestResult,
+    z: f64,
+    slope: f64,
+}
+
+impl MannKendall {
+    /// The statistic `S` and the p-value.
+    pub fn result(&self) -> TestResult {
+        self.result
+    }
+
+    /// The standard normal score of `S`, with continuity
+    /// correction, whose sign is that of the trend.
+    pub fn z(&self) -> f64 {
+        self.z
+    }
+
+    /// Sen's slope: the median of the slopes `(xs[j] - xs[i])
+    /// / (j - i)` between all pairs of values, the change per
+    /// step.
+    pub fn slope(&self) -> f64 {
+        self.slope
+    }
+}
+
+/// Mann–Kendall test of whether a series of values has a
+/// monotonic trend, making no assumption about the
+/// distribution of the values. Its statistic `S` is the
+/// number of pairs of values that increase with time less
+/// the number that decrease, which is Kendall's τ of the
+/// values against time before normalization. The p-value
+/// comes from the normal approximation to the distribution
+/// of `S` with correction for ties, which is good from
+/// around ten values. The test is undefined for values
+/// that are NaN or infinite and for fewer than two distinct
+/// values.
+/// It takes `O(n²)` time and memory, as for
+/// [`theil_sen`](crate::regression::theil_sen).
+///
+/// The test assumes the values are independent, so
+/// autocorrelation, as is common in series, inflates its
+/// significance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::tests::*;
+/// let xs = [1.0, 3.0, 2.0, 5.0, 5.0, 6.0, 8.0, 7.0, 9.0, 12.0];
+/// let trend = mann_kendall(&xs).unwrap();
+/// assert_eq!(40.0, trend.result().statistic);
+/// assert!((trend.z() - 3.502303390).abs() < 1e-9);
+/// assert!((trend.result().p_value - 0.000461254067).abs() < 1e-12);
+/// assert_eq!(1.0, trend.slope());
+/// assert_eq!(None, mann_kendall(&[2.0, 2.0, 2.0]));
+/// assert_eq!(None, mann_kendall(&[1.0, f64::INFINITY, f64::INFINITY, 2.0]));
+/// ```
+pub fn mann_kendall(xs: &[f64]) -> Option<MannKendall> {
+    if xs.iter().any(|x| !x.is_finite()) {
+        return None;
+    }
+    let n = xs.len();
+    let mut s = 0.0;
+    for (i, x) in xs.iter().enumerate() {
+        for y in &xs[i + 1..] {
+            if y > x {
+                s += 1.0;
+            } else if y < x {
+                s -= 1.0;
+            }
+        }
+    }
+    let ties: f64 = tie_runs(xs)
+        .iter()
+        .map(|&t| (t * (t - 1) * (2 * t + 5)) as f64)
+        .sum();
+    let n_f = n as f64;
+    let variance = (n_f * (n_f - 1.0) * (2.0 * n_f + 5.0) - ties) / 18.0;
+    if variance <= 0.0 {
+        return None;
+    }
+    let z = if s > 0.0 {
+        (s - 1.0) / variance.sqrt()
+    } else if s < 0.0 {
+        (s + 1.0) / variance.sqrt()
+    } else {
+        0.0
+    };
+    let times: Vec<f64> = (0..n).map(|t| t as f64).collect();
+    let slope = theil_sen(&times, xs)?.slope();
+    Some(MannKendall {
+        result: TestResult {
+            statistic: s,
+            df: None,
+            p_value: (2.0 * normal_cdf(-z.abs())).min(1.0),
+        },
+        z,
+        slope,
+    })
+}
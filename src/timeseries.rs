@@ -0,0 +1,509 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Statistics of series of values taken at equally spaced
+//! times, whose order matters: correlations across time,
+//! decomposition into components, and the detection of
+//! shifts and peaks.
+//!
+//! The lag-`k` statistics relate each value to the value `k`
+//! steps later. Functions of a series of length `n` up to a
+//! `max_lag` return one value per lag `0..=max_lag`, and are
+//! undefined for values containing NaN and for `max_lag` of
+//! `n` or more.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::SQRT_2;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::intervals::half_alpha;
+use crate::robust::normalized_mad;
+use crate::special::normal_quantile;
+use crate::transform::diff;
+use crate::{dot, has_nan, mean, stddev};
+
+/// Autocovariance of a series at lags `0..=max_lag`: at lag
+/// `k`, the sum of the products of the deviations from the
+/// mean of each value and the value `k` later, divided by
+/// the length of the series. Dividing by `n` rather than the
+/// number of products biases the estimate toward zero at
+/// long lags, but guarantees a valid (positive semidefinite)
+/// autocovariance. The lag-0 autocovariance is the
+/// population variance.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let gamma = autocovariance(&[1.0, 2.0, 3.0, 4.0], 2).unwrap();
+/// assert_eq!(vec![1.25, 0.3125, -0.375], gamma);
+/// assert_eq!(None, autocovariance(&[1.0, 2.0], 2));
+/// ```
+pub fn autocovariance(nums: &[f64], max_lag: usize) -> Option<Vec<f64>> {
+    let n = nums.len();
+    if max_lag >= n || has_nan(nums) {
+        return None;
+    }
+    let m = mean(nums)?;
+    let deviations: Vec<f64> = nums.iter().map(|x| x - m).collect();
+    let gamma = (0..=max_lag)
+        .map(|k| {
+            let products = deviations.iter().zip(&deviations[k..]).map(|(a, b)| a * b);
+            products.sum::<f64>() / n as f64
+        })
+        .collect();
+    Some(gamma)
+}
+
+/// Autocorrelation function (ACF) of a series at lags
+/// `0..=max_lag`: the [`autocovariance`] at each lag divided
+/// by the variance, so that the lag-0 autocorrelation is 1.0.
+/// Undefined also for a series of equal values.
+///
+/// Seasonality of period `p` shows as peaks at multiples of
+/// `p`; a slowly decaying ACF suggests a trend. Compare the
+/// autocorrelations with the [`bartlett_bands`] to judge
+/// which differ from zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let r = acf(&[1.0, 2.0, 3.0, 4.0], 2).unwrap();
+/// assert_eq!(vec![1.0, 0.25, -0.3], r);
+/// ```
+pub fn acf(nums: &[f64], max_lag: usize) -> Option<Vec<f64>> {
+    let gamma = autocovariance(nums, max_lag)?;
+    let variance = gamma[0];
+    if variance <= 0.0 {
+        return None;
+    }
+    Some(gamma.iter().map(|g| g / variance).collect())
+}
+
+/// Half-widths of Bartlett's confidence bands at `level`
+/// (such as 0.95) for the autocorrelations `acf` of a series
+/// of `n` values: at lag `k`, `z √((1 + 2 Σ r_j²) / n)` over
+/// the lags `0 < j < k`, for `z` the normal quantile. An
+/// autocorrelation outside `±` its band is evidence of
+/// correlation at that lag, given that there is none at
+/// longer lags. The band at lag 0 is 0.0, since that
+/// autocorrelation is 1.0 by definition. `None` for no
+/// values or for a `level` outside `0.0..1.0`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let bands = bartlett_bands(&[1.0, 0.5, 0.1], 100, 0.95).unwrap();
+/// assert_eq!(0.0, bands[0]);
+/// assert!((bands[1] - 0.195996398).abs() < 1e-9);
+/// assert!((bands[2] - 0.195996398 * 1.5f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn bartlett_bands(acf: &[f64], n: usize, level: f64) -> Option<Vec<f64>> {
+    let tail = half_alpha(level)?;
+    if n == 0 {
+        return None;
+    }
+    let z = normal_quantile(1.0 - tail);
+    let mut squares = 0.0;
+    let bands = acf
+        .iter()
+        .enumerate()
+        .map(|(k, r)| {
+            if k == 0 {
+                return 0.0;
+            }
+            let band = z * ((1.0 + 2.0 * squares) / n as f64).sqrt();
+            squares += r * r;
+            band
+        })
+        .collect();
+    Some(bands)
+}
+
+/// Partial autocorrelation function (PACF) of a series at
+/// lags `0..=max_lag`: at lag `k`, the correlation between
+/// values `k` apart that remains once the values between
+/// them are accounted for, which is the last coefficient of
+/// the best autoregressive model of order `k`. It is
+/// computed from the [`acf`] by the Durbin–Levinson
+/// recursion. The lag-0 value is 1.0. Undefined also for a
+/// series of equal values.
+///
+/// The PACF of an autoregressive series of order `p` cuts
+/// off after lag `p`, which identifies the order. For a
+/// series with no correlation the partial autocorrelations
+/// are roughly normal with standard deviation `1 / √n`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let phi = pacf(&[1.0, 2.0, 3.0, 4.0], 2).unwrap();
+/// assert_eq!(1.0, phi[0]);
+/// assert_eq!(0.25, phi[1]);
+/// assert!((phi[2] + 0.3625 / 0.9375).abs() < 1e-15);
+/// ```
+pub fn pacf(nums: &[f64], max_lag: usize) -> Option<Vec<f64>> {
+    let r = acf(nums, max_lag)?;
+    let mut partial = Vec::with_capacity(max_lag + 1);
+    partial.push(1.0);
+    //phi holds the coefficients of the order k - 1 model
+    let mut phi: Vec<f64> = Vec::with_capacity(max_lag);
+    for k in 1..=max_lag {
+        let numerator: f64 = r[k] - (1..k).map(|j| phi[j - 1] * r[k - j]).sum::<f64>();
+        let denominator: f64 = 1.0 - (1..k).map(|j| phi[j - 1] * r[j]).sum::<f64>();
+        let phi_kk = numerator / denominator;
+        let previous = phi.clone();
+        for j in 1..k {
+            phi[j - 1] = previous[j - 1] - phi_kk * previous[k - j - 1];
+        }
+        phi.push(phi_kk);
+        partial.push(phi_kk);
+    }
+    Some(partial)
+}
+
+/// Cross-correlation of two series of the same length at
+/// lags `-max_lag..=max_lag`: at lag `k`, the correlation of
+/// each value of `xs` with the value of `ys` `k` steps
+/// earlier, computed as for the [`acf`] from the products of
+/// deviations divided by `n`. The `i`th value is at lag `i -
+/// max_lag`. Undefined for series of different lengths and
+/// for either series of equal values.
+///
+/// A peak at a positive lag `k` means that `ys` leads `xs` by
+/// `k` steps; at a negative lag, that `xs` leads `ys`. The
+/// [`bartlett_bands`] at lag 1 for `n` values, `z / √n`,
+/// approximate the bands for series that are uncorrelated
+/// and have no autocorrelation.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let ys = [1.0, 3.0, 2.0, 5.0, 4.0, 1.0, 2.0];
+/// let xs = [0.0, 1.0, 3.0, 2.0, 5.0, 4.0, 1.0];
+/// let r = cross_correlation(&xs, &ys, 2).unwrap();
+/// let lag = (0..5).max_by(|&a, &b| r[a].total_cmp(&r[b])).unwrap() as i64 - 2;
+/// assert_eq!(1, lag);
+/// let r = cross_correlation(&ys, &ys, 1).unwrap();
+/// assert_eq!(acf(&ys, 1).unwrap()[1], r[0]);
+/// assert!((r[1] - 1.0).abs() < 1e-15);
+/// ```
+pub fn cross_correlation(xs: &[f64], ys: &[f64], max_lag: usize) -> Option<Vec<f64>> {
+    let n = xs.len();
+    if ys.len() != n || max_lag >= n || has_nan(xs) || has_nan(ys) {
+        return None;
+    }
+    let deviations = |nums: &[f64]| -> Option<Vec<f64>> {
+        let m = mean(nums)?;
+        Some(nums.iter().map(|x| x - m).collect())
+    };
+    let (dx, dy) = (deviations(xs)?, deviations(ys)?);
+    let scale = (dot(&dx, &dx)? * dot(&dy, &dy)?).sqrt();
+    if scale <= 0.0 {
+        return None;
+    }
+    //at lag k, x[t + k] against y[t]
+    let at = |k: usize, a: &[f64], b: &[f64]| -> f64 {
+        a[k..].iter().zip(b).map(|(x, y)| x * y).sum::<f64>() / scale
+    };
+    let negative = (1..=max_lag).rev().map(|k| at(k, &dy, &dx));
+    let positive = (0..=max_lag).map(|k| at(k, &dx, &dy));
+    Some(negative.chain(positive).collect())
+}
+
+/// Additive decomposition of a series into trend, seasonal
+/// and residual components, as computed by [`decompose`].
+/// Each component has one value per value of the series,
+/// which is their sum.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Decomposition {
+    /// The slowly varying level of the series.
+    pub trend: Vec<f64>,
+    /// The pattern repeating every period, which sums to
+    /// zero over a period.
+    pub seasonal: Vec<f64>,
+    /// What remains of the series without the trend and
+    /// seasonal components.
+    pub residual: Vec<f64>,
+}
+
+/// Classical additive decomposition of a series with
+/// seasonality of `period` steps. The trend is the centered
+/// moving average over one period, weighting the two end
+/// values by half for an even period; within half a period
+/// of either end, where that window does not fit, the trend
+/// continues linearly with its slope over the nearest
+/// period. The seasonal component of each position in the
+/// period is the mean of the values there less the centered
+/// trend, shifted so the components sum to zero. Undefined
+/// for a `period` less than two and for fewer than two
+/// periods of values.
+///
+/// Subtracting the seasonal component leaves the seasonally
+/// adjusted series; the residuals are what a threshold for
+/// anomalies should be applied to.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let season = [5.0, -2.0, 1.0, -4.0];
+/// let xs: Vec<f64> = (0..12).map(|t| 0.5 * t as f64 + season[t % 4]).collect();
+/// let parts = decompose(&xs, 4).unwrap();
+/// for t in 0..12 {
+///     assert!((parts.trend[t] - 0.5 * t as f64).abs() < 1e-12);
+///     assert!((parts.seasonal[t] - season[t % 4]).abs() < 1e-12);
+///     assert!(parts.residual[t].abs() < 1e-12);
+/// }
+/// assert_eq!(None, decompose(&xs, 7));
+/// ```
+pub fn decompose(nums: &[f64], period: usize) -> Option<Decomposition> {
+    let n = nums.len();
+    if period < 2 || n / 2 < period || has_nan(nums) {
+        return None;
+    }
+    let m = period as f64;
+    let half = period / 2;
+    let (first, last) = (half, n - 1 - half);
+    let mut trend = vec![0.0; n];
+    for (t, level) in trend.iter_mut().enumerate().take(last + 1).skip(first) {
+        let window = &nums[t - half..=t + half];
+        *level = if period % 2 == 1 {
+            window.iter().sum::<f64>() / m
+        } else {
+            let ends = 0.5 * (window[0] + window[period]);
+            (ends + window[1..period].iter().sum::<f64>()) / m
+        };
+    }
+    let span = (last - first).min(period);
+    let slope = (trend[first + span] - trend[first]) / span as f64;
+    for t in 0..first {
+        trend[t] = trend[first] - (first - t) as f64 * slope;
+    }
+    let slope = (trend[last] - trend[last - span]) / span as f64;
+    for t in last + 1..n {
+        trend[t] = trend[last] + (t - last) as f64 * slope;
+    }
+    //there is a centered trend for every position in the period
+    let mut sums = vec![0.0; period];
+    let mut counts = vec![0.0; period];
+    for t in first..=last {
+        sums[t % period] += nums[t] - trend[t];
+        counts[t % period] += 1.0;
+    }
+    let means: Vec<f64> = sums.iter().zip(&counts).map(|(s, c)| s / c).collect();
+    let offset = mean(&means)?;
+    let seasonal: Vec<f64> = (0..n).map(|t| means[t % period] - offset).collect();
+    let residual = nums
+        .iter()
+        .zip(&trend)
+        .zip(&seasonal)
+        .map(|((x, a), b)| x - a - b)
+        .collect();
+    Some(Decomposition {
+        trend,
+        seasonal,
+        residual,
+    })
+}
+
+/// Positions at which the mean of a series shifts, found
+/// by the pruned exact linear time (PELT) method of
+/// Killick, Fearnhead and Eckley. Each position is the
+/// start of a new segment, in increasing order.
+///
+/// The segmentation chosen minimizes the sum over segments
+/// of the squared deviations from the segment mean, in
+/// units of the noise variance, plus `penalty` for each
+/// change point. The noise standard deviation is estimated
+/// robustly from the differences of successive values, so
+/// that the penalty does not depend on the scale of the
+/// series; a penalty of around `2 ln n`, as in the BIC,
+/// suits normal noise, and larger penalties find fewer,
+/// larger shifts. Undefined for values that are not all
+/// finite and for a `penalty` that is negative or not
+/// finite.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let mut xs = vec![];
+/// for (i, level) in [10.0, 14.0, 11.0].iter().enumerate() {
+///     for j in 0..20 {
+///         let noise = [0.3, -0.6, 0.1, 0.5, -0.2][(i + j) % 5];
+///         xs.push(level + noise);
+///     }
+/// }
+/// let penalty = 2.0 * (xs.len() as f64).ln();
+/// assert_eq!(Some(vec![20, 40]), change_points(&xs, penalty));
+/// assert_eq!(Some(vec![]), change_points(&xs[..20], penalty));
+/// assert_eq!(None, change_points(&xs, f64::INFINITY));
+/// ```
+pub fn change_points(nums: &[f64], penalty: f64) -> Option<Vec<usize>> {
+    if !nums.iter().all(|x| x.is_finite()) || !penalty.is_finite() || penalty < 0.0 {
+        return None;
+    }
+    let n = nums.len();
+    if n < 2 {
+        return Some(Vec::new());
+    }
+    //the differences of noise values have twice their variance
+    let differences = diff(nums, 1);
+    let mut noise = normalized_mad(&differences)? / SQRT_2;
+    if noise == 0.0 {
+        noise = stddev(&differences)? / SQRT_2;
+    }
+    if noise == 0.0 {
+        return Some(Vec::new());
+    }
+    //prefix sums of the standardized values and their squares
+    let m = mean(nums)?;
+    let mut sums = vec![(0.0, 0.0); n + 1];
+    for (i, x) in nums.iter().enumerate() {
+        let z = (x - m) / noise;
+        sums[i + 1] = (sums[i].0 + z, sums[i].1 + z * z);
+    }
+    let cost = |a: usize, b: usize| {
+        let (s, s2) = (sums[b].0 - sums[a].0, sums[b].1 - sums[a].1);
+        s2 - s * s / (b - a) as f64
+    };
+    //best[t] is the least penalized cost of the first t values,
+    //whose last segment starts at start[t]
+    let mut best = vec![0.0; n + 1];
+    let mut start = vec![0; n + 1];
+    best[0] = -penalty;
+    let mut candidates = vec![0];
+    for t in 1..=n {
+        let (tau, total) = candidates
+            .iter()
+            .map(|&s| (s, best[s] + cost(s, t) + penalty))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        best[t] = total;
+        start[t] = tau;
+        //a start that cannot beat the best now never will
+        candidates.retain(|&s| best[s] + cost(s, t) <= total);
+        candidates.push(t);
+    }
+    let mut points = Vec::new();
+    let mut t = start[n];
+    while t > 0 {
+        points.push(t);
+        t = start[t];
+    }
+    points.reverse();
+    Some(points)
+}
+
+/// A peak of a series, as found by [`find_peaks`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Peak {
+    /// Position of the peak in the series.
+    pub index: usize,
+    /// How far the peak stands above the higher of the
+    /// lowest values separating it from higher values on
+    /// either side, or from the ends of the series.
+    pub prominence: f64,
+}
+
+/// Peaks of a series, in order: the local maxima, values
+/// higher than their neighbors on both sides, whose
+/// prominence is at least `min_prominence` and which are at
+/// least `min_distance` positions from any higher peak kept.
+/// The first and last values are never peaks. A peak with a
+/// flat top of several equal values is at the middle of
+/// the top, rounding down. Peaks too close together are
+/// resolved in favor of the higher, then the earlier.
+///
+/// The troughs of a series are the peaks of its negation.
+/// Undefined for values containing NaN and for a negative or
+/// NaN `min_prominence`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::timeseries::*;
+/// let xs = [0.0, 2.0, 1.0, 3.0, 1.0, 1.0, 5.0, 5.0, 5.0, 0.0, 1.0, 0.0];
+/// let peaks = find_peaks(&xs, 1.5, 0).unwrap();
+/// let found: Vec<(usize, f64)> = peaks.iter().map(|p| (p.index, p.prominence)).collect();
+/// assert_eq!(vec![(3, 2.0), (7, 5.0)], found);
+/// assert_eq!(4, find_peaks(&xs, 0.0, 0).unwrap().len());
+/// assert_eq!(7, find_peaks(&xs, 1.5, 5).unwrap()[0].index);
+///
+/// let negated: Vec<f64> = xs.iter().map(|x| -x).collect();
+/// let troughs = find_peaks(&negated, 0.0, 0).unwrap();
+/// assert_eq!(vec![2, 4, 9], troughs.iter().map(|p| p.index).collect::<Vec<_>>());
+/// ```
+pub fn find_peaks(nums: &[f64], min_prominence: f64, min_distance: usize) -> Option<Vec<Peak>> {
+    if has_nan(nums) || min_prominence.is_nan() || min_prominence < 0.0 {
+        return None;
+    }
+    let n = nums.len();
+    let mut peaks = Vec::new();
+    let mut i = 1;
+    while i + 1 < n {
+        if nums[i - 1] < nums[i] {
+            let mut end = i;
+            while end + 1 < n && nums[end + 1] == nums[i] {
+                end += 1;
+            }
+            if end + 1 < n && nums[end + 1] < nums[i] {
+                let index = (i + end) / 2;
+                let prominence = prominence(nums, index);
+                if prominence >= min_prominence {
+                    peaks.push(Peak { index, prominence });
+                }
+            }
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+    if min_distance > 1 {
+        let mut by_height: Vec<usize> = (0..peaks.len()).collect();
+        by_height.sort_by(|&a, &b| nums[peaks[b].index].total_cmp(&nums[peaks[a].index]));
+        let mut kept = vec![true; peaks.len()];
+        for &p in &by_height {
+            if !kept[p] {
+                continue;
+            }
+            //the peaks are in order, so the close ones are adjacent
+            let index = peaks[p].index;
+            let close = |q: &usize| peaks[*q].index.abs_diff(index) < min_distance;
+            for q in (0..p)
+                .rev()
+                .take_while(close)
+                .chain((p + 1..peaks.len()).take_while(close))
+            {
+                kept[q] = false;
+            }
+        }
+        let mut kept = kept.into_iter();
+        peaks.retain(|_| kept.next().unwrap());
+    }
+    Some(peaks)
+}
+
+/// Prominence of the peak of a series at `index`.
+fn prominence(nums: &[f64], index: usize) -> f64 {
+    let height = nums[index];
+    let lowest = |side: &mut dyn Iterator<Item = &f64>| {
+        side.take_while(|&&x| x <= height)
+            .fold(height, |low, &x| low.min(x))
+    };
+    let left = lowest(&mut nums[..index].iter().rev());
+    let right = lowest(&mut nums[index + 1..].iter());
+    height - left.max(right)
+}
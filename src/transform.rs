@@ -0,0 +1,194 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Elementwise transforms of a series of values into a new
+//! series, as commonly applied before computing statistics.
+//!
+//! Running totals have one value for each input. The changes
+//! between values a given `lag` apart have one for each
+//! value from position `lag` on, so a series of changes is
+//! `lag` values shorter than the input, and empty if the
+//! input is no longer than `lag`. Limits on the values, to
+//! tame their tails, keep the input's length.
+
+use alloc::vec::Vec;
+
+use crate::{quantile, Neumaier};
+
+/// Running sums of input values: the `i`th output is the
+/// sum of the first `i + 1` inputs. The sums are compensated
+/// as for [`sum`](crate::sum), so that their rounding
+/// errors do not build up along the series.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// assert_eq!(vec![1.0, 3.0, 6.0], cumsum(&[1.0, 2.0, 3.0]));
+/// let totals = cumsum(&[1.0, 1e100, 1.0, -1e100]);
+/// assert_eq!(2.0, totals[3]);
+/// ```
+pub fn cumsum(nums: &[f64]) -> Vec<f64> {
+    let mut total = Neumaier::new();
+    nums.iter()
+        .map(|&x| {
+            total.add(x);
+            total.total()
+        })
+        .collect()
+}
+
+/// Running products of input values: the `i`th output is
+/// the product of the first `i + 1` inputs.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// // Compounding growth factors.
+/// assert_eq!(vec![1.5, 0.75, 3.0], cumprod(&[1.5, 0.5, 4.0]));
+/// ```
+pub fn cumprod(nums: &[f64]) -> Vec<f64> {
+    let mut product = 1.0;
+    nums.iter()
+        .map(|&x| {
+            product *= x;
+            product
+        })
+        .collect()
+}
+
+/// Differences between values `lag` apart: the `i`th output
+/// is `nums[i + lag] - nums[i]`. Differencing at lag 1
+/// removes a linear trend, and at the period of a seasonal
+/// series removes the seasonality.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// assert_eq!(vec![2.0, 3.0, 4.0], diff(&[1.0, 3.0, 6.0, 10.0], 1));
+/// assert_eq!(vec![5.0, 7.0], diff(&[1.0, 3.0, 6.0, 10.0], 2));
+/// assert!(diff(&[1.0], 1).is_empty());
+/// ```
+pub fn diff(nums: &[f64], lag: usize) -> Vec<f64> {
+    changes(nums, lag, |old, new| new - old)
+}
+
+/// Fractional changes between values `lag` apart: the `i`th
+/// output is `(nums[i + lag] - nums[i]) / nums[i]`, so that
+/// a rise of 5% is 0.05. A change from zero is infinite, or
+/// NaN if to zero.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// assert_eq!(vec![0.5, -0.5], pct_change(&[100.0, 150.0, 75.0], 1));
+/// ```
+pub fn pct_change(nums: &[f64], lag: usize) -> Vec<f64> {
+    changes(nums, lag, |old, new| (new - old) / old)
+}
+
+/// `change` from each value to the value `lag` later.
+fn changes<F: Fn(f64, f64) -> f64>(nums: &[f64], lag: usize, change: F) -> Vec<f64> {
+    if lag >= nums.len() {
+        return Vec::new();
+    }
+    nums.iter()
+        .zip(&nums[lag..])
+        .map(|(&old, &new)| change(old, new))
+        .collect()
+}
+
+/// Values limited to the range `min..=max`: values below
+/// `min` become `min` and values above `max` become `max`.
+/// NaN values stay NaN. Undefined for `min` greater than
+/// `max` and for NaN bounds.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// assert_eq!(Some(vec![0.0, 0.5, 1.0]), clip(&[-3.0, 0.5, 7.0], 0.0, 1.0));
+/// assert_eq!(None, clip(&[1.0], 1.0, 0.0));
+/// ```
+pub fn clip(nums: &[f64], min: f64, max: f64) -> Option<Vec<f64>> {
+    let mut nums = nums.to_vec();
+    clip_mut(&mut nums, min, max)?;
+    Some(nums)
+}
+
+/// Limit values to the range `min..=max` in place, as for
+/// [`clip`]. `None`, leaving the values as they were, where
+/// `clip` is undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// let mut xs = [-3.0, 0.5, 7.0];
+/// clip_mut(&mut xs, 0.0, 1.0).unwrap();
+/// assert_eq!([0.0, 0.5, 1.0], xs);
+/// ```
+pub fn clip_mut(nums: &mut [f64], min: f64, max: f64) -> Option<()> {
+    if min.is_nan() || max.is_nan() || min > max {
+        return None;
+    }
+    for x in nums {
+        if *x < min {
+            *x = min;
+        } else if *x > max {
+            *x = max;
+        }
+    }
+    Some(())
+}
+
+/// Winsorized values: values [`clip`]ped to their own
+/// quantiles `lower_q` and `upper_q`, interpolated linearly
+/// as for [`quantile`](crate::quantile), so that the tails
+/// beyond them are pulled in rather than dropped. Unlike
+/// [`winsorized_mean`](crate::winsorized_mean), which
+/// replaces a count of values, the bounds need not be
+/// values in the list. Undefined for an empty list, for
+/// values containing NaN, and unless `0 <= lower_q <=
+/// upper_q <= 1`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// let xs = [0.0, 1.0, 2.0, 6.0, 100.0, -50.0];
+/// let tamed = winsorize(&xs, 0.2, 0.8).unwrap();
+/// assert_eq!(vec![0.0, 1.0, 2.0, 6.0, 6.0, 0.0], tamed);
+/// assert_eq!(stats::winsorized_mean(&xs, 0.2), stats::mean(&tamed));
+/// ```
+pub fn winsorize(nums: &[f64], lower_q: f64, upper_q: f64) -> Option<Vec<f64>> {
+    let mut nums = nums.to_vec();
+    winsorize_mut(&mut nums, lower_q, upper_q)?;
+    Some(nums)
+}
+
+/// Winsorize values in place, as for [`winsorize`]. `None`,
+/// leaving the values as they were, where `winsorize` is
+/// undefined.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::transform::*;
+/// let mut xs = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// winsorize_mut(&mut xs, 0.25, 0.5).unwrap();
+/// assert_eq!([2.0, 2.0, 3.0, 3.0, 3.0], xs);
+/// ```
+pub fn winsorize_mut(nums: &mut [f64], lower_q: f64, upper_q: f64) -> Option<()> {
+    if lower_q > upper_q {
+        return None;
+    }
+    let min = quantile(nums, lower_q)?;
+    let max = quantile(nums, upper_q)?;
+    clip_mut(nums, min, max)
+}
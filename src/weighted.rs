@@ -0,0 +1,159 @@
+// Copyright © 2019 Liam Rotchford
+// [This program is licensed under the "MIT License"]
+// Please see the file LICENSE in the source
+// distribution of this software for license terms.
+
+//! Weighted statistics of a slice of floating-point values
+//! with a parallel slice of weights.
+//!
+//! Every function here returns `None` when the statistic is
+//! ill-defined: when `values` and `weights` differ in
+//! length, when any weight is negative or NaN, or when the
+//! weights sum to zero (which includes empty input). With
+//! all weights equal, each function agrees with its
+//! unweighted counterpart in the crate root.
+
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::has_nan;
+
+/// Total weight, or `None` if `values` and `weights` are not
+/// a valid weighted sample.
+fn total_weight(values: &[f64], weights: &[f64]) -> Option<f64> {
+    if values.len() != weights.len() || !weights.iter().all(|&w| w >= 0.0) {
+        return None;
+    }
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 {
+        Some(total)
+    } else {
+        None
+    }
+}
+
+/// Weighted arithmetic mean of input values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(None, weighted_mean(&[1.0, 2.0], &[0.0, 0.0]));
+/// ```
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(Some(1.75), weighted_mean(&[1.0, 2.0], &[1.0, 3.0]));
+/// ```
+pub fn weighted_mean(values: &[f64], weights: &[f64]) -> Option<f64> {
+    let total = total_weight(values, weights)?;
+    let sum: f64 = values.iter().zip(weights).map(|(x, w)| x * w).sum();
+    Some(sum / total)
+}
+
+/// Weighted population variance of input values: the
+/// weighted mean of squared deviations from the weighted
+/// mean.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(None, weighted_variance(&[1.0], &[1.0, 1.0]));
+/// ```
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(Some(0.75), weighted_variance(&[1.0, 3.0], &[3.0, 1.0]));
+/// ```
+pub fn weighted_variance(values: &[f64], weights: &[f64]) -> Option<f64> {
+    let total = total_weight(values, weights)?;
+    let meanvalue = weighted_mean(values, weights)?;
+    let sum: f64 = values
+        .iter()
+        .zip(weights)
+        .map(|(x, w)| w * (x - meanvalue).powf(2.0))
+        .sum();
+    Some(sum / total)
+}
+
+/// Weighted population standard deviation of input values.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(Some(4.0), weighted_stddev(&[1.0, 5.0, 9.0], &[1.0, 0.0, 1.0]));
+/// ```
+pub fn weighted_stddev(values: &[f64], weights: &[f64]) -> Option<f64> {
+    weighted_variance(values, weights).map(f64::sqrt)
+}
+
+/// Weighted quantile `q` of input values: the smallest value
+/// whose cumulative weight reaches `q` of the total weight.
+/// When the cumulative weight lands exactly on that target,
+/// the result is the average of that value and the next, so
+/// that equal weights give the ordinary median at `q = 0.5`.
+/// No other interpolation is done. The quantile is undefined
+/// for values containing NaN or for `q` outside `[0, 1]`.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(None, weighted_quantile(&[1.0], &[1.0], 2.0));
+/// ```
+/// ```
+/// # use stats::weighted::*;
+/// let (values, weights) = ([3.0, 1.0, 2.0], [1.0, 1.0, 8.0]);
+/// assert_eq!(Some(1.0), weighted_quantile(&values, &weights, 0.05));
+/// assert_eq!(Some(2.0), weighted_quantile(&values, &weights, 0.8));
+/// assert_eq!(Some(3.0), weighted_quantile(&values, &weights, 0.95));
+/// ```
+pub fn weighted_quantile(values: &[f64], weights: &[f64], q: f64) -> Option<f64> {
+    let total = total_weight(values, weights)?;
+    if has_nan(values) || !(0.0..=1.0).contains(&q) {
+        return None;
+    }
+
+    // Sort the positively-weighted values, carrying weights along.
+    let mut pairs: Vec<(f64, f64)> = values
+        .iter()
+        .copied()
+        .zip(weights.iter().copied())
+        .filter(|&(_, w)| w > 0.0)
+        .collect();
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let target = q * total;
+    let mut cumulative = 0.0;
+    for (i, &(x, w)) in pairs.iter().enumerate() {
+        cumulative += w;
+        if cumulative == target && i + 1 < pairs.len() {
+            return Some((x + pairs[i + 1].0) / 2.0);
+        }
+        if cumulative >= target {
+            return Some(x);
+        }
+    }
+    // Rounding can leave the cumulative sum just short of
+    // the total.
+    pairs.last().map(|&(x, _)| x)
+}
+
+/// Weighted median of input values: the weighted quantile
+/// at 0.5.
+///
+/// # Examples:
+///
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(Some(2.5), weighted_median(&[1.0, 2.0, 3.0, 4.0], &[1.0; 4]));
+/// ```
+/// ```
+/// # use stats::weighted::*;
+/// assert_eq!(Some(4.0), weighted_median(&[1.0, 2.0, 3.0, 4.0], &[1.0, 1.0, 1.0, 5.0]));
+/// ```
+pub fn weighted_median(values: &[f64], weights: &[f64]) -> Option<f64> {
+    weighted_quantile(values, weights, 0.5)
+}